@@ -0,0 +1,64 @@
+//! Cards are numbered 0-51: `card % 13` is the rank (0 = deuce, 12 = ace) and
+//! `card / 13` the suit.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+pub type Card = u8;
+
+pub fn rank(card: Card) -> u8 {
+    card % 13
+}
+
+pub fn suit(card: Card) -> u8 {
+    card / 13
+}
+
+/// A shuffled 52-card deck, dealt from the end
+pub fn shuffled_deck(rng: &mut impl Rng) -> Vec<Card> {
+    let mut deck: Vec<Card> = (0..52).collect();
+    deck.shuffle(rng);
+    deck
+}
+
+/// Preflop strength of two hole cards by the Chen formula, from -1 (72 offsuit)
+/// to 20 (aces)
+pub fn chen_score(hole: [Card; 2]) -> i32 {
+    let (high, low) = if rank(hole[0]) >= rank(hole[1]) {
+        (rank(hole[0]), rank(hole[1]))
+    } else {
+        (rank(hole[1]), rank(hole[0]))
+    };
+
+    // Doubled so the half points for low cards stay whole
+    let card_points = |rank: u8| -> i32 {
+        match rank {
+            12 => 20,
+            11 => 16,
+            10 => 14,
+            9 => 12,
+            _ => rank as i32 + 2,
+        }
+    };
+
+    let mut score = card_points(high);
+    if high == low {
+        return ((score * 2).max(10) + 1).div_euclid(2);
+    }
+    if suit(hole[0]) == suit(hole[1]) {
+        score += 4;
+    }
+    let gap = high - low - 1;
+    score -= match gap {
+        0 => 0,
+        1 => 2,
+        2 => 4,
+        3 => 8,
+        _ => 10,
+    };
+    // Connected and one-gapped cards below a queen can make more straights
+    if gap <= 1 && high < 10 {
+        score += 2;
+    }
+    (score + 1).div_euclid(2)
+}
@@ -0,0 +1,16 @@
+//! Bots that play poker tables in an in-process `anchor-litesvm` VM, for load
+//! testing and offline simulation. There is no runner for live clusters yet.
+//!
+//! - [`strategy`] is the [`Strategy`] trait bots decide with, and the built-in
+//!   strategies: [`AlwaysCall`], [`RandomPlay`] and [`TightAggressive`].
+//! - [`cards`] deals hole cards and scores them; the program doesn't deal cards
+//!   yet, so the runner does.
+//! - [`sim`] runs bots against a table on `anchor-litesvm`, driving hands from
+//!   `start_hand` to the payout.
+
+pub mod cards;
+pub mod sim;
+pub mod strategy;
+
+pub use sim::{Simulation, Step};
+pub use strategy::{AlwaysCall, Decision, Move, RandomPlay, Strategy, TightAggressive};
@@ -0,0 +1,256 @@
+//! Runs bots against a table on `anchor-litesvm`.
+//!
+//! The program doesn't deal or reveal cards yet, so the simulation deals each
+//! bot's hole cards itself and, at showdown, pays the pot to the contender with
+//! the best preflop hand by [`cards::chen_score`].
+
+use anchor_litesvm::{Instruction, Keypair, LiteSVM, Pubkey, Signer, TransactionHelpers};
+use anyhow::{anyhow, bail, Context, Result};
+use poker::PlayerState;
+use poker_client::{engine, instructions, state, GameState, PlayerStatus, TableView};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::cards::{self, Card};
+use crate::strategy::{Decision, Move, Strategy};
+
+// Most transactions a single hand may take before the simulation gives up on it
+const MAX_STEPS_PER_HAND: usize = 1000;
+
+struct Bot {
+    keypair: Keypair,
+    strategy: Box<dyn Strategy>,
+    hole_cards: [Card; 2],
+}
+
+/// What a call to [`Simulation::step`] did
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Step {
+    Left { seat: u8 },
+    Started { hand_number: u64 },
+    PostedBlind { seat: u8 },
+    PostedMissedBlinds { seat: u8 },
+    Acted { seat: u8, action: Move },
+    Advanced { state: GameState },
+    Finished { winner: u8 },
+}
+
+pub struct Simulation {
+    svm: LiteSVM,
+    table: Pubkey,
+    // Pays for the transactions no player has to sign (starting hands, moving streets on)
+    dealer: Keypair,
+    bots: Vec<Bot>,
+    rng: StdRng,
+}
+
+impl Simulation {
+    /// `svm` must have the poker program loaded and the table created; `dealer`
+    /// must be funded. `seed` fixes the cards dealt.
+    pub fn new(svm: LiteSVM, table: Pubkey, dealer: Keypair, seed: u64) -> Self {
+        Simulation { svm, table, dealer, bots: Vec::new(), rng: StdRng::seed_from_u64(seed) }
+    }
+
+    pub fn svm(&self) -> &LiteSVM {
+        &self.svm
+    }
+
+    pub fn svm_mut(&mut self) -> &mut LiteSVM {
+        &mut self.svm
+    }
+
+    /// Seat a funded player at `seat` and let `strategy` play for them
+    pub fn join(
+        &mut self,
+        keypair: Keypair,
+        seat: u8,
+        buy_in: u64,
+        strategy: impl Strategy + 'static,
+    ) -> Result<()> {
+        let view = self.table()?;
+        let ix = instructions::join_table(&view, &keypair.pubkey(), buy_in, seat);
+        send(&mut self.svm, ix, &keypair).context("join_table")?;
+        self.bots.push(Bot { keypair, strategy: Box::new(strategy), hole_cards: [0; 2] });
+        Ok(())
+    }
+
+    pub fn table(&self) -> Result<TableView> {
+        let account = self.svm.get_account(&self.table).ok_or_else(|| anyhow!("table not found"))?;
+        Ok(TableView::decode(self.table, &account.data)?)
+    }
+
+    /// States of everyone seated, in seat order
+    pub fn player_states(&self, view: &TableView) -> Result<Vec<PlayerState>> {
+        view.seated()
+            .map(|(_, player)| {
+                let address = view.player_state_address(&player);
+                let account = self
+                    .svm
+                    .get_account(&address)
+                    .ok_or_else(|| anyhow!("player state {address} not found"))?;
+                Ok(state::decode_player_state(&account.data)?)
+            })
+            .collect()
+    }
+
+    /// Hole cards dealt to `player` for the current hand
+    pub fn hole_cards(&self, player: &Pubkey) -> Option<[Card; 2]> {
+        self.bot(player).map(|bot| bot.hole_cards)
+    }
+
+    /// Play hands until one fails or `hands` have been played, returning each winner's seat
+    pub fn play(&mut self, hands: usize) -> Result<Vec<u8>> {
+        (0..hands).map(|_| self.play_hand()).collect()
+    }
+
+    /// Play the next hand through to its payout, returning the winner's seat
+    pub fn play_hand(&mut self) -> Result<u8> {
+        for _ in 0..MAX_STEPS_PER_HAND {
+            if let Step::Finished { winner } = self.step()? {
+                return Ok(winner);
+            }
+        }
+        bail!("hand didn't finish within {MAX_STEPS_PER_HAND} steps")
+    }
+
+    /// Look at the table and send the one transaction that moves the hand on
+    pub fn step(&mut self) -> Result<Step> {
+        let view = self.table()?;
+        let table = &view.table;
+        let players = self.player_states(&view)?;
+
+        if table.game_state.street().is_none() && table.game_state != GameState::Showdown {
+            // Bots that have lost their stack stand up before the next deal
+            if let Some(busted) = players.iter().find(|p| p.stack == 0 && self.bot(&p.player).is_some()) {
+                let keypair = keypair_at(&self.bots, &view, busted.position)?;
+                send(&mut self.svm, instructions::leave_table(&view, &keypair.pubkey()), keypair)
+                    .context("leave_table")?;
+                return Ok(Step::Left { seat: busted.position });
+            }
+            send(&mut self.svm, instructions::start_hand(&view), &self.dealer).context("start_hand")?;
+            self.deal(&view);
+            return Ok(Step::Started { hand_number: table.hand_number + 1 });
+        }
+
+        let contenders: Vec<&PlayerState> = players
+            .iter()
+            .filter(|player| matches!(player.status, PlayerStatus::Active | PlayerStatus::AllIn))
+            .collect();
+        if let [winner] = contenders[..] {
+            return self.finish(&view, &players, winner);
+        }
+
+        if table.game_state == GameState::PreFlop {
+            let (small_blind, big_blind) = table.blind_seats()?;
+            for seat in [small_blind, big_blind] {
+                if table.blinds_posted & (1u16 << seat) == 0 {
+                    let keypair = keypair_at(&self.bots, &view, seat)?;
+                    send(&mut self.svm, instructions::post_blinds(&view, &keypair.pubkey()), keypair)
+                        .context("post_blinds")?;
+                    return Ok(Step::PostedBlind { seat });
+                }
+            }
+        }
+
+        let seats: Vec<engine::Seat> = players.iter().map(PlayerState::seat).collect();
+        if table.game_state == GameState::Showdown
+            || (table.game_state == GameState::River && engine::is_round_complete(table.current_bet, &seats))
+        {
+            let best = contenders
+                .iter()
+                .max_by_key(|player| self.hole_cards(&player.player).map(cards::chen_score))
+                .ok_or_else(|| anyhow!("no one left in the hand"))?;
+            return self.finish(&view, &players, best);
+        }
+        if engine::is_round_complete(table.current_bet, &seats) {
+            send(&mut self.svm, instructions::advance_street_auto(&view), &self.dealer)
+                .context("advance_street_auto")?;
+            return Ok(Step::Advanced { state: self.table()?.table.game_state });
+        }
+
+        self.act(&view, &players)
+    }
+
+    // The player to act owes missed blinds or picks a move
+    fn act(&mut self, view: &TableView, players: &[PlayerState]) -> Result<Step> {
+        let table = &view.table;
+        let seat = table.current_player_index;
+        let player = players
+            .iter()
+            .find(|player| player.position == seat)
+            .ok_or_else(|| anyhow!("no player state for seat {seat}"))?;
+
+        if (table.missed_small_blinds | table.missed_big_blinds) & (1u16 << seat) != 0 {
+            let keypair = keypair_at(&self.bots, view, seat)?;
+            send(&mut self.svm, instructions::post_missed_blinds(view, &keypair.pubkey(), true), keypair)
+                .context("post_missed_blinds")?;
+            return Ok(Step::PostedMissedBlinds { seat });
+        }
+
+        let legal = view.legal_actions(player)?;
+        let bot = self
+            .bots
+            .iter_mut()
+            .find(|bot| bot.keypair.pubkey() == player.player)
+            .ok_or_else(|| anyhow!("seat {seat} isn't played by a bot"))?;
+        let decision = Decision { table, player, legal: &legal, hole_cards: bot.hole_cards };
+        let action = bot.strategy.decide(&decision);
+
+        let ix = instructions::player_action(view, &player.player, action.action, action.raise_amount);
+        let keypair = keypair_at(&self.bots, view, seat)?;
+        send(&mut self.svm, ix, keypair).with_context(|| format!("player_action {action:?}"))?;
+        Ok(Step::Acted { seat, action })
+    }
+
+    // Pay the pot to `winner`: by default when everyone else folded, else as the showdown winner.
+    // check_auto_win only pays while the hand is being bet, so anyone all in goes to end_hand.
+    fn finish(&mut self, view: &TableView, players: &[PlayerState], winner: &PlayerState) -> Result<Step> {
+        let everyone_folded = winner.status == PlayerStatus::Active
+            && view.table.game_state != GameState::Showdown
+            && players.iter().all(|player| {
+                player.player == winner.player
+                    || !matches!(player.status, PlayerStatus::Active | PlayerStatus::AllIn)
+            });
+        let ix = if everyone_folded {
+            instructions::check_auto_win(view)
+        } else {
+            instructions::end_hand(view, winner.position)
+                .ok_or_else(|| anyhow!("winning seat {} is empty", winner.position))?
+        };
+        send(&mut self.svm, ix, &self.dealer).context("paying out the hand")?;
+        if self.table()?.table.game_state != GameState::HandComplete {
+            bail!("hand {} didn't complete", view.table.hand_number);
+        }
+        Ok(Step::Finished { winner: winner.position })
+    }
+
+    // Give every bot two new cards
+    fn deal(&mut self, view: &TableView) {
+        let mut deck = cards::shuffled_deck(&mut self.rng);
+        for (_, player) in view.seated() {
+            if let Some(bot) = self.bots.iter_mut().find(|bot| bot.keypair.pubkey() == player) {
+                bot.hole_cards = [deck.pop().unwrap(), deck.pop().unwrap()];
+            }
+        }
+    }
+
+    fn bot(&self, player: &Pubkey) -> Option<&Bot> {
+        self.bots.iter().find(|bot| bot.keypair.pubkey() == *player)
+    }
+
+}
+
+fn keypair_at<'a>(bots: &'a [Bot], view: &TableView, seat: u8) -> Result<&'a Keypair> {
+    let player = view.player_at(seat).ok_or_else(|| anyhow!("seat {seat} is empty"))?;
+    bots.iter()
+        .find(|bot| bot.keypair.pubkey() == player)
+        .map(|bot| &bot.keypair)
+        .ok_or_else(|| anyhow!("seat {seat} isn't played by a bot"))
+}
+
+// Fresh blockhash each time so repeated moves aren't rejected as duplicates
+fn send(svm: &mut LiteSVM, ix: Instruction, payer: &Keypair) -> Result<()> {
+    svm.expire_blockhash();
+    svm.send_instruction(ix, &[payer]).map_err(|err| anyhow!("{err}"))?;
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! How bots choose an action.
+
+use poker::{LegalActionsView, PlayerState, PokerTable};
+use poker_client::PlayerActionType;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::cards::{self, Card};
+
+/// Everything a bot sees when it's their turn
+pub struct Decision<'a> {
+    pub table: &'a PokerTable,
+    pub player: &'a PlayerState,
+    pub legal: &'a LegalActionsView,
+    pub hole_cards: [Card; 2],
+}
+
+/// An action to submit with `player_action`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Move {
+    pub action: PlayerActionType,
+    pub raise_amount: u64,
+}
+
+impl Move {
+    pub fn fold() -> Self {
+        Move { action: PlayerActionType::Fold, raise_amount: 0 }
+    }
+
+    pub fn check() -> Self {
+        Move { action: PlayerActionType::Check, raise_amount: 0 }
+    }
+
+    pub fn call() -> Self {
+        Move { action: PlayerActionType::Call, raise_amount: 0 }
+    }
+
+    /// Raise by `amount` over the current bet
+    pub fn raise(amount: u64) -> Self {
+        Move { action: PlayerActionType::Raise, raise_amount: amount }
+    }
+
+    /// Check if that's free, otherwise fold
+    pub fn check_or_fold(legal: &LegalActionsView) -> Self {
+        if legal.can_check { Move::check() } else { Move::fold() }
+    }
+
+    /// Check if that's free, otherwise call
+    pub fn check_or_call(legal: &LegalActionsView) -> Self {
+        if legal.can_check {
+            Move::check()
+        } else if legal.can_call {
+            Move::call()
+        } else {
+            Move::fold()
+        }
+    }
+}
+
+/// Decides what a bot does when it's their turn. The move must be one
+/// `decision.legal` allows, or the program rejects it.
+pub trait Strategy {
+    fn decide(&mut self, decision: &Decision) -> Move;
+}
+
+/// Never folds or raises
+pub struct AlwaysCall;
+
+impl Strategy for AlwaysCall {
+    fn decide(&mut self, decision: &Decision) -> Move {
+        Move::check_or_call(decision.legal)
+    }
+}
+
+/// Picks uniformly among the legal actions, raising a random legal amount
+pub struct RandomPlay {
+    rng: StdRng,
+}
+
+impl RandomPlay {
+    pub fn new(seed: u64) -> Self {
+        RandomPlay { rng: StdRng::seed_from_u64(seed) }
+    }
+}
+
+impl Strategy for RandomPlay {
+    fn decide(&mut self, decision: &Decision) -> Move {
+        let legal = decision.legal;
+        let mut moves = vec![Move::check_or_call(legal)];
+        if !legal.can_check {
+            moves.push(Move::fold());
+        }
+        if legal.can_raise {
+            moves.push(Move::raise(self.rng.gen_range(legal.min_raise..=legal.max_raise)));
+        }
+        moves[self.rng.gen_range(0..moves.len())]
+    }
+}
+
+/// Plays only strong hole cards and bets them hard, judged by their Chen score
+pub struct TightAggressive {
+    /// Weakest hand that calls
+    pub play_score: i32,
+    /// Weakest hand that raises
+    pub raise_score: i32,
+}
+
+impl Default for TightAggressive {
+    // Roughly the top fifth of hands play, pairs of tens or better and suited ace-jack or better raise
+    fn default() -> Self {
+        TightAggressive { play_score: 8, raise_score: 10 }
+    }
+}
+
+impl Strategy for TightAggressive {
+    fn decide(&mut self, decision: &Decision) -> Move {
+        let legal = decision.legal;
+        let score = cards::chen_score(decision.hole_cards);
+
+        if score >= self.raise_score && legal.can_raise {
+            // Bet the pot, within what the table allows
+            let pot_raise = decision.table.pot.max(legal.min_raise);
+            return Move::raise(pot_raise.min(legal.max_raise));
+        }
+        if score >= self.play_score {
+            return Move::check_or_call(legal);
+        }
+        Move::check_or_fold(legal)
+    }
+}
@@ -0,0 +1,124 @@
+use anchor_litesvm::{Keypair, LiteSVM, Pubkey, Signer, TransactionHelpers};
+use poker::{ID as POKER_PROGRAM_ID, MAX_BUY_IN_DEFAULT};
+use poker_bot::cards::chen_score;
+use poker_bot::{AlwaysCall, RandomPlay, Simulation, Step, TightAggressive};
+use poker_client::{instructions, pda, GameState, TableVisibility};
+
+const SOL: u64 = 1_000_000_000;
+
+// Cards are rank + 13 * suit, deuce = 0 and ace = 12
+const fn card(rank: u8, suit: u8) -> u8 {
+    rank + 13 * suit
+}
+
+#[test]
+fn test_chen_score_ranks_hole_cards() {
+    let aces = chen_score([card(12, 0), card(12, 1)]);
+    let ace_king_suited = chen_score([card(12, 2), card(11, 2)]);
+    let deuces = chen_score([card(0, 0), card(0, 3)]);
+    let seven_deuce = chen_score([card(5, 0), card(0, 1)]);
+    let nines = chen_score([card(7, 0), card(7, 1)]);
+    let tens = chen_score([card(8, 0), card(8, 1)]);
+
+    assert_eq!(aces, 20);
+    assert_eq!(ace_king_suited, 12);
+    assert_eq!(deuces, 5);
+    assert_eq!(seven_deuce, -1);
+
+    // Tens are the smallest pair the default tight-aggressive bot raises with
+    let raise_score = TightAggressive::default().raise_score;
+    assert!(nines < raise_score);
+    assert!(tens >= raise_score);
+}
+
+// Three bots, one of each strategy, seated at a fresh table with 2000 chips each
+fn three_bots() -> (Simulation, Vec<Pubkey>) {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(POKER_PROGRAM_ID, "../../target/deploy/poker.so").unwrap();
+
+    let dealer = Keypair::new();
+    svm.airdrop(&dealer.pubkey(), 10 * SOL).unwrap();
+    let table_id = 1u64;
+    let settings = instructions::TableSettings {
+        small_blind: 10,
+        big_blind: 20,
+        min_buy_in: 400,
+        max_buy_in: MAX_BUY_IN_DEFAULT,
+        max_seats: 6,
+        visibility: TableVisibility::Public,
+    };
+    let ix = instructions::initialize_table(&dealer.pubkey(), table_id, settings);
+    svm.send_instruction(ix, &[&dealer]).unwrap().assert_success();
+
+    let players: Vec<Keypair> = (0..3).map(|_| Keypair::new()).collect();
+    for player in &players {
+        svm.airdrop(&player.pubkey(), 10 * SOL).unwrap();
+    }
+    let keys = players.iter().map(|player| player.pubkey()).collect();
+
+    let table = pda::table(table_id).0;
+    let mut sim = Simulation::new(svm, table, dealer, 7);
+    let mut players = players.into_iter();
+    sim.join(players.next().unwrap(), 0, 2000, AlwaysCall).unwrap();
+    sim.join(players.next().unwrap(), 1, 2000, RandomPlay::new(7)).unwrap();
+    sim.join(players.next().unwrap(), 2, 2000, TightAggressive::default()).unwrap();
+    (sim, keys)
+}
+
+// Play until `max_hands` are done or one bot has everything, returning each winner's seat
+fn play_while_contested(sim: &mut Simulation, max_hands: usize) -> Vec<u8> {
+    let mut winners = Vec::new();
+    while winners.len() < max_hands && stacks(sim).iter().filter(|&&stack| stack > 0).count() >= 2 {
+        winners.push(sim.play_hand().unwrap());
+    }
+    winners
+}
+
+fn stacks(sim: &Simulation) -> Vec<u64> {
+    sim.player_states(&sim.table().unwrap()).unwrap().iter().map(|p| p.stack).collect()
+}
+
+#[test]
+fn test_bots_take_their_seats() {
+    let (sim, players) = three_bots();
+    let view = sim.table().unwrap();
+    assert_eq!(view.seated().map(|(_, player)| player).collect::<Vec<_>>(), players);
+    assert_eq!(stacks(&sim), [2000, 2000, 2000]);
+}
+
+#[test]
+fn test_starting_a_hand_deals_every_bot_in() {
+    let (mut sim, players) = three_bots();
+    assert_eq!(sim.step().unwrap(), Step::Started { hand_number: 1 });
+    let dealt: Vec<u8> = players.iter().flat_map(|player| sim.hole_cards(player).unwrap()).collect();
+    let mut distinct = dealt.clone();
+    distinct.sort();
+    distinct.dedup();
+    assert_eq!(distinct.len(), dealt.len());
+
+    // The blinds go in before anyone acts
+    assert!(matches!(sim.step().unwrap(), Step::PostedBlind { .. }));
+    assert!(matches!(sim.step().unwrap(), Step::PostedBlind { .. }));
+    assert!(matches!(sim.step().unwrap(), Step::Acted { .. }));
+}
+
+#[test]
+fn test_each_hand_pays_a_seated_winner() {
+    let (mut sim, _) = three_bots();
+    let winners = play_while_contested(&mut sim, 3);
+    assert!(!winners.is_empty());
+    assert!(winners.iter().all(|&winner| winner < 3));
+
+    let view = sim.table().unwrap();
+    assert_eq!(view.table.game_state, GameState::HandComplete);
+    assert_eq!(view.table.hand_number, winners.len() as u64);
+    assert_eq!(view.table.pot, 0);
+}
+
+#[test]
+fn test_bots_never_make_or_lose_chips() {
+    let (mut sim, _) = three_bots();
+    let chips_before: u64 = stacks(&sim).iter().sum();
+    play_while_contested(&mut sim, 10);
+    assert_eq!(stacks(&sim).iter().sum::<u64>(), chips_before);
+}
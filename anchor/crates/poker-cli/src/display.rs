@@ -0,0 +1,93 @@
+//! Human-readable rendering of a table and its players.
+
+use std::fmt::Write;
+
+use poker::PlayerState;
+use poker_client::{GameState, TableView};
+
+// Board cards are shown as the raw values the program stores; it doesn't fix an encoding yet
+fn board(view: &TableView) -> String {
+    let dealt = match view.table.game_state {
+        GameState::Flop => 3,
+        GameState::Turn => 4,
+        GameState::River | GameState::Showdown => 5,
+        _ => 0,
+    };
+    if dealt == 0 {
+        return "-".to_string();
+    }
+    format!("{:?}", &view.table.community_cards[..dealt])
+}
+
+/// The table header followed by one line per seat; `players` are the seated
+/// players' states in any order
+pub fn table(view: &TableView, players: &[PlayerState]) -> String {
+    let table = &view.table;
+    let mut out = String::new();
+
+    let _ = writeln!(out, "Table {} ({})", table.table_id, view.address);
+    let _ = writeln!(
+        out,
+        "  Blinds {}/{}{}  Buy-in {}-{}  Seats {}/{}{}",
+        table.small_blind,
+        table.big_blind,
+        if table.ante > 0 { format!(" ante {}", table.ante) } else { String::new() },
+        table.min_buy_in,
+        table.max_buy_in,
+        table.player_count,
+        table.max_seats,
+        if table.paused { "  PAUSED" } else { "" },
+    );
+    let _ = writeln!(
+        out,
+        "  Hand #{}  {:?}  Pot {}  Bet {}  Board {}",
+        table.hand_number,
+        table.game_state,
+        table.pot,
+        table.current_bet,
+        board(view),
+    );
+    if table.action_timeout > 0 {
+        let _ = writeln!(
+            out,
+            "  Action timeout {}s (turn started at {})",
+            table.action_timeout, table.turn_started_at
+        );
+    }
+
+    let to_act = view.to_act().map(|(seat, _)| seat);
+    for seat in 0..table.max_seats {
+        let marker = match (to_act == Some(seat), table.button_position == seat) {
+            (true, true) => "> D",
+            (true, false) => ">  ",
+            (false, true) => "  D",
+            (false, false) => "   ",
+        };
+        let Some(player) = view.player_at(seat) else {
+            let _ = writeln!(out, "{marker} {seat}: empty");
+            continue;
+        };
+        let _ = write!(out, "{marker} {seat}: {player}");
+        match players.iter().find(|state| state.player == player) {
+            Some(state) => {
+                let _ = write!(out, "  stack {}  bet {}  {:?}", state.stack, state.current_bet, state.status);
+            }
+            None => {
+                let _ = write!(out, "  (no player state)");
+            }
+        }
+        let bit = 1u16 << seat;
+        if table.sitting_out & bit != 0 {
+            let _ = write!(out, "  sitting out");
+        }
+        if (table.missed_small_blinds | table.missed_big_blinds) & bit != 0 {
+            let _ = write!(out, "  owes blinds");
+        }
+        if table.leaving & bit != 0 {
+            let _ = write!(out, "  leaving");
+        }
+        out.push('\n');
+    }
+
+    out
+}
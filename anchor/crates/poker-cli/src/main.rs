@@ -0,0 +1,215 @@
+//! Command line client for poker tables.
+//!
+//! Works against any RPC endpoint, including a local test validator:
+//!
+//! ```text
+//! poker --url http://127.0.0.1:8899 create-table 1 --small-blind 10 --big-blind 20
+//! poker join 1 --buy-in 1000 --seat 0
+//! poker act 1 raise 60
+//! poker show 1
+//! ```
+
+mod display;
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use poker::PlayerState;
+use poker_client::{instructions, pda, state, PlayerActionType, TableView, TableVisibility};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser)]
+#[command(name = "poker", about = "Create, play and inspect poker tables")]
+struct Cli {
+    /// RPC endpoint
+    #[arg(long, short = 'u', global = true, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Keypair that signs and pays (defaults to the Solana CLI keypair)
+    #[arg(long, short = 'k', global = true)]
+    keypair: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a table
+    CreateTable {
+        table_id: u64,
+        #[arg(long)]
+        small_blind: u64,
+        #[arg(long)]
+        big_blind: u64,
+        /// Defaults to 20 big blinds
+        #[arg(long)]
+        min_buy_in: Option<u64>,
+        /// Defaults to 100 big blinds
+        #[arg(long)]
+        max_buy_in: Option<u64>,
+        #[arg(long, default_value_t = 9)]
+        max_seats: u8,
+        /// Only seat players you invite
+        #[arg(long)]
+        private: bool,
+    },
+    /// Let a player sit at your private table
+    Invite { table_id: u64, player: Pubkey },
+    /// Sit down at a table
+    Join {
+        table_id: u64,
+        #[arg(long)]
+        buy_in: u64,
+        #[arg(long)]
+        seat: u8,
+    },
+    /// Deal the next hand
+    StartHand { table_id: u64 },
+    /// Post your blind for the current hand
+    PostBlinds { table_id: u64 },
+    /// Act when it's your turn
+    Act {
+        table_id: u64,
+        action: ActionArg,
+        /// Chips to raise by, on top of the current bet
+        amount: Option<u64>,
+    },
+    /// Check or fold for a player who has run out of time
+    CrankTimeout { table_id: u64 },
+    /// Print a table and its players
+    Show { table_id: u64 },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ActionArg {
+    Fold,
+    Check,
+    Call,
+    Raise,
+}
+
+impl From<ActionArg> for PlayerActionType {
+    fn from(action: ActionArg) -> Self {
+        match action {
+            ActionArg::Fold => PlayerActionType::Fold,
+            ActionArg::Check => PlayerActionType::Check,
+            ActionArg::Call => PlayerActionType::Call,
+            ActionArg::Raise => PlayerActionType::Raise,
+        }
+    }
+}
+
+struct Client {
+    rpc: RpcClient,
+    payer: Keypair,
+}
+
+impl Client {
+    fn table(&self, table_id: u64) -> Result<TableView> {
+        let (address, _) = pda::table(table_id);
+        let data = self
+            .rpc
+            .get_account_data(&address)
+            .with_context(|| format!("table {table_id} ({address}) not found"))?;
+        Ok(TableView::decode(address, &data)?)
+    }
+
+    // Player states of everyone seated, in seat order
+    fn player_states(&self, view: &TableView) -> Result<Vec<PlayerState>> {
+        let addresses: Vec<Pubkey> = view
+            .seated()
+            .map(|(_, player)| view.player_state_address(&player))
+            .collect();
+        self.rpc
+            .get_multiple_accounts(&addresses)?
+            .into_iter()
+            .zip(&addresses)
+            .map(|(account, address)| {
+                let account = account.ok_or_else(|| anyhow!("player state {address} not found"))?;
+                Ok(state::decode_player_state(&account.data)?)
+            })
+            .collect()
+    }
+
+    fn send(&self, instruction: Instruction) -> Result<()> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        );
+        let signature = self.rpc.send_and_confirm_transaction(&transaction)?;
+        println!("Signature: {signature}");
+        Ok(())
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let keypair_path = match cli.keypair {
+        Some(path) => path,
+        None => {
+            let home = std::env::var("HOME").context("HOME not set; pass --keypair")?;
+            format!("{home}/.config/solana/id.json")
+        }
+    };
+    let client = Client {
+        rpc: RpcClient::new_with_commitment(cli.url, CommitmentConfig::confirmed()),
+        payer: read_keypair_file(&keypair_path)
+            .map_err(|err| anyhow!("failed to read keypair {keypair_path}: {err}"))?,
+    };
+    let me = client.payer.pubkey();
+
+    match cli.command {
+        Command::CreateTable { table_id, small_blind, big_blind, min_buy_in, max_buy_in, max_seats, private } => {
+            let settings = instructions::TableSettings {
+                small_blind,
+                big_blind,
+                min_buy_in: min_buy_in.unwrap_or(big_blind * 20),
+                max_buy_in: max_buy_in.unwrap_or(big_blind * 100),
+                max_seats,
+                visibility: if private { TableVisibility::Private } else { TableVisibility::Public },
+            };
+            client.send(instructions::initialize_table(&me, table_id, settings))?;
+            println!("Created table {table_id} at {}", pda::table(table_id).0);
+        }
+        Command::Invite { table_id, player } => {
+            client.send(instructions::invite_player(&pda::table(table_id).0, &me, player))?;
+            println!("Invited {player} to table {table_id}");
+        }
+        Command::Join { table_id, buy_in, seat } => {
+            let view = client.table(table_id)?;
+            client.send(instructions::join_table(&view, &me, buy_in, seat))?;
+        }
+        Command::StartHand { table_id } => {
+            client.send(instructions::start_hand(&client.table(table_id)?))?;
+        }
+        Command::PostBlinds { table_id } => {
+            client.send(instructions::post_blinds(&client.table(table_id)?, &me))?;
+        }
+        Command::Act { table_id, action, amount } => {
+            let raise_amount = match (action, amount) {
+                (ActionArg::Raise, Some(amount)) => amount,
+                (ActionArg::Raise, None) => bail!("raise needs an amount"),
+                (_, Some(_)) => bail!("only a raise takes an amount"),
+                (_, None) => 0,
+            };
+            let view = client.table(table_id)?;
+            client.send(instructions::player_action(&view, &me, action.into(), raise_amount))?;
+        }
+        Command::CrankTimeout { table_id } => {
+            client.send(instructions::crank_timeout(&client.table(table_id)?))?;
+        }
+        Command::Show { table_id } => {
+            let view = client.table(table_id)?;
+            let players = client.player_states(&view)?;
+            print!("{}", display::table(&view, &players));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,689 @@
+//! Typed builders for every program instruction.
+//!
+//! Builders that act on a table take a [`TableView`] so the right `remaining_accounts`
+//! can be attached from the table's current configuration.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use poker::{accounts, instruction};
+
+use crate::state::{TableView, Tournament};
+use crate::{pda, remaining};
+use crate::{
+    BlindLevel, BountyType, DealKind, LevelDurationUnit, PlayerActionType, RakeAttribution,
+    TableConfigUpdate, TableVisibility, PROGRAM_ID,
+};
+
+fn build(
+    accounts: impl ToAccountMetas,
+    args: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining_accounts);
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: metas,
+        data: args.data(),
+    }
+}
+
+// Table management
+
+/// Arguments for [`initialize_table`]
+#[derive(Clone, Debug)]
+pub struct TableSettings {
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub min_buy_in: u64,
+    pub max_buy_in: u64,
+    pub max_seats: u8,
+    pub visibility: TableVisibility,
+}
+
+pub fn initialize_table(creator: &Pubkey, table_id: u64, settings: TableSettings) -> Instruction {
+    build(
+        accounts::InitializeTable {
+            table: pda::table(table_id).0,
+            creator: *creator,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeTable {
+            table_id,
+            small_blind: settings.small_blind,
+            big_blind: settings.big_blind,
+            min_buy_in: settings.min_buy_in,
+            max_buy_in: settings.max_buy_in,
+            max_seats: settings.max_seats,
+            visibility: settings.visibility,
+        },
+        Vec::new(),
+    )
+}
+
+// Private tables check the player's invite
+fn invite_account(view: &TableView, player: &Pubkey) -> Option<Pubkey> {
+    (view.table.visibility == TableVisibility::Private).then(|| pda::invite(&view.address, player).0)
+}
+
+pub fn join_table(
+    view: &TableView,
+    player: &Pubkey,
+    buy_in_amount: u64,
+    position: u8,
+) -> Instruction {
+    build(
+        accounts::JoinTable {
+            table: view.address,
+            player_state: view.player_state_address(player),
+            invite: invite_account(view, player),
+            player: *player,
+            system_program: system_program::ID,
+        },
+        instruction::JoinTable { buy_in_amount, position },
+        remaining::join_table(view),
+    )
+}
+
+pub fn leave_table(view: &TableView, player: &Pubkey) -> Instruction {
+    build(
+        accounts::LeaveTable {
+            table: view.address,
+            player_state: view.player_state_address(player),
+            player: *player,
+        },
+        instruction::LeaveTable {},
+        remaining::betting(view),
+    )
+}
+
+fn sit_out_accounts(view: &TableView, player: &Pubkey) -> accounts::SitOut {
+    accounts::SitOut {
+        table: view.address,
+        player_state: view.player_state_address(player),
+        player: *player,
+    }
+}
+
+pub fn sit_out(view: &TableView, player: &Pubkey) -> Instruction {
+    build(sit_out_accounts(view, player), instruction::SitOut {}, Vec::new())
+}
+
+pub fn sit_in(view: &TableView, player: &Pubkey) -> Instruction {
+    build(sit_out_accounts(view, player), instruction::SitIn {}, Vec::new())
+}
+
+pub fn top_up(view: &TableView, player: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::TopUp {
+            table: view.address,
+            player_state: view.player_state_address(player),
+            player: *player,
+            system_program: system_program::ID,
+        },
+        instruction::TopUp { amount },
+        Vec::new(),
+    )
+}
+
+fn configure_accounts(table: &Pubkey, authority: &Pubkey) -> accounts::ConfigureTable {
+    accounts::ConfigureTable { table: *table, authority: *authority }
+}
+
+pub fn update_table_config(table: &Pubkey, authority: &Pubkey, update: TableConfigUpdate) -> Instruction {
+    build(configure_accounts(table, authority), instruction::UpdateTableConfig { update }, Vec::new())
+}
+
+pub fn set_table_authority(table: &Pubkey, authority: &Pubkey, new_authority: Pubkey) -> Instruction {
+    build(configure_accounts(table, authority), instruction::SetTableAuthority { new_authority }, Vec::new())
+}
+
+pub fn invite_player(table: &Pubkey, authority: &Pubkey, player: Pubkey) -> Instruction {
+    build(
+        accounts::InvitePlayer {
+            table: *table,
+            invite: pda::invite(table, &player).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InvitePlayer { player },
+        Vec::new(),
+    )
+}
+
+pub fn revoke_invite(table: &Pubkey, authority: &Pubkey, player: &Pubkey) -> Instruction {
+    build(
+        accounts::RevokeInvite {
+            table: *table,
+            invite: pda::invite(table, player).0,
+            authority: *authority,
+        },
+        instruction::RevokeInvite {},
+        Vec::new(),
+    )
+}
+
+pub fn close_table(view: &TableView, authority: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseTable {
+            table: view.address,
+            creator: view.table.creator,
+            authority: *authority,
+        },
+        instruction::CloseTable {},
+        remaining::close_table(view),
+    )
+}
+
+pub fn join_waitlist(view: &TableView, player: &Pubkey) -> Instruction {
+    build(
+        accounts::JoinWaitlist {
+            table: view.address,
+            waitlist: pda::waitlist(&view.address).0,
+            invite: invite_account(view, player),
+            player: *player,
+            system_program: system_program::ID,
+        },
+        instruction::JoinWaitlist {},
+        Vec::new(),
+    )
+}
+
+pub fn leave_waitlist(table: &Pubkey, player: &Pubkey) -> Instruction {
+    build(
+        accounts::LeaveWaitlist { waitlist: pda::waitlist(table).0, player: *player },
+        instruction::LeaveWaitlist {},
+        Vec::new(),
+    )
+}
+
+// Hand history
+
+/// Opens the history for the next hand dealt at the table
+pub fn open_hand_history(view: &TableView, authority: &Pubkey) -> Instruction {
+    build(
+        accounts::OpenHandHistory {
+            table: view.address,
+            hand_history: pda::hand_history(&view.address, view.table.hand_number + 1).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::OpenHandHistory {},
+        Vec::new(),
+    )
+}
+
+pub fn close_hand_history(table: &Pubkey, hand_number: u64, payer: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseHandHistory {
+            table: *table,
+            hand_history: pda::hand_history(table, hand_number).0,
+            payer: *payer,
+        },
+        instruction::CloseHandHistory {},
+        Vec::new(),
+    )
+}
+
+// Rake
+
+/// Arguments for [`set_rake`]
+#[derive(Clone, Copy, Debug)]
+pub struct RakeSettings {
+    pub rake_bps: u16,
+    pub rake_cap: u64,
+    pub no_flop_no_drop: bool,
+    pub rake_authority: Pubkey,
+    pub rakeback_bps: u16,
+    pub rake_attribution: RakeAttribution,
+}
+
+pub fn set_rake(table: &Pubkey, authority: &Pubkey, settings: RakeSettings) -> Instruction {
+    build(
+        accounts::SetRake {
+            table: *table,
+            fee_vault: pda::fee_vault(table).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::SetRake {
+            rake_bps: settings.rake_bps,
+            rake_cap: settings.rake_cap,
+            no_flop_no_drop: settings.no_flop_no_drop,
+            rake_authority: settings.rake_authority,
+            rakeback_bps: settings.rakeback_bps,
+            rake_attribution: settings.rake_attribution,
+        },
+        Vec::new(),
+    )
+}
+
+pub fn withdraw_rake(table: &Pubkey, rake_authority: &Pubkey, destination: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::WithdrawRake {
+            fee_vault: pda::fee_vault(table).0,
+            rake_authority: *rake_authority,
+            destination: *destination,
+        },
+        instruction::WithdrawRake { amount },
+        Vec::new(),
+    )
+}
+
+pub fn create_player_stats(player: &Pubkey) -> Instruction {
+    build(
+        accounts::CreatePlayerStats {
+            player_stats: pda::player_stats(player).0,
+            player: *player,
+            system_program: system_program::ID,
+        },
+        instruction::CreatePlayerStats {},
+        Vec::new(),
+    )
+}
+
+pub fn claim_rakeback(player: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimRakeback { player_stats: pda::player_stats(player).0, player: *player },
+        instruction::ClaimRakeback {},
+        Vec::new(),
+    )
+}
+
+// Administration
+
+pub fn initialize_config(upgrade_authority: &Pubkey, admin: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            config: pda::config().0,
+            program: PROGRAM_ID,
+            program_data: pda::program_data().0,
+            upgrade_authority: *upgrade_authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeConfig { admin },
+        Vec::new(),
+    )
+}
+
+// The program admin signs with the config account; the table authority without it
+fn table_admin_accounts(table: &Pubkey, signer: &Pubkey, as_admin: bool) -> accounts::TableAdmin {
+    accounts::TableAdmin {
+        table: *table,
+        config: as_admin.then(|| pda::config().0),
+        signer: *signer,
+    }
+}
+
+pub fn set_table_paused(table: &Pubkey, signer: &Pubkey, as_admin: bool, paused: bool) -> Instruction {
+    build(table_admin_accounts(table, signer, as_admin), instruction::SetTablePaused { paused }, Vec::new())
+}
+
+pub fn emergency_refund(view: &TableView, signer: &Pubkey, as_admin: bool) -> Instruction {
+    build(
+        table_admin_accounts(&view.address, signer, as_admin),
+        instruction::EmergencyRefund {},
+        remaining::emergency_refund(view),
+    )
+}
+
+// Gameplay
+
+pub fn start_hand(view: &TableView) -> Instruction {
+    build(accounts::StartHand { table: view.address }, instruction::StartHand {}, remaining::start_hand(view))
+}
+
+fn post_blinds_accounts(view: &TableView, player: &Pubkey) -> accounts::PostBlinds {
+    accounts::PostBlinds {
+        table: view.address,
+        player_state: view.player_state_address(player),
+        player: *player,
+    }
+}
+
+pub fn post_blinds(view: &TableView, player: &Pubkey) -> Instruction {
+    build(post_blinds_accounts(view, player), instruction::PostBlinds {}, remaining::hand_history(view))
+}
+
+pub fn post_missed_blinds(view: &TableView, player: &Pubkey, live: bool) -> Instruction {
+    build(
+        post_blinds_accounts(view, player),
+        instruction::PostMissedBlinds { live },
+        remaining::hand_history(view),
+    )
+}
+
+pub fn player_action(
+    view: &TableView,
+    player: &Pubkey,
+    action: PlayerActionType,
+    raise_amount: u64,
+) -> Instruction {
+    build(
+        accounts::PlayerAction {
+            table: view.address,
+            player_state: view.player_state_address(player),
+            player: *player,
+        },
+        instruction::PlayerAction { action, raise_amount },
+        remaining::betting(view),
+    )
+}
+
+/// Checks or folds for the player to act once their `action_timeout` has run out
+pub fn crank_timeout(view: &TableView) -> Instruction {
+    let player = view.table.players[view.table.current_player_index as usize];
+    build(
+        accounts::CrankTimeout {
+            table: view.address,
+            player_state: view.player_state_address(&player),
+        },
+        instruction::CrankTimeout {},
+        remaining::betting(view),
+    )
+}
+
+/// Simulate this and decode the return data with [`crate::state::decode_legal_actions`]
+/// to ask the program what the player to act may do
+pub fn legal_actions(view: &TableView) -> Instruction {
+    let player = view.table.players[view.table.current_player_index as usize];
+    build(
+        accounts::LegalActions {
+            table: view.address,
+            player_state: view.player_state_address(&player),
+        },
+        instruction::LegalActions {},
+        Vec::new(),
+    )
+}
+
+pub fn advance_street(view: &TableView) -> Instruction {
+    build(
+        accounts::AdvanceStreet { table: view.address },
+        instruction::AdvanceStreet {},
+        remaining::hand_history(view),
+    )
+}
+
+pub fn advance_street_auto(view: &TableView) -> Instruction {
+    build(
+        accounts::AdvanceStreetAuto { table: view.address },
+        instruction::AdvanceStreetAuto {},
+        remaining::betting(view),
+    )
+}
+
+pub fn check_auto_win(view: &TableView) -> Instruction {
+    build(
+        accounts::CheckAutoWin { table: view.address },
+        instruction::CheckAutoWin {},
+        remaining::check_auto_win(view),
+    )
+}
+
+/// Returns `None` if the winning seat is empty
+pub fn end_hand(view: &TableView, winner_position: u8) -> Option<Instruction> {
+    let winner = view.player_at(winner_position)?;
+    Some(build(
+        accounts::EndHand {
+            table: view.address,
+            winner_state: view.player_state_address(&winner),
+        },
+        instruction::EndHand { winner_position },
+        remaining::end_hand(view),
+    ))
+}
+
+pub fn set_blind_structure(
+    table: &Pubkey,
+    authority: &Pubkey,
+    levels: Vec<BlindLevel>,
+    duration_unit: LevelDurationUnit,
+    level_duration: u64,
+) -> Instruction {
+    build(
+        accounts::SetBlindStructure {
+            table: *table,
+            blind_structure: pda::blind_structure(table).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::SetBlindStructure { levels, duration_unit, level_duration },
+        Vec::new(),
+    )
+}
+
+// Tournaments
+
+pub fn create_tournament(
+    authority: &Pubkey,
+    table: &Pubkey,
+    tournament_id: u64,
+    entry_fee: u64,
+    starting_stack: u64,
+    max_entrants: u8,
+    payout_bps: Vec<u16>,
+) -> Instruction {
+    build(
+        accounts::CreateTournament {
+            tournament: pda::tournament(tournament_id).0,
+            table: *table,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::CreateTournament { tournament_id, entry_fee, starting_stack, max_entrants, payout_bps },
+        Vec::new(),
+    )
+}
+
+fn configure_tournament_accounts(tournament: &Pubkey, creator: &Pubkey) -> accounts::ConfigureTournament {
+    accounts::ConfigureTournament { tournament: *tournament, creator: *creator }
+}
+
+pub fn set_payout_structure(tournament: &Pubkey, creator: &Pubkey, payout_bps: Vec<u16>) -> Instruction {
+    build(
+        configure_tournament_accounts(tournament, creator),
+        instruction::SetPayoutStructure { payout_bps },
+        Vec::new(),
+    )
+}
+
+pub fn set_tournament_bounty(
+    tournament: &Pubkey,
+    creator: &Pubkey,
+    bounty_type: BountyType,
+    bounty_bps: u16,
+) -> Instruction {
+    build(
+        configure_tournament_accounts(tournament, creator),
+        instruction::SetTournamentBounty { bounty_type, bounty_bps },
+        Vec::new(),
+    )
+}
+
+/// Arguments for [`set_tournament_rebuys`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RebuySettings {
+    pub rebuy_fee: u64,
+    pub rebuy_chips: u64,
+    pub max_rebuys: u8,
+    pub rebuy_period_levels: u8,
+    pub addon_fee: u64,
+    pub addon_chips: u64,
+}
+
+pub fn set_tournament_rebuys(tournament: &Pubkey, creator: &Pubkey, settings: RebuySettings) -> Instruction {
+    build(
+        configure_tournament_accounts(tournament, creator),
+        instruction::SetTournamentRebuys {
+            rebuy_fee: settings.rebuy_fee,
+            rebuy_chips: settings.rebuy_chips,
+            max_rebuys: settings.max_rebuys,
+            rebuy_period_levels: settings.rebuy_period_levels,
+            addon_fee: settings.addon_fee,
+            addon_chips: settings.addon_chips,
+        },
+        Vec::new(),
+    )
+}
+
+// The tournament's creator adds a table they hold the authority for, usually signing as both
+pub fn add_tournament_table(
+    tournament: &Tournament,
+    table: &Pubkey,
+    creator: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::AddTournamentTable {
+            tournament: pda::tournament(tournament.tournament_id).0,
+            table: *table,
+            blind_structure: pda::blind_structure(table).0,
+            tournament_blind_structure: pda::blind_structure(&tournament.tables[0]).0,
+            creator: *creator,
+            authority: *authority,
+        },
+        instruction::AddTournamentTable {},
+        Vec::new(),
+    )
+}
+
+pub fn register_tournament(tournament: &Pubkey, table: &Pubkey, player: &Pubkey) -> Instruction {
+    build(
+        accounts::RegisterTournament {
+            tournament: *tournament,
+            table: *table,
+            player_state: pda::player_state(table, player).0,
+            player: *player,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterTournament {},
+        Vec::new(),
+    )
+}
+
+pub fn eliminate_player(tournament: &Pubkey, table: &Pubkey, player: &Pubkey) -> Instruction {
+    build(
+        accounts::EliminatePlayer {
+            tournament: *tournament,
+            table: *table,
+            player_state: pda::player_state(table, player).0,
+            player: *player,
+        },
+        instruction::EliminatePlayer {},
+        Vec::new(),
+    )
+}
+
+fn purchase_accounts(tournament: &Pubkey, table: &Pubkey, player: &Pubkey) -> accounts::TournamentPurchase {
+    accounts::TournamentPurchase {
+        tournament: *tournament,
+        table: *table,
+        player_state: pda::player_state(table, player).0,
+        player: *player,
+        system_program: system_program::ID,
+    }
+}
+
+pub fn rebuy(tournament: &Pubkey, table: &Pubkey, player: &Pubkey) -> Instruction {
+    build(purchase_accounts(tournament, table, player), instruction::Rebuy {}, Vec::new())
+}
+
+pub fn addon(tournament: &Pubkey, table: &Pubkey, player: &Pubkey) -> Instruction {
+    build(purchase_accounts(tournament, table, player), instruction::Addon {}, Vec::new())
+}
+
+pub fn move_tournament_player(
+    tournament: &Pubkey,
+    from_table: &Pubkey,
+    to_table: &Pubkey,
+    player: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::MoveTournamentPlayer {
+            tournament: *tournament,
+            from_table: *from_table,
+            to_table: *to_table,
+            from_player_state: pda::player_state(from_table, player).0,
+            to_player_state: pda::player_state(to_table, player).0,
+            config: None,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MoveTournamentPlayer {},
+        Vec::new(),
+    )
+}
+
+// Deals are priced from every live table and every remaining player's stack
+fn deal_stack_accounts(tournament: &Tournament) -> Vec<AccountMeta> {
+    let mut accounts: Vec<AccountMeta> = tournament
+        .tables
+        .iter()
+        .zip(&tournament.table_counts)
+        .filter(|(_, count)| **count > 0)
+        .map(|(table, _)| AccountMeta::new_readonly(*table, false))
+        .collect();
+    accounts.extend(
+        tournament
+            .entries
+            .iter()
+            .filter(|entry| entry.finish_place == 0)
+            .map(|entry| {
+                let table = tournament.tables[entry.table_index as usize];
+                AccountMeta::new_readonly(pda::player_state(&table, &entry.player).0, false)
+            }),
+    );
+    accounts
+}
+
+pub fn propose_deal(tournament: &Tournament, proposer: &Pubkey, kind: DealKind) -> Instruction {
+    let address = pda::tournament(tournament.tournament_id).0;
+    build(
+        accounts::ProposeDeal {
+            tournament: address,
+            deal: pda::deal(&address).0,
+            proposer: *proposer,
+            system_program: system_program::ID,
+        },
+        instruction::ProposeDeal { kind },
+        deal_stack_accounts(tournament),
+    )
+}
+
+pub fn accept_deal(tournament: &Tournament, proposer: &Pubkey, player: &Pubkey) -> Instruction {
+    let address = pda::tournament(tournament.tournament_id).0;
+    build(
+        accounts::AcceptDeal {
+            tournament: address,
+            deal: pda::deal(&address).0,
+            proposer: *proposer,
+            player: *player,
+        },
+        instruction::AcceptDeal {},
+        deal_stack_accounts(tournament),
+    )
+}
+
+pub fn reject_deal(tournament: &Pubkey, proposer: &Pubkey, player: &Pubkey) -> Instruction {
+    build(
+        accounts::RejectDeal {
+            deal: pda::deal(tournament).0,
+            proposer: *proposer,
+            player: *player,
+        },
+        instruction::RejectDeal {},
+        Vec::new(),
+    )
+}
+
+pub fn claim_tournament_payout(tournament: &Pubkey, player: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimTournamentPayout { tournament: *tournament, player: *player },
+        instruction::ClaimTournamentPayout {},
+        Vec::new(),
+    )
+}
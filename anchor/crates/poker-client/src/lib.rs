@@ -0,0 +1,20 @@
+//! Off-chain client for the poker program.
+//!
+//! - [`pda`] derives every program address.
+//! - [`instructions`] builds each instruction, including the `remaining_accounts`
+//!   the program expects for the table's current configuration.
+//! - [`state`] decodes program accounts.
+//! - [`engine`] is the rules engine the program itself runs, for working out what's
+//!   legal without a round trip.
+
+pub mod instructions;
+pub mod pda;
+pub mod remaining;
+pub mod state;
+
+pub use poker_engine as engine;
+pub use poker::{
+    BlindLevel, BountyType, DealKind, GameState, LevelDurationUnit, PlayerActionType,
+    PlayerStatus, RakeAttribution, TableConfigUpdate, TableVisibility, ID as PROGRAM_ID,
+};
+pub use state::TableView;
@@ -0,0 +1,58 @@
+//! Program derived addresses, mirroring the seeds in the program.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+use crate::PROGRAM_ID;
+
+pub fn table(table_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"table", &table_id.to_le_bytes()], &PROGRAM_ID)
+}
+
+pub fn player_state(table: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"player", table.as_ref(), player.as_ref()], &PROGRAM_ID)
+}
+
+pub fn waitlist(table: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"waitlist", table.as_ref()], &PROGRAM_ID)
+}
+
+pub fn invite(table: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"invite", table.as_ref(), player.as_ref()], &PROGRAM_ID)
+}
+
+pub fn blind_structure(table: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"blind_structure", table.as_ref()], &PROGRAM_ID)
+}
+
+pub fn fee_vault(table: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fee_vault", table.as_ref()], &PROGRAM_ID)
+}
+
+pub fn hand_history(table: &Pubkey, hand_number: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"hand_history", table.as_ref(), &hand_number.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn player_stats(player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"player_stats", player.as_ref()], &PROGRAM_ID)
+}
+
+pub fn config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], &PROGRAM_ID)
+}
+
+pub fn tournament(tournament_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tournament", &tournament_id.to_le_bytes()], &PROGRAM_ID)
+}
+
+pub fn deal(tournament: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"deal", tournament.as_ref()], &PROGRAM_ID)
+}
+
+// The program's upgrade data account, checked by initialize_config
+pub fn program_data() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
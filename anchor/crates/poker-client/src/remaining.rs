@@ -0,0 +1,84 @@
+//! The `remaining_accounts` each instruction expects.
+//!
+//! Auxiliary accounts (waitlist, blind structure, fee vault, hand history, tournament)
+//! are only passed when the table has one configured; the program finds them by key.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+
+use crate::{pda, state::TableView};
+
+fn optional(key: Pubkey) -> Option<AccountMeta> {
+    (key != Pubkey::default()).then(|| AccountMeta::new(key, false))
+}
+
+/// Every seated player's state account
+pub fn player_states(view: &TableView) -> Vec<AccountMeta> {
+    view.seated()
+        .map(|(_, player)| AccountMeta::new(view.player_state_address(&player), false))
+        .collect()
+}
+
+/// Stats accounts for everyone dealt in, needed when rakeback is paid
+pub fn player_stats(view: &TableView) -> Vec<AccountMeta> {
+    let table = &view.table;
+    if table.fee_vault == Pubkey::default() || table.rake_bps == 0 || table.rakeback_bps == 0 {
+        return Vec::new();
+    }
+    view.seated()
+        .filter(|(seat, _)| table.is_dealt_in(*seat))
+        .map(|(_, player)| AccountMeta::new(pda::player_stats(&player).0, false))
+        .collect()
+}
+
+pub fn hand_history(view: &TableView) -> Vec<AccountMeta> {
+    optional(view.table.hand_history).into_iter().collect()
+}
+
+pub fn join_table(view: &TableView) -> Vec<AccountMeta> {
+    optional(view.table.waitlist).into_iter().collect()
+}
+
+pub fn start_hand(view: &TableView) -> Vec<AccountMeta> {
+    let table = &view.table;
+    let mut accounts: Vec<AccountMeta> = [table.tournament, table.blind_structure, table.hand_history]
+        .into_iter()
+        .filter_map(optional)
+        .collect();
+    accounts.extend(player_states(view));
+    accounts
+}
+
+/// Accounts for an instruction that moves the action on: the hand history and,
+/// so the next player can be found precisely, the player states
+pub fn betting(view: &TableView) -> Vec<AccountMeta> {
+    let mut accounts = hand_history(view);
+    accounts.extend(player_states(view));
+    accounts
+}
+
+/// Accounts for paying out a hand. Tournament tables pass the tournament so busts
+/// can be recorded.
+pub fn check_auto_win(view: &TableView) -> Vec<AccountMeta> {
+    let mut accounts: Vec<AccountMeta> = optional(view.table.fee_vault).into_iter().collect();
+    accounts.extend(player_stats(view));
+    accounts.extend(betting(view));
+    accounts.extend(optional(view.table.tournament));
+    accounts
+}
+
+pub fn end_hand(view: &TableView) -> Vec<AccountMeta> {
+    check_auto_win(view)
+}
+
+pub fn emergency_refund(view: &TableView) -> Vec<AccountMeta> {
+    betting(view)
+}
+
+/// Past hand histories and invites can be appended to have their rent returned too
+pub fn close_table(view: &TableView) -> Vec<AccountMeta> {
+    let table = &view.table;
+    [table.tournament, table.blind_structure, table.waitlist, table.fee_vault, table.hand_history]
+        .into_iter()
+        .filter_map(optional)
+        .collect()
+}
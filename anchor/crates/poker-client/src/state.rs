@@ -0,0 +1,80 @@
+//! Account decoders.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Result};
+
+pub use poker::{
+    BlindStructure, Deal, FeeVault, HandHistory, LegalActionsView, PlayerState, PlayerStats,
+    PokerTable, ProgramConfig, Tournament, Waitlist,
+};
+
+/// Decode any program account from its raw data, checking the discriminator
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_table(data: &[u8]) -> Result<PokerTable> {
+    decode(data)
+}
+
+pub fn decode_player_state(data: &[u8]) -> Result<PlayerState> {
+    decode(data)
+}
+
+/// Decode the return data of a simulated legal_actions instruction
+pub fn decode_legal_actions(return_data: &[u8]) -> Result<LegalActionsView> {
+    Ok(LegalActionsView::deserialize(&mut &return_data[..])?)
+}
+
+/// A table account together with its address, which is what most builders need to
+/// work out the accounts an instruction has to carry
+#[derive(Clone)]
+pub struct TableView {
+    pub address: Pubkey,
+    pub table: PokerTable,
+}
+
+impl TableView {
+    pub fn new(address: Pubkey, table: PokerTable) -> Self {
+        Self { address, table }
+    }
+
+    pub fn decode(address: Pubkey, data: &[u8]) -> Result<Self> {
+        Ok(Self::new(address, decode_table(data)?))
+    }
+
+    /// Occupied seats as (seat, player)
+    pub fn seated(&self) -> impl Iterator<Item = (u8, Pubkey)> + '_ {
+        self.table
+            .players
+            .iter()
+            .enumerate()
+            .filter(|(_, player)| **player != Pubkey::default())
+            .map(|(seat, player)| (seat as u8, *player))
+    }
+
+    pub fn player_at(&self, seat: u8) -> Option<Pubkey> {
+        self.table
+            .players
+            .get(seat as usize)
+            .copied()
+            .filter(|player| *player != Pubkey::default())
+    }
+
+    pub fn player_state_address(&self, player: &Pubkey) -> Pubkey {
+        crate::pda::player_state(&self.address, player).0
+    }
+
+    /// Seat whose turn it is, while a hand is being played
+    pub fn to_act(&self) -> Option<(u8, Pubkey)> {
+        self.table.game_state.street()?;
+        let seat = self.table.current_player_index;
+        self.player_at(seat).map(|player| (seat, player))
+    }
+
+    /// What the player to act may do, worked out exactly as the legal_actions view does
+    /// from `player_state`, the state of the player at `current_player_index`
+    pub fn legal_actions(&self, player_state: &PlayerState) -> Result<LegalActionsView> {
+        self.table.legal_actions(player_state)
+    }
+}
@@ -0,0 +1,154 @@
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::{AccountSerialize, Discriminator};
+use poker::PokerTable;
+use poker_client::{instructions, pda, state, GameState, PlayerActionType, RakeAttribution, TableView, TableVisibility};
+
+fn table_with_players(players: &[Pubkey]) -> PokerTable {
+    let mut seats = vec![Pubkey::default(); 6];
+    seats[..players.len()].copy_from_slice(players);
+    PokerTable {
+        table_id: 7,
+        creator: Pubkey::new_unique(),
+        authority: Pubkey::new_unique(),
+        bump: 255,
+        player_count: players.len() as u8,
+        max_seats: 6,
+        players: seats,
+        button_position: 0,
+        current_player_index: 0,
+        pot: 0,
+        current_bet: 0,
+        game_state: GameState::WaitingForPlayers,
+        small_blind: 10,
+        big_blind: 20,
+        min_buy_in: 1_000,
+        max_buy_in: 10_000,
+        hand_number: 3,
+        deck_computation: Pubkey::default(),
+        community_cards: [0; 5],
+        street_bet_count: 0,
+        blinds_posted: 0,
+        last_raise_amount: 0,
+        last_aggressor_index: 0,
+        tournament: Pubkey::default(),
+        blind_structure: Pubkey::default(),
+        ante: 0,
+        sitting_out: 0,
+        missed_small_blinds: 0,
+        missed_big_blinds: 0,
+        leaving: 0,
+        waitlist: Pubkey::default(),
+        seat_freed_at: 0,
+        visibility: TableVisibility::Public,
+        action_timeout: 30,
+        paused: false,
+        fee_vault: Pubkey::default(),
+        rake_bps: 0,
+        rake_cap: 0,
+        no_flop_no_drop: false,
+        rakeback_bps: 0,
+        rake_attribution: RakeAttribution::Contributed,
+        hand_history: Pubkey::default(),
+        turn_started_at: 0,
+    }
+}
+
+fn keys(metas: &[AccountMeta]) -> Vec<Pubkey> {
+    metas.iter().map(|meta| meta.pubkey).collect()
+}
+
+// Table 7 with Alice and Bob in the first two seats
+fn heads_up() -> (TableView, Pubkey, Pubkey) {
+    let alice = Pubkey::new_unique();
+    let bob = Pubkey::new_unique();
+    let (address, _) = pda::table(7);
+    (TableView::new(address, table_with_players(&[alice, bob])), alice, bob)
+}
+
+#[test]
+fn test_plain_table_action_takes_only_player_states() {
+    let (view, alice, bob) = heads_up();
+    let ix = instructions::player_action(&view, &alice, PlayerActionType::Call, 0);
+    assert_eq!(ix.data[..8], *poker::instruction::PlayerAction::DISCRIMINATOR);
+    let alice_state = pda::player_state(&view.address, &alice).0;
+    let bob_state = pda::player_state(&view.address, &bob).0;
+    assert_eq!(keys(&ix.accounts), vec![view.address, alice_state, alice, alice_state, bob_state]);
+}
+
+#[test]
+fn test_end_hand_attaches_configured_extras() {
+    let (mut view, alice, bob) = heads_up();
+    let address = view.address;
+    view.table.fee_vault = pda::fee_vault(&address).0;
+    view.table.rake_bps = 500;
+    view.table.rakeback_bps = 2_000;
+    view.table.hand_history = pda::hand_history(&address, 4).0;
+
+    let alice_state = pda::player_state(&address, &alice).0;
+    let bob_state = pda::player_state(&address, &bob).0;
+    let ix = instructions::end_hand(&view, 1).unwrap();
+    assert_eq!(
+        keys(&ix.accounts),
+        vec![
+            address,
+            bob_state,
+            view.table.fee_vault,
+            pda::player_stats(&alice).0,
+            pda::player_stats(&bob).0,
+            view.table.hand_history,
+            alice_state,
+            bob_state,
+        ],
+    );
+}
+
+#[test]
+fn test_check_auto_win_passes_the_tournament() {
+    let (mut view, alice, bob) = heads_up();
+    let address = view.address;
+    view.table.tournament = pda::tournament(3).0;
+
+    let ix = instructions::check_auto_win(&view);
+    assert_eq!(
+        keys(&ix.accounts),
+        vec![
+            address,
+            pda::player_state(&address, &alice).0,
+            pda::player_state(&address, &bob).0,
+            view.table.tournament,
+        ],
+    );
+}
+
+#[test]
+fn test_end_hand_needs_a_seated_winner() {
+    let (view, _, _) = heads_up();
+    assert!(instructions::end_hand(&view, 1).is_some());
+    assert!(instructions::end_hand(&view, 2).is_none());
+}
+
+#[test]
+fn test_table_decoding_round_trips() {
+    let (view, alice, bob) = heads_up();
+    let mut data = Vec::new();
+    view.table.try_serialize(&mut data).unwrap();
+    let decoded = TableView::decode(view.address, &data).unwrap();
+    assert_eq!(decoded.seated().collect::<Vec<_>>(), vec![(0, alice), (1, bob)]);
+    assert!(state::decode_player_state(&data).is_err());
+}
+
+#[test]
+fn test_join_carries_invite_only_on_private_tables() {
+    let alice = Pubkey::new_unique();
+    let (address, _) = pda::table(7);
+    let mut view = TableView::new(address, table_with_players(&[]));
+    let alice_state = pda::player_state(&address, &alice).0;
+
+    // An absent optional account is passed as the program id
+    let ix = instructions::join_table(&view, &alice, 1_000, 0);
+    assert_eq!(keys(&ix.accounts)[..3], [address, alice_state, poker_client::PROGRAM_ID]);
+
+    view.table.visibility = TableVisibility::Private;
+    let ix = instructions::join_table(&view, &alice, 1_000, 0);
+    assert_eq!(keys(&ix.accounts)[..3], [address, alice_state, pda::invite(&address, &alice).0]);
+}
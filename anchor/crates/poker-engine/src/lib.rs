@@ -0,0 +1,233 @@
+//! Betting rules for a hold'em hand, as pure state transitions.
+//!
+//! The on-chain program and off-chain clients both run these functions, so what a
+//! client or simulator considers legal is exactly what the program accepts.
+
+#![no_std]
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Street {
+    PreFlop,
+    Flop,
+    Turn,
+    River,
+    Showdown,
+}
+
+impl Street {
+    pub fn next(self) -> Option<Street> {
+        match self {
+            Street::PreFlop => Some(Street::Flop),
+            Street::Flop => Some(Street::Turn),
+            Street::Turn => Some(Street::River),
+            Street::River => Some(Street::Showdown),
+            Street::Showdown => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SeatStatus {
+    Active,
+    Folded,
+    AllIn,
+    SittingOut,
+}
+
+/// One player's part in the hand
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Seat {
+    pub stack: u64,
+    pub current_bet: u64,   // Chips in front of the player this street
+    pub status: SeatStatus,
+    pub has_acted: bool,    // Acted since the street started
+}
+
+impl Seat {
+    /// Still contesting the pot
+    pub fn in_hand(&self) -> bool {
+        matches!(self.status, SeatStatus::Active | SeatStatus::AllIn)
+    }
+
+    fn pay(&mut self, amount: u64) {
+        self.stack -= amount;
+        if self.stack == 0 {
+            self.status = SeatStatus::AllIn;
+        }
+    }
+}
+
+/// Table-wide betting state
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Betting {
+    pub pot: u64,
+    pub current_bet: u64,       // Bet every player has to match this street
+    pub last_raise_amount: u64, // Size of the last raise, the minimum for the next one
+    pub big_blind: u64,
+    pub raises: u8,             // Raises made this street
+    pub last_aggressor: u8,     // Seat of the last player to raise
+}
+
+impl Betting {
+    /// Smallest raise allowed: the last raise this street, or the big blind
+    pub fn min_raise(&self) -> u64 {
+        if self.last_raise_amount > 0 {
+            self.last_raise_amount
+        } else {
+            self.big_blind
+        }
+    }
+
+    /// Clear the bet for the next street
+    pub fn new_street(&mut self) {
+        self.current_bet = 0;
+        self.raises = 0;
+        self.last_raise_amount = 0;
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    Raise { amount: u64 },      // Amount above the current bet
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RuleError {
+    PlayerNotActive,
+    CannotCheck,
+    InsufficientFunds,
+    RaiseTooSmall,
+}
+
+/// What the player to act may do
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LegalActions {
+    pub can_check: bool,
+    pub call_amount: u64,       // Chips a call puts in (0 when checking is allowed)
+    pub min_raise: u64,         // Raise amounts are above the current bet, as in Action::Raise
+    pub max_raise: u64,         // 0 when the player can't cover a minimum raise
+}
+
+impl LegalActions {
+    pub fn can_call(&self) -> bool {
+        self.call_amount > 0
+    }
+
+    pub fn can_raise(&self) -> bool {
+        self.max_raise > 0
+    }
+}
+
+pub fn legal_actions(betting: &Betting, seat: &Seat) -> Result<LegalActions, RuleError> {
+    if seat.status != SeatStatus::Active {
+        return Err(RuleError::PlayerNotActive);
+    }
+
+    let to_call = betting.current_bet.saturating_sub(seat.current_bet);
+    // Raising everything puts the stack on top of what's already in front of the player
+    let all_in_raise = (seat.stack + seat.current_bet).saturating_sub(betting.current_bet);
+    let min_raise = betting.min_raise();
+
+    Ok(LegalActions {
+        can_check: to_call == 0,
+        call_amount: to_call.min(seat.stack),
+        min_raise,
+        max_raise: if all_in_raise >= min_raise { all_in_raise } else { 0 },
+    })
+}
+
+/// Apply an action by the player in `position`, returning the chips it put in the pot
+pub fn apply_action(
+    betting: &mut Betting,
+    seat: &mut Seat,
+    position: u8,
+    action: Action,
+) -> Result<u64, RuleError> {
+    let legal = legal_actions(betting, seat)?;
+
+    let added = match action {
+        Action::Fold => {
+            seat.status = SeatStatus::Folded;
+            0
+        }
+        Action::Check => {
+            if !legal.can_check {
+                return Err(RuleError::CannotCheck);
+            }
+            0
+        }
+        Action::Call => {
+            seat.current_bet += legal.call_amount;
+            seat.pay(legal.call_amount);
+            legal.call_amount
+        }
+        Action::Raise { amount } => {
+            // No stack covers a bet past u64::MAX
+            let total_bet = betting.current_bet.checked_add(amount).ok_or(RuleError::InsufficientFunds)?;
+            let to_add = total_bet.saturating_sub(seat.current_bet);
+            if seat.stack < to_add {
+                return Err(RuleError::InsufficientFunds);
+            }
+            if amount < legal.min_raise {
+                return Err(RuleError::RaiseTooSmall);
+            }
+
+            seat.current_bet = total_bet;
+            seat.pay(to_add);
+            betting.current_bet = total_bet;
+            betting.raises += 1;
+            betting.last_raise_amount = amount;
+            betting.last_aggressor = position;
+            to_add
+        }
+    };
+
+    betting.pot += added;
+    seat.has_acted = true;
+    Ok(added)
+}
+
+/// Move chips into the pot without counting them toward the player's bet (dead blinds)
+pub fn post(betting: &mut Betting, seat: &mut Seat, amount: u64) -> Result<(), RuleError> {
+    if seat.stack < amount {
+        return Err(RuleError::InsufficientFunds);
+    }
+    seat.pay(amount);
+    betting.pot += amount;
+    Ok(())
+}
+
+/// Post a blind, which counts toward the player's bet this street. A stack too short
+/// for the blind (say, after antes) goes all in for what's left. Returns the amount posted.
+pub fn post_blind(betting: &mut Betting, seat: &mut Seat, amount: u64) -> u64 {
+    let posted = post_ante(betting, seat, amount);
+    seat.current_bet += posted;
+    posted
+}
+
+/// Take an ante, or whatever is left of a short stack. Returns the amount taken.
+pub fn post_ante(betting: &mut Betting, seat: &mut Seat, ante: u64) -> u64 {
+    let amount = ante.min(seat.stack);
+    seat.pay(amount);
+    betting.pot += amount;
+    amount
+}
+
+/// First seat after `from`, going round the table, for which `eligible` holds
+pub fn next_seat(max_seats: u8, from: u8, eligible: impl Fn(u8) -> bool) -> Option<u8> {
+    (1..=max_seats)
+        .map(|offset| (from + offset) % max_seats)
+        .find(|seat| eligible(*seat))
+}
+
+/// The street's betting is over once every player still able to act has acted and
+/// matched the current bet
+pub fn is_round_complete<'a>(current_bet: u64, seats: impl IntoIterator<Item = &'a Seat>) -> bool {
+    seats
+        .into_iter()
+        .filter(|seat| seat.status == SeatStatus::Active)
+        .all(|seat| seat.has_acted && seat.current_bet == current_bet)
+}
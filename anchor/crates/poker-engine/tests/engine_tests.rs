@@ -0,0 +1,135 @@
+use poker_engine::{
+    apply_action, is_round_complete, legal_actions, next_seat, post_blind, Action, Betting,
+    RuleError, Seat, SeatStatus, Street,
+};
+
+fn seat(stack: u64) -> Seat {
+    Seat { stack, current_bet: 0, status: SeatStatus::Active, has_acted: false }
+}
+
+// Blinds of 10 and 20 posted by seats 0 and 1, with the short stack in seat 2 to act
+fn preflop() -> (Betting, [Seat; 3]) {
+    let mut betting = Betting {
+        pot: 0,
+        current_bet: 20,
+        last_raise_amount: 0,
+        big_blind: 20,
+        raises: 0,
+        last_aggressor: 0,
+    };
+    let mut seats = [seat(1_000), seat(1_000), seat(100)];
+    assert_eq!(post_blind(&mut betting, &mut seats[0], 10), 10);
+    assert_eq!(post_blind(&mut betting, &mut seats[1], 20), 20);
+    (betting, seats)
+}
+
+#[test]
+fn test_blinds_go_into_the_pot() {
+    let (betting, seats) = preflop();
+    assert_eq!(betting.pot, 30);
+    assert_eq!((seats[0].current_bet, seats[1].current_bet), (10, 20));
+    assert_eq!((seats[0].stack, seats[1].stack), (990, 980));
+}
+
+#[test]
+fn test_short_stack_can_raise_by_what_it_has_behind() {
+    let (betting, seats) = preflop();
+    let legal = legal_actions(&betting, &seats[2]).unwrap();
+    assert!(!legal.can_check);
+    assert_eq!((legal.call_amount, legal.min_raise, legal.max_raise), (20, 20, 80));
+}
+
+#[test]
+fn test_facing_a_bet_cannot_check_or_raise_short() {
+    let (mut betting, mut seats) = preflop();
+    assert_eq!(apply_action(&mut betting, &mut seats[2], 2, Action::Check), Err(RuleError::CannotCheck));
+    assert_eq!(
+        apply_action(&mut betting, &mut seats[2], 2, Action::Raise { amount: 10 }),
+        Err(RuleError::RaiseTooSmall),
+    );
+    assert_eq!(
+        apply_action(&mut betting, &mut seats[2], 2, Action::Raise { amount: 90 }),
+        Err(RuleError::InsufficientFunds),
+    );
+    assert_eq!(betting.pot, 30);
+}
+
+#[test]
+fn test_raise_past_the_largest_bet_is_rejected() {
+    let (mut betting, mut seats) = preflop();
+    seats[2].stack = u64::MAX;
+    assert_eq!(
+        apply_action(&mut betting, &mut seats[2], 2, Action::Raise { amount: u64::MAX }),
+        Err(RuleError::InsufficientFunds),
+    );
+    assert_eq!((betting.pot, betting.current_bet, seats[2].stack), (30, 20, u64::MAX));
+}
+
+#[test]
+fn test_all_in_short_of_a_min_raise_is_too_small() {
+    let (mut betting, mut seats) = preflop();
+    seats[2].stack = 30;
+    // Ten more than the bet is all this stack has, so it can only call
+    assert_eq!(legal_actions(&betting, &seats[2]).unwrap().max_raise, 0);
+    assert_eq!(
+        apply_action(&mut betting, &mut seats[2], 2, Action::Raise { amount: 10 }),
+        Err(RuleError::RaiseTooSmall),
+    );
+    assert_eq!(apply_action(&mut betting, &mut seats[2], 2, Action::Call), Ok(20));
+}
+
+#[test]
+fn test_all_in_raise_leaves_player_unable_to_act() {
+    let (mut betting, mut seats) = preflop();
+    assert_eq!(apply_action(&mut betting, &mut seats[2], 2, Action::Raise { amount: 80 }), Ok(100));
+    assert_eq!(seats[2].status, SeatStatus::AllIn);
+    assert_eq!((betting.current_bet, betting.min_raise(), betting.last_aggressor), (100, 80, 2));
+    assert_eq!(legal_actions(&betting, &seats[2]), Err(RuleError::PlayerNotActive));
+}
+
+#[test]
+fn test_round_completes_once_every_bet_is_matched() {
+    let (mut betting, mut seats) = preflop();
+    apply_action(&mut betting, &mut seats[2], 2, Action::Raise { amount: 80 }).unwrap();
+    assert_eq!(apply_action(&mut betting, &mut seats[0], 0, Action::Call), Ok(90));
+    assert!(!is_round_complete(betting.current_bet, &seats));
+    apply_action(&mut betting, &mut seats[1], 1, Action::Fold).unwrap();
+    assert!(is_round_complete(betting.current_bet, &seats));
+    assert_eq!(betting.pot, 220);
+}
+
+#[test]
+fn test_turn_order_wraps_round_the_table() {
+    let statuses = [SeatStatus::Active, SeatStatus::Folded, SeatStatus::AllIn];
+    let active = |s: u8| statuses[s as usize] == SeatStatus::Active;
+    assert_eq!(next_seat(3, 0, active), Some(0));
+    assert_eq!(next_seat(3, 2, active), Some(0));
+    assert_eq!(next_seat(3, 0, |_| false), None);
+}
+
+#[test]
+fn test_streets_end_at_showdown() {
+    assert_eq!(Street::PreFlop.next(), Some(Street::Flop));
+    assert_eq!(Street::River.next(), Some(Street::Showdown));
+    assert_eq!(Street::Showdown.next(), None);
+}
+
+#[test]
+fn test_short_stack_posts_blind_all_in() {
+    let mut betting = Betting {
+        pot: 10,
+        current_bet: 20,
+        last_raise_amount: 0,
+        big_blind: 20,
+        raises: 0,
+        last_aggressor: 0,
+    };
+    let mut short = seat(15);
+    assert_eq!(post_blind(&mut betting, &mut short, 20), 15);
+    assert_eq!((short.stack, short.current_bet, short.status), (0, 15, SeatStatus::AllIn));
+    assert_eq!(betting.pot, 25);
+
+    // The full big blind is still what everyone else has to call
+    let caller = seat(1_000);
+    assert_eq!(legal_actions(&betting, &caller).unwrap().call_amount, 20);
+}
@@ -0,0 +1,104 @@
+//! Decoding the program's events from transaction logs.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::Engine;
+use poker::{
+    AntePosted, BlindPosted, BountyCollected, HandComplete, HandRefunded, HandStarted,
+    MissedBlindsPosted, PlayerActioned, PlayerEliminated, PlayerJoined, PlayerLeft, PlayerToppedUp,
+    SeatReleased, StreetAdvanced, TableClosed, TableCreated, TournamentPayoutClaimed,
+    TournamentPlayerMoved, TournamentPurchased, ID as PROGRAM_ID,
+};
+
+/// The events the indexer records
+pub enum PokerEvent {
+    TableCreated(TableCreated),
+    TableClosed(TableClosed),
+    PlayerJoined(PlayerJoined),
+    PlayerToppedUp(PlayerToppedUp),
+    PlayerLeft(PlayerLeft),
+    SeatReleased(SeatReleased),
+    HandStarted(HandStarted),
+    AntePosted(AntePosted),
+    BlindPosted(BlindPosted),
+    MissedBlindsPosted(MissedBlindsPosted),
+    PlayerActioned(PlayerActioned),
+    StreetAdvanced(StreetAdvanced),
+    HandComplete(HandComplete),
+    HandRefunded(HandRefunded),
+    TournamentPurchased(TournamentPurchased),
+    TournamentPlayerMoved(TournamentPlayerMoved),
+    PlayerEliminated(PlayerEliminated),
+    BountyCollected(BountyCollected),
+    TournamentPayoutClaimed(TournamentPayoutClaimed),
+}
+
+macro_rules! decode_event {
+    ($data:expr, $($event:ident),* $(,)?) => {
+        $(
+            if let Some(body) = $data.strip_prefix($event::DISCRIMINATOR) {
+                return $event::deserialize(&mut &body[..]).ok().map(PokerEvent::$event);
+            }
+        )*
+    };
+}
+
+/// Decode one event from `emit!` data (discriminator then Borsh body)
+pub fn decode_event(data: &[u8]) -> Option<PokerEvent> {
+    decode_event!(
+        data,
+        TableCreated,
+        TableClosed,
+        PlayerJoined,
+        PlayerToppedUp,
+        PlayerLeft,
+        SeatReleased,
+        HandStarted,
+        AntePosted,
+        BlindPosted,
+        MissedBlindsPosted,
+        PlayerActioned,
+        StreetAdvanced,
+        HandComplete,
+        HandRefunded,
+        TournamentPurchased,
+        TournamentPlayerMoved,
+        PlayerEliminated,
+        BountyCollected,
+        TournamentPayoutClaimed,
+    );
+    None
+}
+
+/// Events the poker program emitted in a transaction's logs, in order. Data logged
+/// by other programs, including ones the poker program calls, is skipped.
+pub fn decode_logs(logs: &[String]) -> Vec<PokerEvent> {
+    let program = PROGRAM_ID.to_string();
+    let mut invoked: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        if let Some(rest) = log.strip_prefix("Program data: ") {
+            if invoked.last() != Some(&program.as_str()) {
+                continue;
+            }
+            let Ok(data) = base64::engine::general_purpose::STANDARD.decode(rest) else {
+                continue;
+            };
+            events.extend(decode_event(&data));
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            let (Some(id), Some(status)) = (words.next(), words.next()) else {
+                continue;
+            };
+            match status {
+                "invoke" => invoked.push(id),
+                "success" | "failed:" => {
+                    invoked.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    events
+}
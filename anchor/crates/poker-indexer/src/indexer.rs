@@ -0,0 +1,451 @@
+//! Writing events into SQLite.
+
+use std::path::Path;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+
+use crate::events::{decode_logs, PokerEvent};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    signature TEXT PRIMARY KEY,
+    slot INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tables (
+    address TEXT PRIMARY KEY,
+    table_id INTEGER NOT NULL,
+    creator TEXT NOT NULL,
+    small_blind INTEGER NOT NULL,
+    big_blind INTEGER NOT NULL,
+    max_seats INTEGER NOT NULL,
+    created_slot INTEGER NOT NULL,
+    closed_slot INTEGER
+);
+-- One row per time a player sits down. A player who stands up mid-hand keeps the
+-- seat until the hand ends, so left_slot is when the seat was freed. Tournament
+-- players moved to another table leave with their stack and sit down with it there.
+CREATE TABLE IF NOT EXISTS players (
+    id INTEGER PRIMARY KEY,
+    table_address TEXT NOT NULL,
+    player TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    bought_in INTEGER NOT NULL,
+    joined_slot INTEGER NOT NULL,
+    cashed_out INTEGER,
+    left_slot INTEGER
+);
+CREATE TABLE IF NOT EXISTS hands (
+    table_address TEXT NOT NULL,
+    hand_number INTEGER NOT NULL,
+    button INTEGER NOT NULL,
+    small_blind INTEGER NOT NULL,
+    big_blind INTEGER NOT NULL,
+    ante INTEGER NOT NULL,
+    street TEXT NOT NULL,
+    board BLOB NOT NULL,
+    started_slot INTEGER NOT NULL,
+    winner TEXT,
+    winner_position INTEGER,
+    pot INTEGER,
+    rake INTEGER,
+    refunded INTEGER NOT NULL DEFAULT 0,
+    completed_slot INTEGER,
+    PRIMARY KEY (table_address, hand_number)
+);
+CREATE TABLE IF NOT EXISTS actions (
+    table_address TEXT NOT NULL,
+    hand_number INTEGER NOT NULL,
+    seq INTEGER NOT NULL,
+    player TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    street TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    pot INTEGER,            -- After the action; not reported for missed blinds
+    signature TEXT NOT NULL,
+    PRIMARY KEY (table_address, hand_number, seq)
+);
+-- Chips each player put into and took out of each hand
+CREATE TABLE IF NOT EXISTS hand_players (
+    table_address TEXT NOT NULL,
+    hand_number INTEGER NOT NULL,
+    player TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    invested INTEGER NOT NULL DEFAULT 0,
+    won INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (table_address, hand_number, player)
+);
+-- Pots won at tables and prizes claimed from tournaments
+CREATE TABLE IF NOT EXISTS payouts (
+    id INTEGER PRIMARY KEY,
+    kind TEXT NOT NULL,
+    source TEXT NOT NULL,
+    hand_number INTEGER,
+    player TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    signature TEXT NOT NULL
+);
+-- Rebuys and add-ons bought during tournaments
+CREATE TABLE IF NOT EXISTS tournament_purchases (
+    id INTEGER PRIMARY KEY,
+    tournament TEXT NOT NULL,
+    player TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    fee INTEGER NOT NULL,
+    chips INTEGER NOT NULL,
+    signature TEXT NOT NULL
+);
+-- Knockouts in bounty tournaments. What's paid is claimed with the player's prize,
+-- so it isn't a separate payout.
+CREATE TABLE IF NOT EXISTS bounties (
+    id INTEGER PRIMARY KEY,
+    tournament TEXT NOT NULL,
+    eliminator TEXT NOT NULL,
+    eliminated TEXT NOT NULL,
+    paid INTEGER NOT NULL,
+    added_to_bounty INTEGER NOT NULL,
+    signature TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS hand_players_by_player ON hand_players (player);
+CREATE INDEX IF NOT EXISTS payouts_by_player ON payouts (player);
+-- A hand is paid out once
+CREATE UNIQUE INDEX IF NOT EXISTS hand_payouts ON payouts (source, hand_number) WHERE kind = 'hand';
+";
+
+/// An SQLite database of everything that happened at the program's tables.
+///
+/// Transactions must be ingested in the order they landed, and only ones that
+/// succeeded.
+pub struct Indexer {
+    pub(crate) conn: Connection,
+}
+
+impl Indexer {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Indexer { conn })
+    }
+
+    /// The connection, for queries the API doesn't cover
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// The most recently ingested transaction
+    pub fn last_signature(&self) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT signature FROM transactions ORDER BY slot DESC, rowid DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// Record the events in a successful transaction's logs, returning how many were
+    /// recorded. A transaction already ingested is skipped.
+    pub fn ingest_transaction(&mut self, signature: &str, slot: u64, logs: &[String]) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO transactions (signature, slot) VALUES (?1, ?2)",
+            params![signature, slot],
+        )?;
+        if inserted == 0 {
+            return Ok(0);
+        }
+
+        let events = decode_logs(logs);
+        for event in &events {
+            apply(&tx, signature, slot, event)?;
+        }
+        tx.commit()?;
+        Ok(events.len())
+    }
+}
+
+// Hand being played at a table, if we saw it start
+fn current_hand(tx: &Transaction, table: &Pubkey) -> Result<Option<(u64, String)>> {
+    Ok(tx
+        .query_row(
+            "SELECT hand_number, street FROM hands WHERE table_address = ?1
+             ORDER BY hand_number DESC LIMIT 1",
+            [table.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?)
+}
+
+struct ActionRow<'a> {
+    player: &'a Pubkey,
+    position: u8,
+    kind: &'a str,
+    amount: u64,
+    pot: Option<u64>,
+}
+
+// Log an ante, blind or betting action against the current hand and credit what it cost the player
+fn record_action(tx: &Transaction, signature: &str, table: &Pubkey, action: ActionRow) -> Result<()> {
+    let Some((hand_number, street)) = current_hand(tx, table)? else {
+        return Ok(());
+    };
+    let ActionRow { player, position, kind, amount, pot } = action;
+    let (table, player) = (table.to_string(), player.to_string());
+    tx.execute(
+        "INSERT INTO actions (table_address, hand_number, seq, player, position, street, kind, amount, pot, signature)
+         VALUES (?1, ?2,
+                 (SELECT COUNT(*) FROM actions WHERE table_address = ?1 AND hand_number = ?2),
+                 ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![table, hand_number, player, position, street, kind, amount, pot, signature],
+    )?;
+    tx.execute(
+        "INSERT INTO hand_players (table_address, hand_number, player, position, invested)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT DO UPDATE SET invested = invested + excluded.invested",
+        params![table, hand_number, player, position, amount],
+    )?;
+    Ok(())
+}
+
+fn apply(tx: &Transaction, signature: &str, slot: u64, event: &PokerEvent) -> Result<()> {
+    match event {
+        PokerEvent::TableCreated(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO tables
+                 (address, table_id, creator, small_blind, big_blind, max_seats, created_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    e.table.to_string(),
+                    e.table_id,
+                    e.creator.to_string(),
+                    e.small_blind,
+                    e.big_blind,
+                    e.max_seats,
+                    slot
+                ],
+            )?;
+        }
+        PokerEvent::TableClosed(e) => {
+            tx.execute(
+                "UPDATE tables SET closed_slot = ?2 WHERE address = ?1",
+                params![e.table.to_string(), slot],
+            )?;
+        }
+        PokerEvent::PlayerJoined(e) => {
+            tx.execute(
+                "INSERT INTO players (table_address, player, position, bought_in, joined_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![e.table.to_string(), e.player.to_string(), e.position, e.stack, slot],
+            )?;
+        }
+        PokerEvent::PlayerToppedUp(e) => {
+            tx.execute(
+                "UPDATE players SET bought_in = bought_in + ?3
+                 WHERE table_address = ?1 AND player = ?2 AND left_slot IS NULL",
+                params![e.table.to_string(), e.player.to_string(), e.amount],
+            )?;
+        }
+        PokerEvent::PlayerLeft(e) => {
+            let (table, player) = (e.table.to_string(), e.player.to_string());
+            // Standing up mid-hand holds the seat until SeatReleased
+            let left_slot = if e.forfeited { None } else { Some(slot) };
+            let updated = tx.execute(
+                "UPDATE players SET cashed_out = ?3, left_slot = ?4
+                 WHERE table_address = ?1 AND player = ?2 AND left_slot IS NULL",
+                params![table, player, e.cashed_out, left_slot],
+            )?;
+            // Coming back after forfeiting collects anything refunded since
+            if updated == 0 {
+                tx.execute(
+                    "UPDATE players SET cashed_out = COALESCE(cashed_out, 0) + ?3
+                     WHERE id = (SELECT MAX(id) FROM players WHERE table_address = ?1 AND player = ?2)",
+                    params![table, player, e.cashed_out],
+                )?;
+            }
+        }
+        PokerEvent::SeatReleased(e) => {
+            tx.execute(
+                "UPDATE players SET left_slot = ?3
+                 WHERE table_address = ?1 AND player = ?2 AND left_slot IS NULL",
+                params![e.table.to_string(), e.player.to_string(), slot],
+            )?;
+        }
+        PokerEvent::TournamentPlayerMoved(e) => {
+            // The player's chips go with them to a seat at the new table
+            let player = e.player.to_string();
+            tx.execute(
+                "UPDATE players SET cashed_out = ?3, left_slot = ?4
+                 WHERE table_address = ?1 AND player = ?2 AND left_slot IS NULL",
+                params![e.from_table.to_string(), player, e.stack, slot],
+            )?;
+            tx.execute(
+                "INSERT INTO players (table_address, player, position, bought_in, joined_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![e.to_table.to_string(), player, e.position, e.stack, slot],
+            )?;
+        }
+        PokerEvent::PlayerEliminated(e) => {
+            tx.execute(
+                "UPDATE players SET cashed_out = 0, left_slot = ?3
+                 WHERE table_address = ?1 AND player = ?2 AND left_slot IS NULL",
+                params![e.table.to_string(), e.player.to_string(), slot],
+            )?;
+        }
+        PokerEvent::HandStarted(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO hands
+                 (table_address, hand_number, button, small_blind, big_blind, ante, street, board, started_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'PreFlop', ?7, ?8)",
+                params![
+                    e.table.to_string(),
+                    e.hand_number,
+                    e.button_position,
+                    e.small_blind,
+                    e.big_blind,
+                    e.ante,
+                    &[0u8; 5][..],
+                    slot
+                ],
+            )?;
+        }
+        PokerEvent::AntePosted(e) => {
+            let action = ActionRow {
+                player: &e.player,
+                position: e.position,
+                kind: "ante",
+                amount: e.amount,
+                pot: None,
+            };
+            record_action(tx, signature, &e.table, action)?;
+        }
+        PokerEvent::BlindPosted(e) => {
+            let action = ActionRow {
+                player: &e.player,
+                position: e.position,
+                kind: "blind",
+                amount: e.amount,
+                pot: Some(e.pot),
+            };
+            record_action(tx, signature, &e.table, action)?;
+        }
+        PokerEvent::MissedBlindsPosted(e) => {
+            let action = ActionRow {
+                player: &e.player,
+                position: e.position,
+                kind: "missed_blinds",
+                amount: e.dead + e.live,
+                pot: None,
+            };
+            record_action(tx, signature, &e.table, action)?;
+        }
+        PokerEvent::PlayerActioned(e) => {
+            let kind = format!("{:?}", e.action).to_lowercase();
+            let action = ActionRow {
+                player: &e.player,
+                position: e.position,
+                kind: &kind,
+                amount: e.amount,
+                pot: Some(e.pot),
+            };
+            record_action(tx, signature, &e.table, action)?;
+        }
+        PokerEvent::StreetAdvanced(e) => {
+            tx.execute(
+                "UPDATE hands SET street = ?3, board = ?4 WHERE table_address = ?1 AND hand_number = ?2",
+                params![e.table.to_string(), e.hand_number, format!("{:?}", e.street), &e.board[..]],
+            )?;
+        }
+        PokerEvent::HandComplete(e) => {
+            // A hand is completed once; seeing it again would pay the winner twice
+            let (table, winner) = (e.table.to_string(), e.winner.to_string());
+            let completed_slot: Option<Option<u64>> = tx
+                .query_row(
+                    "SELECT completed_slot FROM hands WHERE table_address = ?1 AND hand_number = ?2",
+                    params![table, e.hand_number],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if let Some(Some(_)) = completed_slot {
+                return Ok(());
+            }
+            let paid = tx.execute(
+                "INSERT INTO payouts (kind, source, hand_number, player, amount, signature)
+                 VALUES ('hand', ?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT DO NOTHING",
+                params![table, e.hand_number, winner, e.pot, signature],
+            )?;
+            if paid == 0 {
+                return Ok(());
+            }
+            tx.execute(
+                "UPDATE hands SET winner = ?3, winner_position = ?4, pot = ?5, rake = ?6, completed_slot = ?7
+                 WHERE table_address = ?1 AND hand_number = ?2",
+                params![table, e.hand_number, winner, e.position, e.pot, e.rake, slot],
+            )?;
+            tx.execute(
+                "INSERT INTO hand_players (table_address, hand_number, player, position, won)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT DO UPDATE SET won = won + excluded.won",
+                params![table, e.hand_number, winner, e.position, e.pot],
+            )?;
+        }
+        PokerEvent::HandRefunded(e) => {
+            // Everyone got back what they'd put in
+            let (table, hand_number) = (e.table.to_string(), e.hand_number);
+            tx.execute(
+                "UPDATE hands SET refunded = 1, completed_slot = ?3 WHERE table_address = ?1 AND hand_number = ?2",
+                params![table, hand_number, slot],
+            )?;
+            tx.execute(
+                "UPDATE hand_players SET invested = 0 WHERE table_address = ?1 AND hand_number = ?2",
+                params![table, hand_number],
+            )?;
+        }
+        PokerEvent::TournamentPurchased(e) => {
+            tx.execute(
+                "INSERT INTO tournament_purchases (tournament, player, kind, fee, chips, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    e.tournament.to_string(),
+                    e.player.to_string(),
+                    if e.addon { "addon" } else { "rebuy" },
+                    e.fee,
+                    e.chips,
+                    signature
+                ],
+            )?;
+        }
+        PokerEvent::BountyCollected(e) => {
+            tx.execute(
+                "INSERT INTO bounties (tournament, eliminator, eliminated, paid, added_to_bounty, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    e.tournament.to_string(),
+                    e.eliminator.to_string(),
+                    e.eliminated.to_string(),
+                    e.paid,
+                    e.added_to_bounty,
+                    signature
+                ],
+            )?;
+        }
+        PokerEvent::TournamentPayoutClaimed(e) => {
+            tx.execute(
+                "INSERT INTO payouts (kind, source, player, amount, signature)
+                 VALUES ('tournament', ?1, ?2, ?3, ?4)",
+                params![e.tournament.to_string(), e.player.to_string(), e.amount, signature],
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,18 @@
+//! Indexes the poker program's events into SQLite.
+//!
+//! - [`events`] decodes the events the program logs with `emit!`.
+//! - [`Indexer`] writes them into normalized tables: `tables`, `players` (one row per
+//!   sitting), `hands`, `actions`, `hand_players` (each player's chips in and out
+//!   of a hand) and `payouts`.
+//! - The query API reads back [`PlayerResults`], [`TableActivity`] and whole
+//!   [`HandRecord`]s.
+//!
+//! Feed it logs from a live subscription, an RPC backfill (see the `poker-indexer`
+//! binary) or transactions replayed from a local ledger.
+
+pub mod events;
+mod indexer;
+mod query;
+
+pub use indexer::Indexer;
+pub use query::{ActionRecord, HandRecord, PlayerResults, TableActivity};
@@ -0,0 +1,170 @@
+//! Backfills the index from an RPC endpoint and answers queries against it.
+//!
+//! ```text
+//! poker-indexer --url http://127.0.0.1:8899 sync --follow
+//! poker-indexer player <PLAYER>
+//! poker-indexer table <TABLE>
+//! poker-indexer hand <TABLE> 3
+//! ```
+
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use poker::ID as PROGRAM_ID;
+use poker_indexer::Indexer;
+use solana_rpc_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_rpc_client_api::config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::option_serializer::OptionSerializer;
+use solana_transaction_status_client_types::UiTransactionEncoding;
+
+#[derive(Parser)]
+#[command(name = "poker-indexer", about = "Index poker hands into SQLite and query them")]
+struct Cli {
+    /// SQLite database to write and query
+    #[arg(long, global = true, default_value = "poker-index.db")]
+    db: String,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index every program transaction since the last sync
+    Sync {
+        /// RPC endpoint
+        #[arg(long, short = 'u', default_value = "http://127.0.0.1:8899")]
+        url: String,
+        /// Keep polling for new transactions
+        #[arg(long)]
+        follow: bool,
+        /// Seconds between polls
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+    },
+    /// A player's results across every table
+    Player { player: Pubkey },
+    /// Activity at a table
+    Table { table: Pubkey },
+    /// Every action in a hand
+    Hand { table: Pubkey, hand_number: u64 },
+}
+
+// Ingest the program's transactions newer than the last one indexed, oldest first
+fn sync(rpc: &RpcClient, indexer: &mut Indexer) -> Result<usize> {
+    let until = indexer.last_signature()?.map(|s| Signature::from_str(&s)).transpose()?;
+    let mut pending = Vec::new();
+    let mut before = None;
+    loop {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            until,
+            limit: None,
+            commitment: Some(CommitmentConfig::confirmed()),
+        };
+        let page = rpc.get_signatures_for_address_with_config(&PROGRAM_ID, config)?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = Some(Signature::from_str(&last.signature)?);
+        pending.extend(page.into_iter().filter(|status| status.err.is_none()));
+    }
+
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Json),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let mut events = 0;
+    for status in pending.iter().rev() {
+        let signature = Signature::from_str(&status.signature)?;
+        let transaction = rpc
+            .get_transaction_with_config(&signature, config)
+            .with_context(|| format!("fetching {signature}"))?;
+        let logs = match transaction.transaction.meta.map(|meta| meta.log_messages) {
+            Some(OptionSerializer::Some(logs)) => logs,
+            _ => Vec::new(),
+        };
+        events += indexer.ingest_transaction(&status.signature, transaction.slot, &logs)?;
+    }
+    Ok(events)
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut indexer = Indexer::open(&cli.db).with_context(|| format!("opening {}", cli.db))?;
+
+    match cli.command {
+        Command::Sync { url, follow, interval } => {
+            let rpc = RpcClient::new_with_commitment(url, CommitmentConfig::confirmed());
+            loop {
+                let events = sync(&rpc, &mut indexer)?;
+                println!("Indexed {events} events");
+                if !follow {
+                    break;
+                }
+                thread::sleep(Duration::from_secs(interval));
+            }
+        }
+        Command::Player { player } => {
+            let results = indexer.player_results(&player)?;
+            println!("Player {player}");
+            println!("  Hands played {}  won {}", results.hands_played, results.hands_won);
+            println!("  Invested {}  won {}  net {}", results.invested, results.won, results.net());
+            println!(
+                "  Tournament winnings {}  rebuys and add-ons {}",
+                results.tournament_winnings, results.tournament_purchases
+            );
+            println!("  Knockouts {}  bounties {}", results.knockouts, results.bounties_won);
+        }
+        Command::Table { table } => {
+            let activity = indexer.table_activity(&table)?;
+            println!("Table {table}");
+            println!(
+                "  Hands started {}  completed {}  last {}",
+                activity.hands_started,
+                activity.hands_completed,
+                activity.last_hand.map_or("-".to_string(), |hand| hand.to_string()),
+            );
+            println!("  Paid out {}  rake {}", activity.total_pot, activity.total_rake);
+            println!("  Players {}  seated {}", activity.distinct_players, activity.seated.len());
+            for player in &activity.seated {
+                println!("    {player}");
+            }
+        }
+        Command::Hand { table, hand_number } => {
+            let Some(hand) = indexer.hand(&table, hand_number)? else {
+                println!("Hand {hand_number} not indexed");
+                return Ok(());
+            };
+            println!(
+                "Hand #{}  button {}  blinds {}/{}{}",
+                hand.hand_number,
+                hand.button,
+                hand.small_blind,
+                hand.big_blind,
+                if hand.ante > 0 { format!(" ante {}", hand.ante) } else { String::new() },
+            );
+            for action in &hand.actions {
+                println!(
+                    "  {:<8} seat {} {} {} {}",
+                    action.street, action.position, action.player, action.kind, action.amount
+                );
+            }
+            match (hand.refunded, hand.winner, hand.pot) {
+                (true, ..) => println!("  Refunded"),
+                (false, Some(winner), Some(pot)) => {
+                    println!("  {winner} won {pot} (rake {})", hand.rake.unwrap_or(0))
+                }
+                _ => println!("  In progress, on the {}", hand.street),
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,199 @@
+//! Reading player results and table activity back out.
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use rusqlite::{params, OptionalExtension};
+
+use crate::indexer::Indexer;
+
+/// How a player has done across every hand they played
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct PlayerResults {
+    pub hands_played: u64,
+    pub hands_won: u64,
+    /// Chips put into pots, less refunds
+    pub invested: u64,
+    /// Chips won from pots, after rake
+    pub won: u64,
+    /// Prizes claimed from tournaments, bounties included
+    pub tournament_winnings: u64,
+    /// Paid for tournament rebuys and add-ons
+    pub tournament_purchases: u64,
+    /// Players knocked out in bounty tournaments
+    pub knockouts: u64,
+    /// Bounties earned for those knockouts
+    pub bounties_won: u64,
+}
+
+impl PlayerResults {
+    /// Chips won or lost at the tables
+    pub fn net(&self) -> i64 {
+        self.won as i64 - self.invested as i64
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TableActivity {
+    pub hands_started: u64,
+    pub hands_completed: u64,
+    /// Paid to winners, after rake
+    pub total_pot: u64,
+    pub total_rake: u64,
+    /// Everyone who has played a hand here
+    pub distinct_players: u64,
+    /// Players sitting at the table now
+    pub seated: Vec<Pubkey>,
+    pub last_hand: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ActionRecord {
+    pub player: Pubkey,
+    pub position: u8,
+    pub street: String,
+    /// `ante`, `blind`, `missed_blinds`, `fold`, `check`, `call` or `raise`
+    pub kind: String,
+    pub amount: u64,
+    pub pot: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HandRecord {
+    pub hand_number: u64,
+    pub button: u8,
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub ante: u64,
+    /// Last street reached
+    pub street: String,
+    pub winner: Option<Pubkey>,
+    pub pot: Option<u64>,
+    pub rake: Option<u64>,
+    pub refunded: bool,
+    pub actions: Vec<ActionRecord>,
+}
+
+fn pubkey(text: String) -> rusqlite::Result<Pubkey> {
+    text.parse().map_err(|err| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(err))
+    })
+}
+
+impl Indexer {
+    pub fn player_results(&self, player: &Pubkey) -> Result<PlayerResults> {
+        let player = player.to_string();
+        let mut results = self.conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(won > 0), 0), COALESCE(SUM(invested), 0), COALESCE(SUM(won), 0)
+             FROM hand_players WHERE player = ?1",
+            [&player],
+            |row| {
+                Ok(PlayerResults {
+                    hands_played: row.get(0)?,
+                    hands_won: row.get(1)?,
+                    invested: row.get(2)?,
+                    won: row.get(3)?,
+                    ..PlayerResults::default()
+                })
+            },
+        )?;
+        results.tournament_winnings = self.conn.query_row(
+            "SELECT COALESCE(SUM(amount), 0) FROM payouts WHERE kind = 'tournament' AND player = ?1",
+            [&player],
+            |row| row.get(0),
+        )?;
+        results.tournament_purchases = self.conn.query_row(
+            "SELECT COALESCE(SUM(fee), 0) FROM tournament_purchases WHERE player = ?1",
+            [&player],
+            |row| row.get(0),
+        )?;
+        (results.knockouts, results.bounties_won) = self.conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(paid), 0) FROM bounties WHERE eliminator = ?1",
+            [&player],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(results)
+    }
+
+    pub fn table_activity(&self, table: &Pubkey) -> Result<TableActivity> {
+        let table = table.to_string();
+        let (hands_started, hands_completed, total_pot, total_rake, last_hand) = self.conn.query_row(
+            "SELECT COUNT(*), COUNT(completed_slot), COALESCE(SUM(pot), 0), COALESCE(SUM(rake), 0),
+                    MAX(hand_number)
+             FROM hands WHERE table_address = ?1",
+            [&table],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )?;
+        let distinct_players = self.conn.query_row(
+            "SELECT COUNT(DISTINCT player) FROM hand_players WHERE table_address = ?1",
+            [&table],
+            |row| row.get(0),
+        )?;
+        let seated = self
+            .conn
+            .prepare(
+                "SELECT player FROM players WHERE table_address = ?1 AND left_slot IS NULL ORDER BY position",
+            )?
+            .query_map([&table], |row| pubkey(row.get(0)?))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        Ok(TableActivity {
+            hands_started,
+            hands_completed,
+            total_pot,
+            total_rake,
+            distinct_players,
+            seated,
+            last_hand,
+        })
+    }
+
+    /// A hand with every blind and action in order
+    pub fn hand(&self, table: &Pubkey, hand_number: u64) -> Result<Option<HandRecord>> {
+        let table = table.to_string();
+        let hand = self
+            .conn
+            .query_row(
+                "SELECT button, small_blind, big_blind, ante, street, winner, pot, rake, refunded
+                 FROM hands WHERE table_address = ?1 AND hand_number = ?2",
+                params![table, hand_number],
+                |row| {
+                    Ok(HandRecord {
+                        hand_number,
+                        button: row.get(0)?,
+                        small_blind: row.get(1)?,
+                        big_blind: row.get(2)?,
+                        ante: row.get(3)?,
+                        street: row.get(4)?,
+                        winner: row.get::<_, Option<String>>(5)?.map(pubkey).transpose()?,
+                        pot: row.get(6)?,
+                        rake: row.get(7)?,
+                        refunded: row.get(8)?,
+                        actions: Vec::new(),
+                    })
+                },
+            )
+            .optional()?;
+        let Some(mut hand) = hand else {
+            return Ok(None);
+        };
+
+        hand.actions = self
+            .conn
+            .prepare(
+                "SELECT player, position, street, kind, amount, pot FROM actions
+                 WHERE table_address = ?1 AND hand_number = ?2 ORDER BY seq",
+            )?
+            .query_map(params![table, hand_number], |row| {
+                Ok(ActionRecord {
+                    player: pubkey(row.get(0)?)?,
+                    position: row.get(1)?,
+                    street: row.get(2)?,
+                    kind: row.get(3)?,
+                    amount: row.get(4)?,
+                    pot: row.get(5)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(Some(hand))
+    }
+}
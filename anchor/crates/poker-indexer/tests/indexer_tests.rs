@@ -0,0 +1,307 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Event;
+use base64::Engine;
+use poker::{
+    AntePosted, BlindPosted, BountyCollected, GameState, HandComplete, HandStarted, PlayerActionType,
+    PlayerActioned, PlayerEliminated, PlayerJoined, PlayerLeft, SeatReleased, StreetAdvanced,
+    TableCreated, TableVisibility, TournamentPayoutClaimed, TournamentPlayerMoved, TournamentPurchased,
+    ID as PROGRAM_ID,
+};
+use poker_indexer::Indexer;
+
+// Logs as the runtime writes them for one poker instruction emitting `events`
+fn logs(events: &[&dyn Fn() -> Vec<u8>]) -> Vec<String> {
+    let mut logs = vec![format!("Program {PROGRAM_ID} invoke [1]")];
+    for event in events {
+        logs.push(format!("Program data: {}", base64::engine::general_purpose::STANDARD.encode(event())));
+    }
+    logs.push(format!("Program {PROGRAM_ID} success"));
+    logs
+}
+
+// An indexer that has seen a table created and `players` sit down with 1000 chips each
+struct Fixture {
+    indexer: Indexer,
+    table: Pubkey,
+    slot: u64,
+}
+
+impl Fixture {
+    fn new(players: &[Pubkey]) -> Self {
+        let table = Pubkey::new_unique();
+        let mut fixture = Fixture { indexer: Indexer::open_in_memory().unwrap(), table, slot: 0 };
+        let created = TableCreated {
+            table,
+            table_id: 1,
+            creator: players[0],
+            small_blind: 10,
+            big_blind: 20,
+            min_buy_in: 400,
+            max_buy_in: 2000,
+            max_seats: 6,
+            visibility: TableVisibility::Public,
+        };
+        fixture.ingest(&[&|| created.data()]);
+        for (position, &player) in players.iter().enumerate() {
+            let joined = PlayerJoined { table, player, position: position as u8, stack: 1000 };
+            fixture.ingest(&[&|| joined.data()]);
+        }
+        fixture
+    }
+
+    // Ingest one transaction in the next slot, returning how many events were recorded
+    fn ingest(&mut self, events: &[&dyn Fn() -> Vec<u8>]) -> usize {
+        self.slot += 1;
+        let signature = format!("tx{}", self.slot);
+        self.indexer.ingest_transaction(&signature, self.slot, &logs(events)).unwrap()
+    }
+
+    fn start_hand(&mut self, hand_number: u64, ante: u64) {
+        let started = HandStarted {
+            table: self.table,
+            hand_number,
+            button_position: 0,
+            small_blind: 10,
+            big_blind: 20,
+            ante,
+            pot: 0,
+        };
+        self.ingest(&[&|| started.data()]);
+    }
+}
+
+// Alice and Bob play hand 1 to a flop raise Bob folds to, with a 4 chip rake
+fn played_hand() -> (Fixture, Pubkey, Pubkey) {
+    let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut fixture = Fixture::new(&[alice, bob]);
+    let table = fixture.table;
+    fixture.start_hand(1, 0);
+    fixture.ingest(&[
+        &|| BlindPosted { table, player: alice, position: 0, amount: 10, pot: 10 }.data(),
+        &|| BlindPosted { table, player: bob, position: 1, amount: 20, pot: 30 }.data(),
+    ]);
+    fixture.ingest(&[&|| action(table, (alice, 0), PlayerActionType::Call, 10, 40).data()]);
+    let flop = StreetAdvanced { table, hand_number: 1, street: GameState::Flop, board: [0; 5], pot: 40 };
+    fixture.ingest(&[&|| flop.data()]);
+    fixture.ingest(&[&|| action(table, (alice, 0), PlayerActionType::Raise, 40, 80).data()]);
+    let won = HandComplete { table, hand_number: 1, winner: alice, position: 0, pot: 76, rake: 4 };
+    fixture.ingest(&[&|| action(table, (bob, 1), PlayerActionType::Fold, 0, 80).data(), &|| won.data()]);
+    (fixture, alice, bob)
+}
+
+fn action(
+    table: Pubkey,
+    (player, position): (Pubkey, u8),
+    action: PlayerActionType,
+    amount: u64,
+    pot: u64,
+) -> PlayerActioned {
+    PlayerActioned { table, player, position, action, amount, stack: 0, current_bet: 20, pot }
+}
+
+#[test]
+fn test_hand_lists_actions_by_street() {
+    let (fixture, alice, _) = played_hand();
+    let hand = fixture.indexer.hand(&fixture.table, 1).unwrap().unwrap();
+    let kinds: Vec<(&str, &str)> = hand.actions.iter().map(|a| (a.street.as_str(), a.kind.as_str())).collect();
+    assert_eq!(
+        kinds,
+        [
+            ("PreFlop", "blind"),
+            ("PreFlop", "blind"),
+            ("PreFlop", "call"),
+            ("Flop", "raise"),
+            ("Flop", "fold"),
+        ]
+    );
+    assert_eq!(hand.street, "Flop");
+    assert_eq!((hand.winner, hand.pot, hand.rake), (Some(alice), Some(76), Some(4)));
+    assert!(fixture.indexer.hand(&fixture.table, 2).unwrap().is_none());
+}
+
+#[test]
+fn test_skips_other_programs_data_and_repeated_transactions() {
+    let alice = Pubkey::new_unique();
+    let mut fixture = Fixture::new(&[alice]);
+    let table = fixture.table;
+    fixture.start_hand(1, 0);
+
+    // Data logged by another program the poker program calls isn't an event of ours
+    let raise = action(table, (alice, 0), PlayerActionType::Raise, 40, 80);
+    let mut nested = logs(&[&|| raise.data()]);
+    let other = Pubkey::new_unique();
+    nested.splice(
+        1..1,
+        [
+            format!("Program {other} invoke [2]"),
+            format!("Program data: {}", base64::engine::general_purpose::STANDARD.encode(raise.data())),
+            format!("Program {other} success"),
+        ],
+    );
+    assert_eq!(fixture.indexer.ingest_transaction("raise", 10, &nested).unwrap(), 1);
+    // Ingesting the same transaction again does nothing
+    assert_eq!(fixture.indexer.ingest_transaction("raise", 10, &nested).unwrap(), 0);
+    assert_eq!(fixture.indexer.hand(&table, 1).unwrap().unwrap().actions.len(), 1);
+    assert_eq!(fixture.indexer.last_signature().unwrap().as_deref(), Some("raise"));
+}
+
+#[test]
+fn test_player_results_net_winnings_against_investment() {
+    let (fixture, alice, bob) = played_hand();
+    let alice_results = fixture.indexer.player_results(&alice).unwrap();
+    assert_eq!((alice_results.hands_played, alice_results.hands_won), (1, 1));
+    assert_eq!((alice_results.invested, alice_results.won, alice_results.net()), (60, 76, 16));
+    let bob_results = fixture.indexer.player_results(&bob).unwrap();
+    assert_eq!((bob_results.hands_won, bob_results.net()), (0, -20));
+}
+
+#[test]
+fn test_table_activity_totals_hands() {
+    let (fixture, alice, bob) = played_hand();
+    let activity = fixture.indexer.table_activity(&fixture.table).unwrap();
+    assert_eq!((activity.hands_started, activity.hands_completed), (1, 1));
+    assert_eq!((activity.total_pot, activity.total_rake), (76, 4));
+    assert_eq!(activity.distinct_players, 2);
+    assert_eq!(activity.seated, vec![alice, bob]);
+    assert_eq!(activity.last_hand, Some(1));
+}
+
+#[test]
+fn test_repeated_hand_complete_is_paid_once() {
+    let (mut fixture, alice, _) = played_hand();
+    let table = fixture.table;
+    let won = HandComplete { table, hand_number: 1, winner: alice, position: 0, pot: 76, rake: 4 };
+    fixture.ingest(&[&|| won.data()]);
+    // A hand we didn't see start is paid once too
+    let unseen = HandComplete { table, hand_number: 7, winner: alice, position: 0, pot: 30, rake: 0 };
+    fixture.ingest(&[&|| unseen.data()]);
+    fixture.ingest(&[&|| unseen.data()]);
+
+    let results = fixture.indexer.player_results(&alice).unwrap();
+    assert_eq!((results.hands_won, results.won), (2, 76 + 30));
+    let activity = fixture.indexer.table_activity(&table).unwrap();
+    assert_eq!((activity.hands_completed, activity.total_pot), (1, 76));
+    let payouts: u64 = fixture
+        .indexer
+        .connection()
+        .query_row("SELECT COUNT(*) FROM payouts WHERE kind = 'hand'", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(payouts, 2);
+}
+
+#[test]
+fn test_antes_are_credited_to_each_player() {
+    let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut fixture = Fixture::new(&[alice, bob]);
+    let table = fixture.table;
+    fixture.start_hand(1, 5);
+    fixture.ingest(&[
+        &|| AntePosted { table, player: alice, position: 0, amount: 5 }.data(),
+        &|| AntePosted { table, player: bob, position: 1, amount: 5 }.data(),
+        &|| BlindPosted { table, player: alice, position: 0, amount: 10, pot: 20 }.data(),
+        &|| BlindPosted { table, player: bob, position: 1, amount: 20, pot: 40 }.data(),
+    ]);
+
+    let hand = fixture.indexer.hand(&table, 1).unwrap().unwrap();
+    let kinds: Vec<(&str, u64, Option<u64>)> =
+        hand.actions.iter().map(|a| (a.kind.as_str(), a.amount, a.pot)).collect();
+    assert_eq!(
+        kinds,
+        [("ante", 5, None), ("ante", 5, None), ("blind", 10, Some(20)), ("blind", 20, Some(40))]
+    );
+    assert_eq!(fixture.indexer.player_results(&alice).unwrap().invested, 15);
+    assert_eq!(fixture.indexer.player_results(&bob).unwrap().invested, 25);
+}
+
+#[test]
+fn test_forfeited_seat_is_held_until_released() {
+    let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut fixture = Fixture::new(&[alice, bob]);
+    let table = fixture.table;
+    fixture.start_hand(1, 0);
+
+    // Standing up mid-hand cashes out what's behind but keeps the seat for the hand
+    let forfeit = PlayerLeft { table, player: bob, position: 1, cashed_out: 980, forfeited: true };
+    fixture.ingest(&[&|| forfeit.data()]);
+    assert_eq!(fixture.indexer.table_activity(&table).unwrap().seated, vec![alice, bob]);
+
+    let won = HandComplete { table, hand_number: 1, winner: alice, position: 0, pot: 30, rake: 0 };
+    let released = SeatReleased { table, player: bob, position: 1 };
+    fixture.ingest(&[&|| won.data(), &|| released.data()]);
+    assert_eq!(fixture.indexer.table_activity(&table).unwrap().seated, vec![alice]);
+
+    // Coming back to close the account collects anything refunded in the meantime
+    let closed = PlayerLeft { table, player: bob, position: 1, cashed_out: 15, forfeited: false };
+    fixture.ingest(&[&|| closed.data()]);
+    let (cashed_out, left_slot): (u64, u64) = fixture
+        .indexer
+        .connection()
+        .query_row(
+            "SELECT cashed_out, left_slot FROM players WHERE player = ?1",
+            [bob.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!((cashed_out, left_slot), (995, fixture.slot - 1));
+}
+
+#[test]
+fn test_records_tournament_purchases_and_bounties() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    let tournament = Pubkey::new_unique();
+    let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let purchases = logs(&[
+        &|| TournamentPurchased { tournament, player: alice, addon: false, fee: 100, chips: 1500 }.data(),
+        &|| TournamentPurchased { tournament, player: alice, addon: true, fee: 50, chips: 1000 }.data(),
+    ]);
+    assert_eq!(indexer.ingest_transaction("purchases", 1, &purchases).unwrap(), 2);
+    let knockout =
+        BountyCollected { tournament, eliminator: alice, eliminated: bob, paid: 25, added_to_bounty: 25 };
+    indexer.ingest_transaction("knockout", 2, &logs(&[&|| knockout.data()])).unwrap();
+    let claim = TournamentPayoutClaimed { tournament, player: alice, finish_place: 1, amount: 525 };
+    indexer.ingest_transaction("claim", 3, &logs(&[&|| claim.data()])).unwrap();
+
+    let results = indexer.player_results(&alice).unwrap();
+    assert_eq!(results.tournament_purchases, 150);
+    assert_eq!((results.knockouts, results.bounties_won), (1, 25));
+    // The bounty was paid with the prize, not on its own
+    assert_eq!(results.tournament_winnings, 525);
+    let kinds: Vec<String> = indexer
+        .connection()
+        .prepare("SELECT kind FROM tournament_purchases ORDER BY id")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(kinds, ["rebuy", "addon"]);
+    assert_eq!(indexer.player_results(&bob).unwrap(), Default::default());
+}
+
+#[test]
+fn test_tournament_moves_and_eliminations_free_seats() {
+    let (alice, bob, carol) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let mut fixture = Fixture::new(&[alice, bob, carol]);
+    let (tournament, table) = (Pubkey::new_unique(), fixture.table);
+    let to_table = Pubkey::new_unique();
+
+    let moved =
+        TournamentPlayerMoved { tournament, player: bob, from_table: table, to_table, position: 4, stack: 1800 };
+    fixture.ingest(&[&|| moved.data()]);
+    let eliminated = PlayerEliminated { tournament, table, player: carol, finish_place: 3 };
+    fixture.ingest(&[&|| eliminated.data()]);
+
+    assert_eq!(fixture.indexer.table_activity(&table).unwrap().seated, vec![alice]);
+    assert_eq!(fixture.indexer.table_activity(&to_table).unwrap().seated, vec![bob]);
+    let seats: Vec<(String, u8, u64, Option<u64>)> = fixture
+        .indexer
+        .connection()
+        .prepare("SELECT table_address, position, bought_in, cashed_out FROM players WHERE player = ?1 ORDER BY id")
+        .unwrap()
+        .query_map([bob.to_string()], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(seats, [(table.to_string(), 1, 1000, Some(1800)), (to_table.to_string(), 4, 1800, None)]);
+}
@@ -1,12 +1,29 @@
 use anchor_lang::prelude::*;
+use poker_engine::{Action, Betting, RuleError, Seat, SeatStatus, Street};
 
 declare_id!("Ev6eGkLNZQjgXekHWY1UMb1qkTVUzWsX1ziqcixqsieV");
 
-pub const MAX_PLAYERS: usize = 9;
+pub const MAX_PLAYERS: usize = 10; // Most seats any table can have
+pub const MIN_SEATS: u8 = 2;
+pub const MAX_SEATS_DEFAULT: u8 = 9;
 pub const SMALL_BLIND_DEFAULT: u64 = 10;
 pub const BIG_BLIND_DEFAULT: u64 = 20;
 pub const MIN_BUY_IN_DEFAULT: u64 = 1000;
 pub const MAX_BUY_IN_DEFAULT: u64 = 10000;
+pub const ACTION_TIMEOUT_DEFAULT: i64 = 30;
+pub const MAX_ACTION_TIMEOUT: i64 = 86_400;
+pub const MAX_BLIND_LEVELS: usize = 16;
+pub const MAX_TOURNAMENT_TABLES: usize = 8;
+pub const MAX_TOURNAMENT_ENTRANTS: usize = MAX_TOURNAMENT_TABLES * MAX_PLAYERS;
+pub const MAX_PAYOUT_PLACES: usize = 16;
+pub const MAX_ICM_DEAL_PLAYERS: usize = 6; // ICM walks every finishing order, so its cost doubles per player
+pub const ICM_PROBABILITY_SCALE: u128 = 1_000_000_000_000_000_000;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_WAITLIST: usize = 16;
+pub const MAX_RAKE_BPS: u16 = 1_000;
+pub const MAX_HAND_ACTIONS: usize = 96;
+pub const WAITLIST_SEAT_WINDOW_SECONDS: i64 = 60;
+pub const REBUY_DECISION_SECONDS: i64 = 60; // How long a busted player who can rebuy holds up elimination
 
 // Helper function to find next active player using remaining_accounts
 fn find_next_active_player<'info>(
@@ -15,46 +32,95 @@ fn find_next_active_player<'info>(
     current_index: u8,
     player_state_accounts: &[AccountInfo<'info>],
 ) -> Result<u8> {
-    let mut next = (current_index + 1) % MAX_PLAYERS as u8;
-    let mut checked = 0;
-
-    while checked < MAX_PLAYERS {
-        // Skip empty seats
-        if table.players[next as usize] == Pubkey::default() {
-            next = (next + 1) % MAX_PLAYERS as u8;
-            checked += 1;
+    // Seats whose player state we were given and who can still act
+    let mut active = [false; MAX_PLAYERS];
+    for seat in 0..table.max_seats {
+        let player_pubkey = table.players[seat as usize];
+        if player_pubkey == Pubkey::default() {
             continue;
         }
 
-        // Get player pubkey at this position
-        let player_pubkey = table.players[next as usize];
-
-        // Derive expected PDA for this player
         let (expected_pda, _) = Pubkey::find_program_address(
             &[b"player", table_key.as_ref(), player_pubkey.as_ref()],
             &crate::ID,
         );
+        if let Some(account_info) = player_state_accounts
+            .iter()
+            .find(|account_info| account_info.key == &expected_pda)
+        {
+            let data = account_info.try_borrow_data()?;
+            let player_state = PlayerState::try_deserialize(&mut &data[..])?;
+            active[seat as usize] = player_state.status == PlayerStatus::Active;
+        }
+    }
 
-        // Try to find this account in remaining_accounts
-        for account_info in player_state_accounts {
-            if account_info.key == &expected_pda {
-                // Try to deserialize the player state
-                let data = account_info.try_borrow_data()?;
-                let player_state = PlayerState::try_deserialize(&mut &data[..])?;
+    poker_engine::next_seat(table.max_seats, current_index, |seat| active[seat as usize])
+        .ok_or(PokerError::NoActivePlayersRemaining.into())
+}
 
-                // Check if player can act (is active, not folded/all-in)
-                if player_state.status == PlayerStatus::Active {
-                    return Ok(next);
-                }
-                break;
-            }
-        }
+// Helper to read a player state only if it's this program's and belongs to a player
+// seated at this table
+fn seated_player_state<'info>(
+    table_key: &Pubkey,
+    table: &PokerTable,
+    account_info: &AccountInfo<'info>,
+) -> Result<Option<PlayerState>> {
+    if account_info.owner != &crate::ID {
+        return Ok(None);
+    }
+    let data = account_info.try_borrow_data()?;
+    if !data.starts_with(PlayerState::DISCRIMINATOR) {
+        return Ok(None);
+    }
+    let player_state = PlayerState::try_deserialize(&mut &data[..])?;
+    let seated = player_state.table == *table_key
+        && table.players.get(player_state.position as usize) == Some(&player_state.player);
+    Ok(seated.then_some(player_state))
+}
 
-        next = (next + 1) % MAX_PLAYERS as u8;
-        checked += 1;
+// Helper to check player_state_accounts hold the state of every player in `seats`,
+// none of them twice
+fn require_player_states<'info>(
+    table_key: &Pubkey,
+    table: &PokerTable,
+    player_state_accounts: &[AccountInfo<'info>],
+    seats: u16,
+) -> Result<()> {
+    let mut seen = 0u16;
+    for account_info in player_state_accounts {
+        let Some(player_state) = seated_player_state(table_key, table, account_info)? else {
+            continue;
+        };
+        let bit = 1u16 << player_state.position;
+        require!(seen & bit == 0, PokerError::DuplicatePlayerAccount);
+        seen |= bit;
     }
+    require!(seen & seats == seats, PokerError::MissingPlayerAccounts);
+    Ok(())
+}
+
+// Helper to check player_state_accounts hold the state of every player dealt into the hand
+fn require_dealt_in_states<'info>(
+    table_key: &Pubkey,
+    table: &PokerTable,
+    player_state_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let dealt_in = (0..table.max_seats)
+        .filter(|seat| table.is_dealt_in(*seat))
+        .fold(0u16, |mask, seat| mask | 1u16 << seat);
+    require_player_states(table_key, table, player_state_accounts, dealt_in)
+}
 
-    Err(PokerError::NoActivePlayersRemaining.into())
+// Helper to check player_state_accounts hold the state of every seated player
+fn require_seated_states<'info>(
+    table_key: &Pubkey,
+    table: &PokerTable,
+    player_state_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let seated = (0..table.max_seats)
+        .filter(|seat| table.players[*seat as usize] != Pubkey::default())
+        .fold(0u16, |mask, seat| mask | 1u16 << seat);
+    require_player_states(table_key, table, player_state_accounts, seated)
 }
 
 // Helper to check if betting round is complete
@@ -62,42 +128,93 @@ fn is_betting_round_complete<'info>(
     table: &PokerTable,
     player_state_accounts: &[AccountInfo<'info>],
 ) -> Result<bool> {
-    let mut active_players = Vec::new();
-
-    // Collect all active/all-in players from remaining_accounts
+    let mut seats = Vec::with_capacity(player_state_accounts.len());
     for account_info in player_state_accounts {
         let data = account_info.try_borrow_data()?;
         let player_state = PlayerState::try_deserialize(&mut &data[..])?;
-
-        match player_state.status {
-            PlayerStatus::Active | PlayerStatus::AllIn => {
-                active_players.push(player_state);
-            }
-            PlayerStatus::Folded => continue,
-        }
+        seats.push(player_state.seat());
     }
 
-    if active_players.is_empty() {
-        return Ok(true);
-    }
+    Ok(poker_engine::is_round_complete(table.current_bet, &seats))
+}
 
-    // Check all active players have acted
-    for player_state in &active_players {
-        if player_state.status == PlayerStatus::Active
-            && !player_state.has_acted_this_street {
-            return Ok(false);
-        }
-    }
+// Helper to apply a betting action for the player to act and pass the turn on
+fn apply_player_action<'info>(
+    table_key: Pubkey,
+    table: &mut PokerTable,
+    player_state: &mut PlayerState,
+    action: PlayerActionType,
+    raise_amount: u64,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let mut betting = table.betting();
+    let mut seat = player_state.seat();
+    let added = poker_engine::apply_action(
+        &mut betting,
+        &mut seat,
+        player_state.position,
+        action.to_action(raise_amount),
+    )
+    .map_err(PokerError::from)?;
+    table.set_betting(betting);
+    player_state.set_seat(seat);
+    player_state.commit(table.hand_number, added);
 
-    // Check all active players match current bet (or all-in)
-    for player_state in &active_players {
-        if player_state.status == PlayerStatus::Active
-            && player_state.current_bet != table.current_bet {
-            return Ok(false);
-        }
+    match action {
+        PlayerActionType::Fold => msg!("Player folded"),
+        PlayerActionType::Check => msg!("Player checked"),
+        PlayerActionType::Call => msg!("Player called {}", added),
+        PlayerActionType::Raise => msg!("Player raised to {}", table.current_bet),
     }
 
-    Ok(true)
+    let (history_account, player_accounts) =
+        split_remaining_accounts(remaining_accounts, &table.hand_history);
+    record_hand_actions(
+        table,
+        history_account,
+        vec![HandAction::Action {
+            seat: player_state.position,
+            action,
+            amount: added,
+        }],
+    )?;
+
+    emit!(PlayerActioned {
+        table: table_key,
+        player: player_state.player,
+        position: player_state.position,
+        action,
+        amount: added,
+        stack: player_state.stack,
+        current_bet: table.current_bet,
+        pot: table.pot,
+    });
+
+    // Move to next active player
+    // If no player states were passed, fall back to simple next-seat logic
+    let next_player = if player_accounts.is_empty() {
+        table.next_dealt_in(table.current_player_index)?
+    } else {
+        find_next_active_player(
+            &table_key,
+            table,
+            table.current_player_index,
+            &player_accounts,
+        )?
+    };
+    table.current_player_index = next_player;
+    table.turn_started_at = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
+
+// Helper to move a hand on to its next street
+fn next_street(state: GameState) -> Result<GameState> {
+    state
+        .street()
+        .and_then(Street::next)
+        .map(GameState::from)
+        .ok_or(PokerError::WrongGameState.into())
 }
 
 // Helper to reset player states for new street
@@ -112,25 +229,630 @@ fn reset_player_states_for_street<'info>(
         player_state.has_acted_this_street = false;
         // Don't change status (keep folded/all-in)
 
-        player_state.try_serialize(&mut &mut data[8..])?;
+        player_state.try_serialize(&mut &mut data[..])?;
     }
     Ok(())
 }
 
-// Helper to reset player states for new hand
+// Helper to reset player states for new hand. States from other tables are left alone.
 fn reset_player_states_for_hand<'info>(
+    table_key: &Pubkey,
+    table: &PokerTable,
     player_state_accounts: &[AccountInfo<'info>],
 ) -> Result<()> {
     for account_info in player_state_accounts {
+        if seated_player_state(table_key, table, account_info)?.is_none() {
+            continue;
+        }
         let mut data = account_info.try_borrow_mut_data()?;
         let mut player_state = PlayerState::try_deserialize(&mut &data[..])?;
 
         player_state.current_bet = 0;
         player_state.has_acted_this_street = false;
-        player_state.status = PlayerStatus::Active;
+        player_state.status = if table.sitting_out & (1u16 << player_state.position) != 0 {
+            PlayerStatus::SittingOut
+        } else {
+            PlayerStatus::Active
+        };
+
+        player_state.try_serialize(&mut &mut data[..])?;
+    }
+    Ok(())
+}
+
+// Helper to free the seats of players who stood up mid-hand, once that hand is over
+fn release_leaving_seats(table_key: Pubkey, table: &mut PokerTable) -> Result<()> {
+    if table.leaving != 0 {
+        table.seat_freed_at = Clock::get()?.unix_timestamp;
+    }
+    for seat in 0..table.max_seats as usize {
+        let bit = 1u16 << seat;
+        if table.leaving & bit != 0 {
+            emit!(SeatReleased {
+                table: table_key,
+                player: table.players[seat],
+                position: seat as u8,
+            });
+            table.players[seat] = Pubkey::default();
+            table.player_count -= 1;
+            table.sitting_out &= !bit;
+            table.missed_small_blinds &= !bit;
+            table.missed_big_blinds &= !bit;
+        }
+    }
+    table.leaving = 0;
+    Ok(())
+}
+
+// Helper to list the chips each player put into the current hand, one entry per player
+fn hand_contributions(
+    table_key: &Pubkey,
+    hand_number: u64,
+    player_state_accounts: &[AccountInfo],
+    mut contributions: Vec<(Pubkey, u64)>,
+) -> Result<Vec<(Pubkey, u64)>> {
+    for account_info in player_state_accounts {
+        if account_info.owner != &crate::ID {
+            continue;
+        }
+        let data = account_info.try_borrow_data()?;
+        if !data.starts_with(PlayerState::DISCRIMINATOR) {
+            continue;
+        }
+        let player_state = PlayerState::try_deserialize(&mut &data[..])?;
+        if player_state.table == *table_key
+            && !contributions.iter().any(|(player, _)| *player == player_state.player)
+        {
+            contributions.push((player_state.player, player_state.committed_this_hand(hand_number)));
+        }
+    }
+    Ok(contributions)
+}
+
+// Helper to separate player stats accounts from the rest of remaining_accounts
+fn split_player_stats<'info>(
+    accounts: &[AccountInfo<'info>],
+) -> (Vec<AccountInfo<'info>>, Vec<AccountInfo<'info>>) {
+    accounts.iter().cloned().partition(|account_info| {
+        account_info.owner == &crate::ID
+            && account_info
+                .try_borrow_data()
+                .is_ok_and(|data| data.starts_with(PlayerStats::DISCRIMINATOR))
+    })
+}
+
+// Helper to split a hand's rake between the players it's attributed to, crediting their
+// cross-table stats and moving their rakeback out of the fee vault
+fn attribute_rake(
+    table: &PokerTable,
+    pot: u64,
+    rake: u64,
+    contributions: &[(Pubkey, u64)],
+    fee_vault: &AccountInfo,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    let weights: Vec<(Pubkey, u64)> = match table.rake_attribution {
+        RakeAttribution::Contributed => {
+            // Every contributor must be accounted for, or the shares would be skewed
+            let total: u64 = contributions.iter().map(|(_, committed)| committed).sum();
+            require!(total == pot, PokerError::MissingPlayerAccounts);
+            contributions.to_vec()
+        }
+        RakeAttribution::Dealt => (0..table.max_seats)
+            .filter(|seat| table.is_dealt_in(*seat))
+            .map(|seat| (table.players[seat as usize], 1))
+            .collect(),
+    };
+    let total_weight: u64 = weights.iter().map(|(_, weight)| weight).sum();
+    if total_weight == 0 {
+        return Ok(());
+    }
+
+    for (player, weight) in weights {
+        let share = (rake as u128 * weight as u128 / total_weight as u128) as u64;
+        if share == 0 {
+            continue;
+        }
+        let (stats_key, _) =
+            Pubkey::find_program_address(&[b"player_stats", player.as_ref()], &crate::ID);
+        // The address is still required so a cranker can't withhold rakeback by leaving
+        // it out; a player who never created stats gets none and their share stays put
+        let stats_account = remaining_accounts
+            .iter()
+            .find(|account_info| account_info.key == &stats_key)
+            .ok_or(PokerError::MissingPlayerStatsAccount)?;
+        if stats_account.owner != &crate::ID {
+            continue;
+        }
+        let rakeback = share * table.rakeback_bps as u64 / BPS_DENOMINATOR;
+
+        {
+            let mut data = stats_account.try_borrow_mut_data()?;
+            let mut stats = PlayerStats::try_deserialize(&mut &data[..])?;
+            stats.rake_contributed += share;
+            stats.rakeback_earned += rakeback;
+            stats.try_serialize(&mut &mut data[..])?;
+        }
+        // Rakeback waits in the stats account until the player claims it
+        if rakeback > 0 {
+            fee_vault.sub_lamports(rakeback)?;
+            stats_account.add_lamports(rakeback)?;
+        }
+    }
+    Ok(())
+}
+
+// Helper to move the table's rake out of the pot into its fee vault at hand completion.
+// Must run before the hand is marked complete so no-flop-no-drop can see the street.
+fn collect_rake(
+    table: &mut Account<PokerTable>,
+    fee_vault: Option<&AccountInfo>,
+    contributions: &[(Pubkey, u64)],
+    remaining_accounts: &[AccountInfo],
+) -> Result<u64> {
+    if table.fee_vault == Pubkey::default()
+        || table.tournament != Pubkey::default()
+        || table.rake_bps == 0
+    {
+        return Ok(0);
+    }
+    let fee_vault = fee_vault.ok_or(PokerError::MissingFeeVaultAccount)?;
+    if table.no_flop_no_drop && table.game_state == GameState::PreFlop {
+        return Ok(0);
+    }
+
+    let pot = table.pot;
+    let mut rake = (pot as u128 * table.rake_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+    if table.rake_cap > 0 {
+        rake = rake.min(table.rake_cap);
+    }
+    if rake == 0 {
+        return Ok(0);
+    }
+
+    table.pot -= rake;
+    table.sub_lamports(rake)?;
+    fee_vault.add_lamports(rake)?;
+
+    {
+        let mut data = fee_vault.try_borrow_mut_data()?;
+        let mut vault = FeeVault::try_deserialize(&mut &data[..])?;
+        vault.total_collected += rake;
+        vault.try_serialize(&mut &mut data[..])?;
+    }
+    // Only tables paying rakeback attribute rake to players
+    if table.rakeback_bps > 0 {
+        attribute_rake(table, pot, rake, contributions, fee_vault, remaining_accounts)?;
+    }
+
+    emit!(RakeCollected {
+        table: table.key(),
+        hand_number: table.hand_number,
+        pot,
+        rake,
+    });
+    Ok(rake)
+}
+
+// Helper to list the players still in the hand when it's decided. Hole cards never leave
+// the encrypted deal, so a contested pot records each hand as not revealed and an
+// uncontested one records no showdown at all.
+fn showdown_actions(
+    table_key: &Pubkey,
+    player_state_accounts: &[AccountInfo],
+    winner_seat: u8,
+) -> Result<Vec<HandAction>> {
+    let mut seats = vec![winner_seat];
+    for account_info in player_state_accounts {
+        if account_info.owner != &crate::ID {
+            continue;
+        }
+        let data = account_info.try_borrow_data()?;
+        if !data.starts_with(PlayerState::DISCRIMINATOR) {
+            continue;
+        }
+        let player_state = PlayerState::try_deserialize(&mut &data[..])?;
+        if player_state.table == *table_key
+            && matches!(player_state.status, PlayerStatus::Active | PlayerStatus::AllIn)
+            && !seats.contains(&player_state.position)
+        {
+            seats.push(player_state.position);
+        }
+    }
+    if seats.len() < 2 {
+        return Ok(Vec::new());
+    }
+    seats.sort_unstable();
+    Ok(seats.into_iter().map(|seat| HandAction::Showdown { seat, cards: None }).collect())
+}
+
+// Helper to append entries to the current hand's history, when one is being recorded
+fn record_hand_actions(
+    table: &PokerTable,
+    history_account: Option<&AccountInfo>,
+    actions: Vec<HandAction>,
+) -> Result<()> {
+    if table.hand_history == Pubkey::default() {
+        return Ok(());
+    }
+    let history_account = history_account.ok_or(PokerError::MissingHandHistoryAccount)?;
+    let mut data = history_account.try_borrow_mut_data()?;
+    let mut history = HandHistory::try_deserialize(&mut &data[..])?;
+
+    for action in actions {
+        // A full log is flagged rather than blocking play
+        if history.actions.len() < MAX_HAND_ACTIONS {
+            history.actions.push(action);
+        } else {
+            history.truncated = true;
+        }
+    }
+
+    history.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+// Helper to check the signer is the table authority or the program-wide admin
+fn require_table_admin(
+    table: &PokerTable,
+    config: &Option<Account<ProgramConfig>>,
+    signer: &Pubkey,
+) -> Result<()> {
+    require!(
+        table.authority == *signer
+        || config.as_ref().is_some_and(|config| config.admin == *signer),
+        PokerError::Unauthorized
+    );
+    Ok(())
+}
+
+// Helper to close a program-owned account passed through remaining_accounts
+fn close_remaining_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = account.lamports();
+    account.sub_lamports(lamports)?;
+    destination.add_lamports(lamports)?;
+    account.assign(&anchor_lang::system_program::ID);
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+// Helper to pull an auxiliary account (tournament, etc.) out of remaining_accounts by key,
+// returning it alongside the player state accounts that remain
+fn split_remaining_accounts<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    key: &Pubkey,
+) -> (Option<&'a AccountInfo<'info>>, Vec<AccountInfo<'info>>) {
+    let mut found = None;
+    let mut rest = Vec::with_capacity(accounts.len());
+
+    for account_info in accounts {
+        if *key != Pubkey::default() && account_info.key == key && found.is_none() {
+            found = Some(account_info);
+        } else {
+            rest.push(account_info.clone());
+        }
+    }
+
+    (found, rest)
+}
+
+// Helper to move a blind structure forward to the level in effect for `next_hand` and
+// copy that level's blinds onto the table
+fn apply_blind_structure(
+    table: &mut PokerTable,
+    blind_structure: &mut BlindStructure,
+    next_hand: u64,
+    now: i64,
+) {
+    // The level clock starts with the first hand dealt under this structure
+    if blind_structure.level_started_hand == 0 {
+        blind_structure.level_started_hand = next_hand;
+        blind_structure.level_started_at = now;
+    }
+
+    while (blind_structure.current_level as usize) + 1 < blind_structure.levels.len() {
+        let expired = match blind_structure.duration_unit {
+            LevelDurationUnit::Hands => {
+                next_hand - blind_structure.level_started_hand >= blind_structure.level_duration
+            }
+            LevelDurationUnit::Seconds => {
+                now.saturating_sub(blind_structure.level_started_at) as u64
+                    >= blind_structure.level_duration
+            }
+        };
+        if !expired {
+            break;
+        }
+
+        blind_structure.current_level += 1;
+        blind_structure.level_started_hand = next_hand;
+        blind_structure.level_started_at = match blind_structure.duration_unit {
+            LevelDurationUnit::Hands => now,
+            LevelDurationUnit::Seconds => {
+                blind_structure.level_started_at + blind_structure.level_duration as i64
+            }
+        };
+    }
+
+    let level = blind_structure.levels[blind_structure.current_level as usize];
+    table.small_blind = level.small_blind;
+    table.big_blind = level.big_blind;
+    table.ante = level.ante;
+}
+
+// Helper to take the ante from every dealt-in player
+fn collect_antes<'info>(
+    table_key: &Pubkey,
+    table: &mut PokerTable,
+    player_state_accounts: &[AccountInfo<'info>],
+) -> Result<Vec<(Pubkey, u8, u64)>> {
+    let mut antes = Vec::new();
+    for account_info in player_state_accounts {
+        if seated_player_state(table_key, table, account_info)?.is_none() {
+            continue;
+        }
+        let mut data = account_info.try_borrow_mut_data()?;
+        let mut player_state = PlayerState::try_deserialize(&mut &data[..])?;
+        if player_state.status == PlayerStatus::SittingOut {
+            continue;
+        }
+
+        let mut betting = table.betting();
+        let mut seat = player_state.seat();
+        let ante = poker_engine::post_ante(&mut betting, &mut seat, table.ante);
+        table.set_betting(betting);
+        player_state.set_seat(seat);
+        player_state.commit(table.hand_number, ante);
+        antes.push((player_state.player, player_state.position, ante));
+
+        player_state.try_serialize(&mut &mut data[..])?;
+    }
+    Ok(antes)
+}
+
+// Helper to check a table can be handed over to a tournament
+fn require_tournament_ready_table(table: &PokerTable) -> Result<()> {
+    require!(
+        table.tournament == Pubkey::default()
+        && table.player_count == 0
+        && table.game_state == GameState::WaitingForPlayers,
+        PokerError::TableNotAvailable
+    );
+    // Tournament blinds always go up on the table's schedule
+    require!(
+        table.blind_structure != Pubkey::default(),
+        PokerError::MissingBlindStructureAccount
+    );
+    Ok(())
+}
+
+// Helper to check whether a tournament table may be broken up: its players must fit
+// on the other tables, and it must be (one of) the shortest tables still in play
+fn is_table_breakable(tournament: &Tournament, table_index: usize) -> bool {
+    let active_tables = tournament.table_counts.iter().filter(|count| **count > 0).count();
+    let shortest = tournament
+        .table_counts
+        .iter()
+        .filter(|count| **count > 0)
+        .min()
+        .copied()
+        .unwrap_or(0);
+
+    active_tables > 1
+        && tournament.players_remaining as usize <= (active_tables - 1) * tournament.seats_per_table as usize
+        && tournament.table_counts[table_index] == shortest
+}
+
+// Helper to validate a payout table: shares must sum to 100% and never increase with place
+fn validate_payout_bps(payout_bps: &[u16], max_entrants: u8) -> Result<()> {
+    require!(
+        !payout_bps.is_empty()
+        && payout_bps.len() <= MAX_PAYOUT_PLACES
+        && payout_bps.len() <= max_entrants as usize
+        && payout_bps.iter().map(|bps| *bps as u64).sum::<u64>() == BPS_DENOMINATOR
+        && payout_bps.windows(2).all(|pair| pair[0] >= pair[1]),
+        PokerError::InvalidPayoutStructure
+    );
+    Ok(())
+}
+
+// Helper to compute the lamports paid for a finishing place
+fn place_payout(tournament: &Tournament, finish_place: u8) -> u64 {
+    match tournament.payout_bps.get(finish_place as usize - 1) {
+        Some(bps) => (tournament.prize_pool as u128 * *bps as u128 / BPS_DENOMINATOR as u128) as u64,
+        None => 0,
+    }
+}
+
+// Helper to compute Malmuth-Harville ICM: each player's share of `prizes` (1st, 2nd, ...)
+// given their chip `stacks`. Walks every subset of players finishing ahead, so callers
+// cap `stacks` at MAX_ICM_DEAL_PLAYERS. Rounding dust goes to the chip leader.
+fn icm_equities(stacks: &[u64], prizes: &[u64]) -> Vec<u64> {
+    let n = stacks.len();
+    let total_chips: u128 = stacks.iter().map(|stack| *stack as u128).sum();
+    let mut equities = vec![0u128; n];
+    if n == 0 || total_chips == 0 {
+        return vec![0; n];
+    }
+
+    // probability[set] = chance the players in `set` take the top |set| places
+    let mut probability = vec![0u128; 1 << n];
+    probability[0] = ICM_PROBABILITY_SCALE;
+
+    for set in 0..(1usize << n) {
+        let place = set.count_ones() as usize;
+        if probability[set] == 0 || place >= prizes.len().min(n) {
+            continue;
+        }
+        let chips_left = total_chips
+            - (0..n)
+                .filter(|i| set & (1 << i) != 0)
+                .map(|i| stacks[i] as u128)
+                .sum::<u128>();
+        if chips_left == 0 {
+            continue;
+        }
+
+        for i in (0..n).filter(|i| set & (1 << i) == 0) {
+            let finishes_next = probability[set] * stacks[i] as u128 / chips_left;
+            equities[i] += finishes_next * prizes[place] as u128;
+            probability[set | (1 << i)] += finishes_next;
+        }
+    }
+
+    let mut shares: Vec<u64> = equities
+        .iter()
+        .map(|equity| (equity / ICM_PROBABILITY_SCALE) as u64)
+        .collect();
+    let paid: u64 = shares.iter().sum();
+    let total_prizes: u64 = prizes.iter().take(n).sum();
+    let leader = (0..n).max_by_key(|i| stacks[*i]).unwrap();
+    shares[leader] += total_prizes.saturating_sub(paid);
+    shares
+}
+
+// Helper to split a prize pool in proportion to chip counts, dust to the chip leader
+fn chip_chop_shares(stacks: &[u64], pool: u64) -> Vec<u64> {
+    let total_chips: u128 = stacks.iter().map(|stack| *stack as u128).sum();
+    if stacks.is_empty() || total_chips == 0 {
+        return vec![0; stacks.len()];
+    }
+
+    let mut shares: Vec<u64> = stacks
+        .iter()
+        .map(|stack| (pool as u128 * *stack as u128 / total_chips) as u64)
+        .collect();
+    let paid: u64 = shares.iter().sum();
+    let leader = (0..stacks.len()).max_by_key(|i| stacks[*i]).unwrap();
+    shares[leader] += pool - paid;
+    shares
+}
+
+// Helper to read every remaining player's stack for a deal. remaining_accounts must hold
+// each tournament table still in play (to prove it is between hands) and the player
+// state of every player still in the tournament.
+fn load_deal_stacks<'info>(
+    tournament: &Tournament,
+    accounts: &[AccountInfo<'info>],
+) -> Result<Vec<(Pubkey, u64)>> {
+    for (index, table_key) in tournament.tables.iter().enumerate() {
+        if tournament.table_counts[index] == 0 {
+            continue;
+        }
+        let table_account = accounts
+            .iter()
+            .find(|account_info| account_info.key == table_key)
+            .ok_or(PokerError::MissingPlayerAccounts)?;
+        let data = table_account.try_borrow_data()?;
+        let table = PokerTable::try_deserialize(&mut &data[..])?;
+        require!(
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
+        );
+    }
+
+    let mut stacks = Vec::new();
+    for entry in tournament.entries.iter().filter(|entry| entry.finish_place == 0) {
+        let table_key = tournament.tables[entry.table_index as usize];
+        let (expected_pda, _) = Pubkey::find_program_address(
+            &[b"player", table_key.as_ref(), entry.player.as_ref()],
+            &crate::ID,
+        );
+        let account_info = accounts
+            .iter()
+            .find(|account_info| account_info.key == &expected_pda)
+            .ok_or(PokerError::MissingPlayerAccounts)?;
+        let data = account_info.try_borrow_data()?;
+        let player_state = PlayerState::try_deserialize(&mut &data[..])?;
+        stacks.push((entry.player, player_state.stack));
+    }
+    Ok(stacks)
+}
+
+// Helper to pay bounties for every player a showdown left with no chips. The
+// winner's own state is skipped: an all-in winner has a zero stack until the pot lands.
+fn settle_bounties<'info>(
+    tournament: &mut Tournament,
+    tournament_key: Pubkey,
+    table_key: &Pubkey,
+    table: &PokerTable,
+    winner: Pubkey,
+    player_state_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    for account_info in player_state_accounts {
+        let Some(player_state) = seated_player_state(table_key, table, account_info)? else {
+            continue;
+        };
+
+        if player_state.player == winner || player_state.stack > 0 {
+            continue;
+        }
+
+        let busted = tournament
+            .entries
+            .iter_mut()
+            .find(|entry| entry.player == player_state.player)
+            .ok_or(PokerError::NotRegistered)?;
+        if busted.eliminated_by.is_some() {
+            continue;
+        }
+        busted.eliminated_by = Some(winner);
+        busted.busted_at = Clock::get()?.unix_timestamp;
+        let bounty = busted.bounty;
+        busted.bounty = 0;
+
+        // Progressive knockouts pay half now and put the other half on the winner's head
+        let (paid, added_to_bounty) = match tournament.bounty_type {
+            BountyType::None => (0, 0),
+            BountyType::Standard => (bounty, 0),
+            BountyType::Progressive => (bounty / 2, bounty - bounty / 2),
+        };
+
+        let eliminator = tournament
+            .entries
+            .iter_mut()
+            .find(|entry| entry.player == winner)
+            .ok_or(PokerError::NotRegistered)?;
+        eliminator.bounties_won += paid;
+        eliminator.bounty += added_to_bounty;
+
+        if bounty > 0 {
+            emit!(BountyCollected {
+                tournament: tournament_key,
+                eliminator: winner,
+                eliminated: player_state.player,
+                paid,
+                added_to_bounty,
+            });
+        }
+    }
+    Ok(())
+}
 
-        player_state.try_serialize(&mut &mut data[8..])?;
+// Helper for every path that completes a tournament hand. It needs the tournament
+// and every seated player's state so each bust is credited to the hand's winner
+// before eliminate_player can run.
+fn record_tournament_busts<'info>(
+    table_key: &Pubkey,
+    table: &PokerTable,
+    winner: Pubkey,
+    tournament_account: Option<&AccountInfo<'info>>,
+    player_state_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    if table.tournament == Pubkey::default() {
+        return Ok(());
     }
+    let tournament_account = tournament_account.ok_or(PokerError::MissingTournamentAccount)?;
+    require_seated_states(table_key, table, player_state_accounts)?;
+
+    let mut data = tournament_account.try_borrow_mut_data()?;
+    let mut tournament = Tournament::try_deserialize(&mut &data[..])?;
+    settle_bounties(&mut tournament, table.tournament, table_key, table, winner, player_state_accounts)?;
+    tournament.try_serialize(&mut &mut data[..])?;
     Ok(())
 }
 
@@ -140,6 +862,7 @@ pub mod poker {
 
     // Table Management 
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_table(
         ctx: Context<InitializeTable>,
         table_id: u64,
@@ -147,13 +870,22 @@ pub mod poker {
         big_blind: u64,
         min_buy_in: u64,
         max_buy_in: u64,
+        max_seats: u8,
+        visibility: TableVisibility,
     ) -> Result<()> {
+        require!(
+            (MIN_SEATS..=MAX_PLAYERS as u8).contains(&max_seats),
+            PokerError::InvalidSeatCount
+        );
+
         let table = &mut ctx.accounts.table;
         table.table_id = table_id;
         table.creator = ctx.accounts.creator.key();
+        table.authority = ctx.accounts.creator.key();
         table.bump = ctx.bumps.table;
         table.player_count = 0;
-        table.players = vec![Pubkey::default(); MAX_PLAYERS];
+        table.max_seats = max_seats;
+        table.players = vec![Pubkey::default(); max_seats as usize];
         table.button_position = 0;
         table.current_player_index = 0;
         table.pot = 0;
@@ -170,6 +902,38 @@ pub mod poker {
         table.blinds_posted = 0;
         table.last_raise_amount = 0;
         table.last_aggressor_index = 0;
+        table.tournament = Pubkey::default();
+        table.blind_structure = Pubkey::default();
+        table.ante = 0;
+        table.sitting_out = 0;
+        table.missed_small_blinds = 0;
+        table.missed_big_blinds = 0;
+        table.leaving = 0;
+        table.waitlist = Pubkey::default();
+        table.seat_freed_at = 0;
+        table.visibility = visibility;
+        table.action_timeout = ACTION_TIMEOUT_DEFAULT;
+        table.paused = false;
+        table.fee_vault = Pubkey::default();
+        table.rake_bps = 0;
+        table.rake_cap = 0;
+        table.no_flop_no_drop = false;
+        table.rakeback_bps = 0;
+        table.rake_attribution = RakeAttribution::Contributed;
+        table.hand_history = Pubkey::default();
+        table.turn_started_at = 0;
+
+        emit!(TableCreated {
+            table: table.key(),
+            table_id,
+            creator: table.creator,
+            small_blind,
+            big_blind,
+            min_buy_in,
+            max_buy_in,
+            max_seats,
+            visibility: table.visibility,
+        });
 
         msg!("Poker table {} initialized by {}", table_id, ctx.accounts.creator.key());
         Ok(())
@@ -183,25 +947,72 @@ pub mod poker {
         let table = &mut ctx.accounts.table;
 
         require!(
-            table.game_state == GameState::WaitingForPlayers,
-            PokerError::GameInProgress
+            !table.paused,
+            PokerError::TablePaused
         );
         require!(
-            table.player_count < MAX_PLAYERS as u8,
-            PokerError::TableFull
+            table.tournament == Pubkey::default(),
+            PokerError::TournamentTable
+        );
+        // Cash tables never go back to WaitingForPlayers, so a seat freed when a hand
+        // completes (and held for the waitlist) has to be taken between hands
+        require!(
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
+        );
+        require!(
+            table.player_count < table.max_seats,
+            PokerError::TableFull
         );
         require!(
             buy_in_amount >= table.min_buy_in && buy_in_amount <= table.max_buy_in,
             PokerError::InvalidBuyIn
         );
         require!(
-            position < MAX_PLAYERS as u8,
+            position < table.max_seats,
             PokerError::InvalidPosition
         );
         require!(
             table.players[position as usize] == Pubkey::default(),
             PokerError::SeatTaken
         );
+        require!(
+            table.can_join(ctx.accounts.invite.as_deref()),
+            PokerError::NotInvited
+        );
+
+        // Tables with a waitlist hold a freed seat for the head of the queue for a short window
+        if table.waitlist != Pubkey::default() {
+            let (waitlist_account, _) =
+                split_remaining_accounts(ctx.remaining_accounts, &table.waitlist);
+            let waitlist_account = waitlist_account.ok_or(PokerError::MissingWaitlistAccount)?;
+            let mut data = waitlist_account.try_borrow_mut_data()?;
+            let mut waitlist = Waitlist::try_deserialize(&mut &data[..])?;
+
+            let player = ctx.accounts.player.key();
+            if let Some(head) = waitlist.players.first() {
+                let now = Clock::get()?.unix_timestamp;
+                require!(
+                    *head == player || now >= table.seat_freed_at + WAITLIST_SEAT_WINDOW_SECONDS,
+                    PokerError::SeatReserved
+                );
+            }
+            waitlist.players.retain(|waiting| *waiting != player);
+            waitlist.try_serialize(&mut &mut data[..])?;
+        }
+
+        // Cash-game chips are lamports held in escrow by the table account
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: table.to_account_info(),
+                },
+            ),
+            buy_in_amount,
+        )?;
 
         let player_state = &mut ctx.accounts.player_state;
         player_state.player = ctx.accounts.player.key();
@@ -212,23 +1023,67 @@ pub mod poker {
         player_state.status = PlayerStatus::Active;
         player_state.hole_cards_computation = Pubkey::default();
         player_state.has_acted_this_street = false;
+        player_state.leaving = false;
 
         table.players[position as usize] = ctx.accounts.player.key();
         table.player_count += 1;
 
+        emit!(PlayerJoined {
+            table: table.key(),
+            player: ctx.accounts.player.key(),
+            position,
+            stack: buy_in_amount,
+        });
+
         msg!("Player {} joined table at position {}", ctx.accounts.player.key(), position);
         Ok(())
     }
 
     pub fn leave_table(ctx: Context<LeaveTable>) -> Result<()> {
         let table = &mut ctx.accounts.table;
-        let player_state = &ctx.accounts.player_state;
+        let player_state = &mut ctx.accounts.player_state;
+        let hand_in_progress = table.game_state != GameState::WaitingForPlayers
+            && table.game_state != GameState::HandComplete;
 
-        require!(
-            table.game_state == GameState::WaitingForPlayers
-            || table.game_state == GameState::HandComplete,
-            PokerError::CannotLeaveNow
-        );
+        // Tournament players leave by elimination; only the winner stands up on their own,
+        // until the tournament is over and everyone left can. That takes the tournament
+        // account in remaining_accounts.
+        if table.tournament != Pubkey::default() {
+            let (tournament_account, _) =
+                split_remaining_accounts(ctx.remaining_accounts, &table.tournament);
+            let tournament_over = match tournament_account {
+                Some(account_info) => {
+                    let data = account_info.try_borrow_data()?;
+                    Tournament::try_deserialize(&mut &data[..])?.is_over()
+                }
+                None => false,
+            };
+            require!(
+                (tournament_over && !hand_in_progress)
+                || (table.game_state == GameState::HandComplete && table.player_count == 1),
+                PokerError::TournamentTable
+            );
+        }
+
+        // A player who forfeited mid-hand comes back once the hand is over to close their account
+        if player_state.leaving {
+            require!(!hand_in_progress, PokerError::CannotLeaveNow);
+            // Chips refunded by an emergency unwind still belong to the player
+            if table.tournament == Pubkey::default() && player_state.stack > 0 {
+                table.sub_lamports(player_state.stack)?;
+                ctx.accounts.player.add_lamports(player_state.stack)?;
+            }
+            emit!(PlayerLeft {
+                table: table.key(),
+                player: ctx.accounts.player.key(),
+                position: player_state.position,
+                cashed_out: player_state.stack,
+                forfeited: false,
+            });
+            player_state.close(ctx.accounts.player.to_account_info())?;
+            msg!("Player {} left table", ctx.accounts.player.key());
+            return Ok(());
+        }
 
         let position = player_state.position as usize;
         require!(
@@ -236,400 +1091,2793 @@ pub mod poker {
             PokerError::NotAtTable
         );
 
+        // Cash out the stack from the table's escrow
+        if table.tournament == Pubkey::default() && player_state.stack > 0 {
+            table.sub_lamports(player_state.stack)?;
+            ctx.accounts.player.add_lamports(player_state.stack)?;
+        }
+        emit!(PlayerLeft {
+            table: table.key(),
+            player: ctx.accounts.player.key(),
+            position: position as u8,
+            cashed_out: player_state.stack,
+            forfeited: hand_in_progress,
+        });
+
+        if hand_in_progress {
+            // Standing up mid-hand folds: committed chips stay in the pot and the seat
+            // is held until the hand completes
+            player_state.status = PlayerStatus::Folded;
+            player_state.stack = 0;
+            player_state.leaving = true;
+            table.leaving |= 1u16 << position;
+
+            let (history_account, player_accounts) =
+                split_remaining_accounts(ctx.remaining_accounts, &table.hand_history);
+            record_hand_actions(
+                table,
+                history_account,
+                vec![HandAction::Forfeit { seat: position as u8 }],
+            )?;
+
+            if table.current_player_index == position as u8 {
+                let next_player = if player_accounts.is_empty() {
+                    table.next_dealt_in(table.current_player_index)?
+                } else {
+                    find_next_active_player(
+                        &table.key(),
+                        table,
+                        table.current_player_index,
+                        &player_accounts,
+                    )?
+                };
+                table.current_player_index = next_player;
+                table.turn_started_at = Clock::get()?.unix_timestamp;
+            }
+
+            msg!("Player {} forfeited and will leave after this hand", ctx.accounts.player.key());
+            return Ok(());
+        }
+
         table.players[position] = Pubkey::default();
         table.player_count -= 1;
+        table.sitting_out &= !(1u16 << position);
+        table.missed_small_blinds &= !(1u16 << position);
+        table.missed_big_blinds &= !(1u16 << position);
+        table.seat_freed_at = Clock::get()?.unix_timestamp;
 
+        player_state.close(ctx.accounts.player.to_account_info())?;
         msg!("Player {} left table", ctx.accounts.player.key());
         Ok(())
     }
 
-    // Game Flow
-
-    pub fn start_hand(ctx: Context<StartHand>) -> Result<()> {
+    pub fn sit_out(ctx: Context<SitOut>) -> Result<()> {
         let table = &mut ctx.accounts.table;
+        let player_state = &mut ctx.accounts.player_state;
 
+        // Tournament players are blinded off instead of sitting out
         require!(
-            table.player_count >= 2,
-            PokerError::NotEnoughPlayers
+            table.tournament == Pubkey::default(),
+            PokerError::TournamentTable
+        );
+        require!(
+            table.players[player_state.position as usize] == player_state.player,
+            PokerError::NotAtTable
         );
+        // Mid-hand, only a player who is out of the hand can step away
         require!(
             table.game_state == GameState::WaitingForPlayers
-            || table.game_state == GameState::HandComplete,
+            || table.game_state == GameState::HandComplete
+            || player_state.status == PlayerStatus::Folded,
             PokerError::GameInProgress
         );
 
-        // Reset all player states for new hand if remaining_accounts provided
-        if !ctx.remaining_accounts.is_empty() {
-            reset_player_states_for_hand(ctx.remaining_accounts)?;
-        }
-
-        table.hand_number += 1;
-        table.game_state = GameState::PreFlop;
-        table.pot = 0;
-        table.current_bet = table.big_blind;
-        table.street_bet_count = 0;
-        table.community_cards = [0; 5];
-        table.blinds_posted = 0;
-        table.last_raise_amount = 0;
-        table.last_aggressor_index = 0;
-
-        // Find next button position (skip empty seats)
-        let mut next_button = (table.button_position + 1) % MAX_PLAYERS as u8;
-        while table.players[next_button as usize] == Pubkey::default() {
-            next_button = (next_button + 1) % MAX_PLAYERS as u8;
-        }
-        table.button_position = next_button;
-
-        // First to act is after big blind (UTG)
-        let mut utg = (table.button_position + 3) % MAX_PLAYERS as u8;
-        while table.players[utg as usize] == Pubkey::default() {
-            utg = (utg + 1) % MAX_PLAYERS as u8;
-        }
-        table.current_player_index = utg;
+        table.sitting_out |= 1u16 << player_state.position;
+        player_state.status = PlayerStatus::SittingOut;
 
-        emit!(HandStarted {
+        emit!(PlayerSatOut {
             table: table.key(),
-            hand_number: table.hand_number,
+            player: player_state.player,
+            sitting_out: true,
         });
 
-        msg!("Starting hand #{}", table.hand_number);
+        msg!("Player {} is sitting out", player_state.player);
         Ok(())
     }
 
-    pub fn post_blinds(ctx: Context<PostBlinds>) -> Result<()> {
+    pub fn sit_in(ctx: Context<SitOut>) -> Result<()> {
         let table = &mut ctx.accounts.table;
         let player_state = &mut ctx.accounts.player_state;
 
         require!(
-            table.game_state == GameState::PreFlop,
-            PokerError::WrongGameState
-        );
-
-        let player_position = player_state.position;
-
-        // Check if player already posted blind this hand using bitmask
-        require!(
-            (table.blinds_posted & (1u16 << player_position)) == 0,
-            PokerError::AlreadyPostedBlind
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
         );
-
-        let small_blind_pos = {
-            let mut sb = (table.button_position + 1) % MAX_PLAYERS as u8;
-            while table.players[sb as usize] == Pubkey::default() {
-                sb = (sb + 1) % MAX_PLAYERS as u8;
-            }
-            sb
-        };
-        let big_blind_pos = {
-            let mut bb = (small_blind_pos + 1) % MAX_PLAYERS as u8;
-            while table.players[bb as usize] == Pubkey::default() {
-                bb = (bb + 1) % MAX_PLAYERS as u8;
-            }
-            bb
-        };
-
-        let blind_amount = if player_position == small_blind_pos {
-            table.small_blind
-        } else if player_position == big_blind_pos {
-            table.big_blind
-        } else {
-            return Err(PokerError::NotBlindPosition.into());
-        };
-
         require!(
-            player_state.stack >= blind_amount,
-            PokerError::InsufficientFunds
+            player_state.status == PlayerStatus::SittingOut,
+            PokerError::NotSittingOut
         );
 
-        player_state.stack -= blind_amount;
-        player_state.current_bet = blind_amount;
-        table.pot += blind_amount;
-
-        // Mark player as having posted blind using bitmask
-        table.blinds_posted |= 1u16 << player_position;
+        // Missed blinds stay owed until posted with post_missed_blinds
+        table.sitting_out &= !(1u16 << player_state.position);
+        player_state.status = PlayerStatus::Active;
 
-        // Mark player as having acted this street
-        player_state.has_acted_this_street = true;
+        emit!(PlayerSatOut {
+            table: table.key(),
+            player: player_state.player,
+            sitting_out: false,
+        });
 
-        msg!("Player posted {} blind", blind_amount);
+        msg!("Player {} is back in", player_state.player);
         Ok(())
     }
 
-    pub fn player_action(
-        ctx: Context<PlayerAction>,
-        action: PlayerActionType,
-        raise_amount: u64,
-    ) -> Result<()> {
+    pub fn update_table_config(ctx: Context<ConfigureTable>, update: TableConfigUpdate) -> Result<()> {
         let table = &mut ctx.accounts.table;
-        let player_state = &mut ctx.accounts.player_state;
 
+        // Tournament tables take their blinds from the blind structure
         require!(
-            player_state.status == PlayerStatus::Active,
-            PokerError::PlayerNotActive
+            table.tournament == Pubkey::default(),
+            PokerError::TournamentTable
         );
         require!(
-            table.players[table.current_player_index as usize] == ctx.accounts.player.key(),
-            PokerError::NotYourTurn
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
         );
 
-        match action {
-            PlayerActionType::Fold => {
-                player_state.status = PlayerStatus::Folded;
-                msg!("Player folded");
-            }
-            PlayerActionType::Check => {
-                require!(
-                    player_state.current_bet == table.current_bet,
-                    PokerError::CannotCheck
-                );
-                msg!("Player checked");
-            }
-            PlayerActionType::Call => {
-                let call_amount = table.current_bet.saturating_sub(player_state.current_bet);
-                let actual_call = call_amount.min(player_state.stack);
-
-                player_state.stack -= actual_call;
-                player_state.current_bet += actual_call;
-                table.pot += actual_call;
+        let small_blind = update.small_blind.unwrap_or(table.small_blind);
+        let big_blind = update.big_blind.unwrap_or(table.big_blind);
+        let min_buy_in = update.min_buy_in.unwrap_or(table.min_buy_in);
+        let max_buy_in = update.max_buy_in.unwrap_or(table.max_buy_in);
+        let action_timeout = update.action_timeout.unwrap_or(table.action_timeout);
+        require!(
+            small_blind > 0 && big_blind >= small_blind,
+            PokerError::InvalidTableConfig
+        );
+        require!(
+            min_buy_in > 0 && min_buy_in <= max_buy_in,
+            PokerError::InvalidTableConfig
+        );
+        require!(
+            (0..=MAX_ACTION_TIMEOUT).contains(&action_timeout),
+            PokerError::InvalidTableConfig
+        );
 
-                if player_state.stack == 0 {
-                    player_state.status = PlayerStatus::AllIn;
-                }
+        table.small_blind = small_blind;
+        table.big_blind = big_blind;
+        table.min_buy_in = min_buy_in;
+        table.max_buy_in = max_buy_in;
+        table.action_timeout = action_timeout;
+        if let Some(visibility) = update.visibility {
+            table.visibility = visibility;
+        }
 
-                msg!("Player called {}", actual_call);
-            }
-            PlayerActionType::Raise => {
-                let total_bet = table.current_bet + raise_amount;
-                let amount_to_add = total_bet.saturating_sub(player_state.current_bet);
+        emit!(TableConfigUpdated {
+            table: table.key(),
+            authority: ctx.accounts.authority.key(),
+            small_blind,
+            big_blind,
+            min_buy_in,
+            max_buy_in,
+            action_timeout,
+            visibility: table.visibility,
+        });
 
-                require!(
-                    player_state.stack >= amount_to_add,
-                    PokerError::InsufficientFunds
-                );
+        msg!("Table {} config updated", table.table_id);
+        Ok(())
+    }
 
-                // Minimum raise validation: must be at least the big blind OR the last raise amount
-                let min_raise = if table.last_raise_amount > 0 {
-                    table.last_raise_amount
-                } else {
-                    table.big_blind
-                };
-                require!(
-                    raise_amount >= min_raise,
-                    PokerError::RaiseTooSmall
-                );
+    pub fn set_table_authority(ctx: Context<ConfigureTable>, new_authority: Pubkey) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        emit!(TableAuthorityChanged {
+            table: table.key(),
+            old_authority: table.authority,
+            new_authority,
+        });
+        table.authority = new_authority;
 
-                player_state.stack -= amount_to_add;
-                player_state.current_bet = total_bet;
-                table.current_bet = total_bet;
-                table.pot += amount_to_add;
-                table.street_bet_count += 1;
+        msg!("Table {} authority transferred to {}", table.table_id, new_authority);
+        Ok(())
+    }
 
-                // Track this raise for future min-raise validation
-                table.last_raise_amount = raise_amount;
-                table.last_aggressor_index = player_state.position;
+    pub fn invite_player(ctx: Context<InvitePlayer>, player: Pubkey) -> Result<()> {
+        let invite = &mut ctx.accounts.invite;
+        invite.table = ctx.accounts.table.key();
+        invite.player = player;
+        invite.bump = ctx.bumps.invite;
 
-                msg!("Player raised to {}", total_bet);
-            }
-        }
+        emit!(InviteUpdated {
+            table: invite.table,
+            player,
+            invited: true,
+        });
 
-        player_state.has_acted_this_street = true;
+        msg!("Player {} invited to table {}", player, ctx.accounts.table.table_id);
+        Ok(())
+    }
 
-        // Move to next active player using helper function
-        // If remaining_accounts is empty, fall back to simple next-seat logic
-        let next_player = if ctx.remaining_accounts.is_empty() {
-            let mut next = (table.current_player_index + 1) % MAX_PLAYERS as u8;
-            while table.players[next as usize] == Pubkey::default() {
-                next = (next + 1) % MAX_PLAYERS as u8;
-            }
-            next
-        } else {
-            find_next_active_player(
-                &table.key(),
-                &table,
-                table.current_player_index,
-                ctx.remaining_accounts,
-            )?
-        };
-        table.current_player_index = next_player;
+    pub fn revoke_invite(ctx: Context<RevokeInvite>) -> Result<()> {
+        // Revoking only stops future joins; a seated player keeps their seat
+        emit!(InviteUpdated {
+            table: ctx.accounts.table.key(),
+            player: ctx.accounts.invite.player,
+            invited: false,
+        });
 
+        msg!("Invite for {} to table {} revoked", ctx.accounts.invite.player, ctx.accounts.table.table_id);
         Ok(())
     }
 
-    pub fn advance_street(ctx: Context<AdvanceStreet>) -> Result<()> {
+    pub fn open_hand_history(ctx: Context<OpenHandHistory>) -> Result<()> {
         let table = &mut ctx.accounts.table;
 
-        table.game_state = match table.game_state {
-            GameState::PreFlop => GameState::Flop,
-            GameState::Flop => GameState::Turn,
-            GameState::Turn => GameState::River,
-            GameState::River => GameState::Showdown,
-            _ => return Err(PokerError::WrongGameState.into()),
-        };
+        require!(
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
+        );
 
-        // Reset betting for new street
-        table.current_bet = 0;
-        table.street_bet_count = 0;
+        // Opened between hands for the hand start_hand will deal next
+        let history = &mut ctx.accounts.hand_history;
+        history.table = table.key();
+        history.hand_number = table.hand_number + 1;
+        history.bump = ctx.bumps.hand_history;
+        history.payer = ctx.accounts.authority.key();
+        history.actions = Vec::new();
+        history.truncated = false;
 
-        // First to act is after button
-        let mut first_to_act = (table.button_position + 1) % MAX_PLAYERS as u8;
-        while table.players[first_to_act as usize] == Pubkey::default() {
-            first_to_act = (first_to_act + 1) % MAX_PLAYERS as u8;
-        }
-        table.current_player_index = first_to_act;
+        table.hand_history = history.key();
 
-        msg!("Advanced to {:?}", table.game_state);
+        emit!(HandHistoryOpened {
+            table: table.key(),
+            hand_history: history.key(),
+            hand_number: history.hand_number,
+        });
+
+        msg!("Recording history for hand #{}", history.hand_number);
         Ok(())
     }
 
-    pub fn advance_street_auto(ctx: Context<AdvanceStreetAuto>) -> Result<()> {
+    pub fn close_hand_history(ctx: Context<CloseHandHistory>) -> Result<()> {
+        // The log is final once the hand is over
+        require!(
+            ctx.accounts.table.hand_history != ctx.accounts.hand_history.key(),
+            PokerError::GameInProgress
+        );
+
+        emit!(HandHistoryClosed {
+            table: ctx.accounts.table.key(),
+            hand_history: ctx.accounts.hand_history.key(),
+            hand_number: ctx.accounts.hand_history.hand_number,
+        });
+
+        msg!("Closed history for hand #{}", ctx.accounts.hand_history.hand_number);
+        Ok(())
+    }
+
+    pub fn set_rake(
+        ctx: Context<SetRake>,
+        rake_bps: u16,
+        rake_cap: u64,
+        no_flop_no_drop: bool,
+        rake_authority: Pubkey,
+        rakeback_bps: u16,
+        rake_attribution: RakeAttribution,
+    ) -> Result<()> {
         let table = &mut ctx.accounts.table;
 
-        // Validate betting round is complete using remaining_accounts
         require!(
-            !ctx.remaining_accounts.is_empty(),
-            PokerError::BettingRoundNotComplete
+            table.tournament == Pubkey::default(),
+            PokerError::TournamentTable
         );
-
-        let round_complete = is_betting_round_complete(&table, ctx.remaining_accounts)?;
         require!(
-            round_complete,
-            PokerError::BettingRoundNotComplete
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
         );
+        require!(rake_bps <= MAX_RAKE_BPS, PokerError::InvalidTableConfig);
+        require!(rakeback_bps as u64 <= BPS_DENOMINATOR, PokerError::InvalidTableConfig);
 
-        // Reset all player states for new street
-        reset_player_states_for_street(ctx.remaining_accounts)?;
-
-        // Advance to next street
-        table.game_state = match table.game_state {
-            GameState::PreFlop => GameState::Flop,
-            GameState::Flop => GameState::Turn,
-            GameState::Turn => GameState::River,
-            GameState::River => GameState::Showdown,
-            _ => return Err(PokerError::WrongGameState.into()),
-        };
+        // The rake authority is fixed when the vault is created, so the table authority
+        // can't redirect rake that's already been collected
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        require!(
+            fee_vault.rake_authority == Pubkey::default() || fee_vault.rake_authority == rake_authority,
+            PokerError::RakeAuthorityLocked
+        );
+        require!(rake_authority != Pubkey::default(), PokerError::InvalidTableConfig);
+        fee_vault.table = table.key();
+        fee_vault.bump = ctx.bumps.fee_vault;
+        fee_vault.rake_authority = rake_authority;
 
-        // Reset betting for new street
-        table.current_bet = 0;
-        table.street_bet_count = 0;
-        table.last_raise_amount = 0;
+        table.fee_vault = fee_vault.key();
+        table.rake_bps = rake_bps;
+        table.rake_cap = rake_cap;
+        table.no_flop_no_drop = no_flop_no_drop;
+        table.rakeback_bps = rakeback_bps;
+        table.rake_attribution = rake_attribution;
 
-        // Find first active player to act after button
-        let first_to_act = if ctx.remaining_accounts.is_empty() {
-            let mut next = (table.button_position + 1) % MAX_PLAYERS as u8;
-            while table.players[next as usize] == Pubkey::default() {
-                next = (next + 1) % MAX_PLAYERS as u8;
-            }
-            next
-        } else {
-            find_next_active_player(
-                &table.key(),
-                &table,
-                table.button_position,
-                ctx.remaining_accounts,
-            )?
-        };
-        table.current_player_index = first_to_act;
+        emit!(RakeConfigured {
+            table: table.key(),
+            rake_bps,
+            rake_cap,
+            no_flop_no_drop,
+            rake_authority,
+            rakeback_bps,
+            rake_attribution,
+        });
 
-        msg!("Advanced to {:?} with validation", table.game_state);
+        msg!("Table {} rake set to {} bps, cap {}", table.table_id, rake_bps, rake_cap);
         Ok(())
     }
 
-    pub fn check_auto_win(ctx: Context<CheckAutoWin>) -> Result<()> {
-        let table = &mut ctx.accounts.table;
-
+    pub fn withdraw_rake(ctx: Context<WithdrawRake>, amount: u64) -> Result<()> {
+        let fee_vault = &ctx.accounts.fee_vault;
+        let rent = Rent::get()?.minimum_balance(fee_vault.to_account_info().data_len());
+        let needed = rent.checked_add(amount).ok_or(PokerError::InsufficientFunds)?;
         require!(
-            !ctx.remaining_accounts.is_empty(),
-            PokerError::NoActivePlayersRemaining
+            fee_vault.get_lamports() >= needed,
+            PokerError::InsufficientFunds
         );
 
-        // Count active players (not folded, not all-in)
-        let mut active_count = 0;
-        let mut last_active_position = 0u8;
+        fee_vault.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
 
-        for account_info in ctx.remaining_accounts {
-            let data = account_info.try_borrow_data()?;
-            let player_state = PlayerState::try_deserialize(&mut &data[..])?;
+        emit!(RakeWithdrawn {
+            table: fee_vault.table,
+            destination: ctx.accounts.destination.key(),
+            amount,
+        });
 
-            if player_state.status == PlayerStatus::Active {
-                active_count += 1;
-                last_active_position = player_state.position;
-            }
-        }
+        msg!("Withdrew {} rake from table {}", amount, fee_vault.table);
+        Ok(())
+    }
 
-        // If only one active player remains, they win automatically
-        if active_count == 1 {
-            // Find the winner's account in remaining_accounts
-            for account_info in ctx.remaining_accounts {
-                let mut data = account_info.try_borrow_mut_data()?;
-                let mut player_state = PlayerState::try_deserialize(&mut &data[..])?;
+    pub fn create_player_stats(ctx: Context<CreatePlayerStats>) -> Result<()> {
+        let stats = &mut ctx.accounts.player_stats;
+        stats.player = ctx.accounts.player.key();
+        stats.bump = ctx.bumps.player_stats;
+        stats.rake_contributed = 0;
+        stats.rakeback_earned = 0;
+        stats.rakeback_claimed = 0;
 
-                if player_state.position == last_active_position {
-                    // Award pot to winner
-                    player_state.stack += table.pot;
-                    player_state.try_serialize(&mut &mut data[8..])?;
+        emit!(PlayerStatsCreated {
+            player: stats.player,
+            player_stats: stats.key(),
+        });
 
-                    table.pot = 0;
-                    table.game_state = GameState::HandComplete;
+        msg!("Player stats created for {}", stats.player);
+        Ok(())
+    }
 
-                    emit!(HandComplete {
-                        table: table.key(),
-                        winner: player_state.player,
-                        pot: table.pot,
-                    });
+    pub fn claim_rakeback(ctx: Context<ClaimRakeback>) -> Result<()> {
+        let stats = &mut ctx.accounts.player_stats;
+        let amount = stats.rakeback_earned - stats.rakeback_claimed;
+        require!(amount > 0, PokerError::NothingToClaim);
 
-                    msg!("Auto-win: Player at position {} wins by default", last_active_position);
-                    return Ok(());
+        stats.rakeback_claimed += amount;
+        stats.sub_lamports(amount)?;
+        ctx.accounts.player.add_lamports(amount)?;
+
+        emit!(RakebackClaimed {
+            player: stats.player,
+            amount,
+        });
+
+        msg!("Player {} claimed {} rakeback", stats.player, amount);
+        Ok(())
+    }
+
+    pub fn initialize_config(ctx: Context<InitializeConfig>, admin: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = admin;
+        config.bump = ctx.bumps.config;
+
+        msg!("Program admin set to {}", admin);
+        Ok(())
+    }
+
+    pub fn set_table_paused(ctx: Context<TableAdmin>, paused: bool) -> Result<()> {
+        require_table_admin(&ctx.accounts.table, &ctx.accounts.config, &ctx.accounts.signer.key())?;
+
+        let table = &mut ctx.accounts.table;
+        table.paused = paused;
+
+        emit!(TablePaused {
+            table: table.key(),
+            paused,
+            by: ctx.accounts.signer.key(),
+        });
+
+        msg!("Table {} {}", table.table_id, if paused { "paused" } else { "resumed" });
+        Ok(())
+    }
+
+    pub fn emergency_refund<'info>(ctx: Context<'_, '_, 'info, 'info, TableAdmin<'info>>) -> Result<()> {
+        require_table_admin(&ctx.accounts.table, &ctx.accounts.config, &ctx.accounts.signer.key())?;
+
+        let table = &mut ctx.accounts.table;
+        require!(table.paused, PokerError::TableNotPaused);
+        require!(
+            table.game_state != GameState::WaitingForPlayers
+            && table.game_state != GameState::HandComplete,
+            PokerError::WrongGameState
+        );
+        // Every seated player must be passed so the whole pot is accounted for
+        let (history_account, player_accounts) =
+            split_remaining_accounts(ctx.remaining_accounts, &table.hand_history);
+        require!(
+            player_accounts.len() == table.player_count as usize,
+            PokerError::MissingPlayerAccounts
+        );
+
+        let mut refunded = 0u64;
+        let mut seen = 0u16;
+        let mut actions = Vec::new();
+        for account_info in &player_accounts {
+            let mut data = account_info.try_borrow_mut_data()?;
+            let mut player_state = PlayerState::try_deserialize(&mut &data[..])?;
+            require!(
+                player_state.table == table.key()
+                && table.players[player_state.position as usize] == player_state.player,
+                PokerError::NotAtTable
+            );
+            require!(
+                seen & (1u16 << player_state.position) == 0,
+                PokerError::MissingPlayerAccounts
+            );
+            seen |= 1u16 << player_state.position;
+
+            let committed = player_state.committed_this_hand(table.hand_number);
+            player_state.stack += committed;
+            player_state.committed = 0;
+            player_state.current_bet = 0;
+            player_state.has_acted_this_street = false;
+            if player_state.status != PlayerStatus::SittingOut {
+                player_state.status = PlayerStatus::Active;
+            }
+            refunded += committed;
+            if committed > 0 {
+                actions.push(HandAction::Refund { seat: player_state.position, amount: committed });
+            }
+
+            player_state.try_serialize(&mut &mut data[..])?;
+        }
+        require!(refunded == table.pot, PokerError::RefundMismatch);
+        record_hand_actions(table, history_account, actions)?;
+        table.hand_history = Pubkey::default();
+
+        table.pot = 0;
+        table.current_bet = 0;
+        table.blinds_posted = 0;
+        table.community_cards = [0; 5];
+        table.game_state = GameState::HandComplete;
+        release_leaving_seats(table.key(), table)?;
+
+        emit!(HandRefunded {
+            table: table.key(),
+            hand_number: table.hand_number,
+            refunded,
+        });
+
+        msg!("Hand {} unwound, {} refunded", table.hand_number, refunded);
+        Ok(())
+    }
+
+    pub fn close_table<'info>(ctx: Context<'_, '_, 'info, 'info, CloseTable<'info>>) -> Result<()> {
+        let table = &ctx.accounts.table;
+        let creator = ctx.accounts.creator.to_account_info();
+
+        require!(table.player_count == 0, PokerError::TableNotEmpty);
+        require!(table.pot == 0, PokerError::TableNotEmpty);
+
+        // Tournament tables stay open until the tournament is over
+        if table.tournament != Pubkey::default() {
+            let (tournament_account, _) =
+                split_remaining_accounts(ctx.remaining_accounts, &table.tournament);
+            let tournament_account = tournament_account
+                .ok_or(PokerError::MissingTournamentAccount)?;
+            let data = tournament_account.try_borrow_data()?;
+            let tournament = Tournament::try_deserialize(&mut &data[..])?;
+            require!(tournament.is_over(), PokerError::TournamentTable);
+        }
+
+        // Accounts hanging off the table go with it
+        if table.blind_structure != Pubkey::default() {
+            let (blind_structure_account, _) =
+                split_remaining_accounts(ctx.remaining_accounts, &table.blind_structure);
+            let blind_structure_account = blind_structure_account
+                .ok_or(PokerError::MissingBlindStructureAccount)?;
+            close_remaining_account(blind_structure_account, &creator)?;
+        }
+        if table.waitlist != Pubkey::default() {
+            let (waitlist_account, _) =
+                split_remaining_accounts(ctx.remaining_accounts, &table.waitlist);
+            let waitlist_account = waitlist_account.ok_or(PokerError::MissingWaitlistAccount)?;
+            close_remaining_account(waitlist_account, &creator)?;
+        }
+        if table.fee_vault != Pubkey::default() {
+            let (fee_vault_account, _) =
+                split_remaining_accounts(ctx.remaining_accounts, &table.fee_vault);
+            let fee_vault_account = fee_vault_account.ok_or(PokerError::MissingFeeVaultAccount)?;
+            // Rake belongs to the rake authority, so it has to be withdrawn first
+            let rent = Rent::get()?.minimum_balance(fee_vault_account.data_len());
+            require!(
+                fee_vault_account.lamports() <= rent,
+                PokerError::RakeNotWithdrawn
+            );
+            close_remaining_account(fee_vault_account, &creator)?;
+        }
+
+        // Hand histories and invites can't be closed once the table is gone, so any
+        // passed along are closed with it. The open history has to be.
+        let authority = ctx.accounts.authority.to_account_info();
+        let mut open_history_closed = table.hand_history == Pubkey::default();
+        for account_info in ctx.remaining_accounts {
+            if account_info.owner != &crate::ID {
+                continue;
+            }
+            let data = account_info.try_borrow_data()?;
+            if data.starts_with(HandHistory::DISCRIMINATOR) {
+                let history = HandHistory::try_deserialize(&mut &data[..])?;
+                drop(data);
+                if history.table != table.key() {
+                    continue;
+                }
+                // Rent goes back to whoever opened the history
+                let payer = if history.payer == authority.key() {
+                    &authority
+                } else {
+                    ctx.remaining_accounts
+                        .iter()
+                        .find(|account| account.key() == history.payer)
+                        .ok_or(PokerError::MissingRentPayerAccount)?
+                };
+                open_history_closed |= account_info.key() == table.hand_history;
+                close_remaining_account(account_info, payer)?;
+            } else if data.starts_with(TableInvite::DISCRIMINATOR) {
+                let invite = TableInvite::try_deserialize(&mut &data[..])?;
+                drop(data);
+                if invite.table == table.key() {
+                    close_remaining_account(account_info, &authority)?;
                 }
             }
         }
+        require!(open_history_closed, PokerError::MissingHandHistoryAccount);
 
-        // If multiple active players, do nothing (hand continues)
-        msg!("No auto-win: {} active players remaining", active_count);
+        emit!(TableClosed {
+            table: table.key(),
+            table_id: table.table_id,
+        });
+
+        msg!("Table {} closed", table.table_id);
         Ok(())
     }
 
-    pub fn end_hand(ctx: Context<EndHand>, winner_position: u8) -> Result<()> {
+    pub fn join_waitlist(ctx: Context<JoinWaitlist>) -> Result<()> {
         let table = &mut ctx.accounts.table;
-        let winner_state = &mut ctx.accounts.winner_state;
+        let waitlist = &mut ctx.accounts.waitlist;
+        let player = ctx.accounts.player.key();
+
+        require!(
+            table.tournament == Pubkey::default(),
+            PokerError::TournamentTable
+        );
+        require!(
+            !table.players.contains(&player),
+            PokerError::AlreadySeated
+        );
+        require!(
+            table.player_count == table.max_seats,
+            PokerError::TableNotFull
+        );
+        require!(
+            table.can_join(ctx.accounts.invite.as_deref()),
+            PokerError::NotInvited
+        );
+
+        // First player to queue creates the table's waitlist
+        if table.waitlist == Pubkey::default() {
+            waitlist.table = table.key();
+            waitlist.bump = ctx.bumps.waitlist;
+            table.waitlist = waitlist.key();
+        }
+
+        require!(
+            !waitlist.players.contains(&player),
+            PokerError::AlreadyWaitlisted
+        );
+        require!(
+            waitlist.players.len() < MAX_WAITLIST,
+            PokerError::WaitlistFull
+        );
+        waitlist.players.push(player);
+
+        emit!(WaitlistUpdated {
+            table: table.key(),
+            player,
+            joined: true,
+            waiting: waitlist.players.len() as u8,
+        });
+
+        msg!("Player {} joined the waitlist at position {}", player, waitlist.players.len());
+        Ok(())
+    }
+
+    pub fn leave_waitlist(ctx: Context<LeaveWaitlist>) -> Result<()> {
+        let waitlist = &mut ctx.accounts.waitlist;
+        let player = ctx.accounts.player.key();
+
+        let index = waitlist
+            .players
+            .iter()
+            .position(|waiting| *waiting == player)
+            .ok_or(PokerError::NotWaitlisted)?;
+        waitlist.players.remove(index);
+
+        emit!(WaitlistUpdated {
+            table: waitlist.table,
+            player,
+            joined: false,
+            waiting: waitlist.players.len() as u8,
+        });
+
+        msg!("Player {} left the waitlist", player);
+        Ok(())
+    }
+
+    pub fn top_up(ctx: Context<TopUp>, amount: u64) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        let player_state = &mut ctx.accounts.player_state;
+
+        require!(
+            !table.paused,
+            PokerError::TablePaused
+        );
+        require!(
+            table.tournament == Pubkey::default(),
+            PokerError::TournamentTable
+        );
+        require!(
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
+        );
+        require!(
+            table.players[player_state.position as usize] == player_state.player,
+            PokerError::NotAtTable
+        );
+        let stack = player_state.stack.checked_add(amount).ok_or(PokerError::InvalidBuyIn)?;
+        require!(
+            amount > 0 && stack <= table.max_buy_in,
+            PokerError::InvalidBuyIn
+        );
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: table.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        player_state.stack = stack;
+
+        emit!(PlayerToppedUp {
+            table: table.key(),
+            player: player_state.player,
+            amount,
+            stack: player_state.stack,
+        });
+
+        msg!("Player {} topped up {} to {}", player_state.player, amount, player_state.stack);
+        Ok(())
+    }
+
+    // Game Flow
+
+    pub fn start_hand(ctx: Context<StartHand>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+
+        require!(
+            !table.paused,
+            PokerError::TablePaused
+        );
+        require!(
+            table.player_count >= 2,
+            PokerError::NotEnoughPlayers
+        );
+        require!(
+            (0..table.max_seats).filter(|seat| table.is_dealt_in(*seat)).count() >= 2,
+            PokerError::NotEnoughPlayers
+        );
+        require!(
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
+        );
+
+        // Tournament and blind structure accounts ride along with the player states
+        let (tournament_account, player_accounts) =
+            split_remaining_accounts(ctx.remaining_accounts, &table.tournament);
+        let (blind_structure_account, player_accounts) =
+            split_remaining_accounts(&player_accounts, &table.blind_structure);
+        let (history_account, player_accounts) =
+            split_remaining_accounts(&player_accounts, &table.hand_history);
+        let table_key = table.key();
+
+        let mut running_tournament = None;
+        if table.tournament != Pubkey::default() {
+            let tournament_account = tournament_account
+                .ok_or(PokerError::MissingTournamentAccount)?;
+            let data = tournament_account.try_borrow_data()?;
+            let tournament = Tournament::try_deserialize(&mut &data[..])?;
+
+            require!(
+                tournament.status == TournamentStatus::Running,
+                PokerError::TournamentNotRunning
+            );
+
+            // Busted players must be eliminated before the next hand is dealt, so
+            // every seated player's state has to be checked
+            require_seated_states(&table_key, table, &player_accounts)?;
+            for account_info in &player_accounts {
+                if let Some(player_state) = seated_player_state(&table_key, table, account_info)? {
+                    require!(player_state.stack > 0, PokerError::EliminationPending);
+                }
+            }
+            running_tournament = Some(tournament);
+        }
+
+        if table.blind_structure != Pubkey::default() {
+            let blind_structure_account = blind_structure_account
+                .ok_or(PokerError::MissingBlindStructureAccount)?;
+            let mut data = blind_structure_account.try_borrow_mut_data()?;
+            let mut blind_structure = BlindStructure::try_deserialize(&mut &data[..])?;
+            let previous_level = blind_structure.current_level;
+
+            // Tournament tables all run off the tournament's level clock, which counts
+            // hands dealt across every table, so no table drifts onto its own level
+            let next_hand = match &running_tournament {
+                Some(tournament) => {
+                    blind_structure.current_level = tournament.blind_level;
+                    blind_structure.level_started_hand = tournament.level_started_hand;
+                    blind_structure.level_started_at = tournament.level_started_at;
+                    tournament.hands_dealt + 1
+                }
+                None => table.hand_number + 1,
+            };
+            apply_blind_structure(table, &mut blind_structure, next_hand, Clock::get()?.unix_timestamp);
+            if let Some(tournament) = running_tournament.as_mut() {
+                tournament.blind_level = blind_structure.current_level;
+                tournament.level_started_hand = blind_structure.level_started_hand;
+                tournament.level_started_at = blind_structure.level_started_at;
+                tournament.hands_dealt = next_hand;
+            }
+
+            if blind_structure.current_level > previous_level {
+                emit!(BlindsIncreased {
+                    table: table.key(),
+                    level: blind_structure.current_level,
+                    small_blind: table.small_blind,
+                    big_blind: table.big_blind,
+                    ante: table.ante,
+                });
+                msg!("Blinds up to {}/{} ante {}", table.small_blind, table.big_blind, table.ante);
+            }
+            blind_structure.try_serialize(&mut &mut data[..])?;
+        }
+
+        if let (Some(tournament), Some(tournament_account)) = (running_tournament, tournament_account) {
+            let mut data = tournament_account.try_borrow_mut_data()?;
+            tournament.try_serialize(&mut &mut data[..])?;
+        }
+
+        // Antes are taken from the player states we were given, so they must be every
+        // dealt-in player's, each passed once
+        if table.ante > 0 || !player_accounts.is_empty() {
+            require_dealt_in_states(&table_key, table, &player_accounts)?;
+            reset_player_states_for_hand(&table_key, table, &player_accounts)?;
+        }
+
+        table.hand_number += 1;
+        table.game_state = GameState::PreFlop;
+        table.pot = 0;
+        table.current_bet = table.big_blind;
+        table.street_bet_count = 0;
+        table.community_cards = [0; 5];
+        table.blinds_posted = 0;
+        table.last_raise_amount = 0;
+        table.last_aggressor_index = 0;
+
+        let antes = if table.ante > 0 {
+            collect_antes(&table_key, table, &player_accounts)?
+        } else {
+            Vec::new()
+        };
+
+        // Find next button position (skip empty seats)
+        table.button_position = table.next_dealt_in(table.button_position)?;
+
+        // Players sitting out in the blinds miss them and owe them on return
+        let small_blind_seat = table.next_occupied(table.button_position);
+        let big_blind_seat = table.next_occupied(small_blind_seat);
+        if table.sitting_out & (1u16 << small_blind_seat) != 0 {
+            table.missed_small_blinds |= 1u16 << small_blind_seat;
+        }
+        if table.sitting_out & (1u16 << big_blind_seat) != 0 {
+            table.missed_big_blinds |= 1u16 << big_blind_seat;
+        }
+
+        // First to act is after big blind (UTG)
+        let (_, big_blind_pos) = table.blind_seats()?;
+        table.current_player_index = table.next_dealt_in(big_blind_pos)?;
+        table.turn_started_at = Clock::get()?.unix_timestamp;
+
+        if table.hand_history != Pubkey::default() {
+            let mut actions = vec![HandAction::Button { seat: table.button_position }];
+            for account_info in &player_accounts {
+                let Some(player_state) = seated_player_state(&table_key, table, account_info)? else {
+                    continue;
+                };
+                if !table.is_dealt_in(player_state.position) {
+                    continue;
+                }
+                let ante = player_state.committed_this_hand(table.hand_number);
+                actions.push(HandAction::Dealt {
+                    seat: player_state.position,
+                    stack: player_state.stack + ante,
+                });
+                if ante > 0 {
+                    actions.push(HandAction::Ante { seat: player_state.position, amount: ante });
+                }
+            }
+            record_hand_actions(table, history_account, actions)?;
+        }
+
+        emit!(HandStarted {
+            table: table.key(),
+            hand_number: table.hand_number,
+            button_position: table.button_position,
+            small_blind: table.small_blind,
+            big_blind: table.big_blind,
+            ante: table.ante,
+            pot: table.pot,
+        });
+        // Short stacks post less than the full ante
+        for (player, position, amount) in antes {
+            emit!(AntePosted {
+                table: table.key(),
+                player,
+                position,
+                amount,
+            });
+        }
+
+        msg!("Starting hand #{}", table.hand_number);
+        Ok(())
+    }
+
+    pub fn post_blinds(ctx: Context<PostBlinds>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        let player_state = &mut ctx.accounts.player_state;
+
+        require!(
+            !table.paused,
+            PokerError::TablePaused
+        );
+        require!(
+            table.game_state == GameState::PreFlop,
+            PokerError::WrongGameState
+        );
+
+        let player_position = player_state.position;
+
+        // Check if player already posted blind this hand using bitmask
+        require!(
+            (table.blinds_posted & (1u16 << player_position)) == 0,
+            PokerError::AlreadyPostedBlind
+        );
+
+        let (small_blind_pos, big_blind_pos) = table.blind_seats()?;
+
+        let blind_amount = if player_position == small_blind_pos {
+            table.small_blind
+        } else if player_position == big_blind_pos {
+            table.big_blind
+        } else {
+            return Err(PokerError::NotBlindPosition.into());
+        };
+
+        // A stack the antes left short of the blind posts what it has, all in
+        let mut betting = table.betting();
+        let mut seat = player_state.seat();
+        let blind_amount = poker_engine::post_blind(&mut betting, &mut seat, blind_amount);
+        table.set_betting(betting);
+        player_state.set_seat(seat);
+        player_state.commit(table.hand_number, blind_amount);
+
+        // Mark player as having posted blind using bitmask
+        table.blinds_posted |= 1u16 << player_position;
+
+        // Mark player as having acted this street
+        player_state.has_acted_this_street = true;
+
+        // Heads-up the small blind acts first; all in, the turn passes on
+        if player_state.status == PlayerStatus::AllIn && table.current_player_index == player_position {
+            table.current_player_index = table.next_dealt_in(player_position)?;
+        }
+
+        let (history_account, _) = split_remaining_accounts(ctx.remaining_accounts, &table.hand_history);
+        record_hand_actions(
+            table,
+            history_account,
+            vec![HandAction::Blind { seat: player_position, amount: blind_amount }],
+        )?;
+
+        emit!(BlindPosted {
+            table: table.key(),
+            player: player_state.player,
+            position: player_position,
+            amount: blind_amount,
+            pot: table.pot,
+        });
+
+        msg!("Player posted {} blind", blind_amount);
+        Ok(())
+    }
+
+    pub fn post_missed_blinds(ctx: Context<PostBlinds>, live: bool) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        let player_state = &mut ctx.accounts.player_state;
+
+        require!(
+            !table.paused,
+            PokerError::TablePaused
+        );
+        require!(
+            table.game_state == GameState::PreFlop,
+            PokerError::WrongGameState
+        );
+        require!(
+            player_state.status == PlayerStatus::Active,
+            PokerError::PlayerNotActive
+        );
+
+        let seat_bit = 1u16 << player_state.position;
+        require!(
+            (table.missed_small_blinds | table.missed_big_blinds) & seat_bit != 0,
+            PokerError::NoMissedBlinds
+        );
+
+        // A missed small blind is always dead; a missed big blind may be posted live,
+        // counting toward the player's bet this round
+        let small_blind = if table.missed_small_blinds & seat_bit != 0 { table.small_blind } else { 0 };
+        let big_blind = if table.missed_big_blinds & seat_bit != 0 { table.big_blind } else { 0 };
+        let total = small_blind + big_blind;
+
+        let mut betting = table.betting();
+        let mut seat = player_state.seat();
+        poker_engine::post(&mut betting, &mut seat, total).map_err(PokerError::from)?;
+        if live {
+            seat.current_bet += big_blind;
+        }
+        table.set_betting(betting);
+        player_state.set_seat(seat);
+        player_state.commit(table.hand_number, total);
+
+        table.missed_small_blinds &= !seat_bit;
+        table.missed_big_blinds &= !seat_bit;
+
+        let (history_account, _) = split_remaining_accounts(ctx.remaining_accounts, &table.hand_history);
+        record_hand_actions(
+            table,
+            history_account,
+            vec![HandAction::MissedBlinds { seat: player_state.position, amount: total, live }],
+        )?;
+
+        emit!(MissedBlindsPosted {
+            table: table.key(),
+            player: player_state.player,
+            position: player_state.position,
+            dead: if live { small_blind } else { total },
+            live: if live { big_blind } else { 0 },
+        });
+
+        msg!("Player posted {} in missed blinds", total);
+        Ok(())
+    }
+
+    pub fn player_action(
+        ctx: Context<PlayerAction>,
+        action: PlayerActionType,
+        raise_amount: u64,
+    ) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        let player_state = &mut ctx.accounts.player_state;
+
+        require!(
+            !table.paused,
+            PokerError::TablePaused
+        );
+        require!(
+            player_state.status == PlayerStatus::Active,
+            PokerError::PlayerNotActive
+        );
+        require!(
+            table.players[table.current_player_index as usize] == ctx.accounts.player.key(),
+            PokerError::NotYourTurn
+        );
+        require!(
+            (table.missed_small_blinds | table.missed_big_blinds) & (1u16 << player_state.position) == 0,
+            PokerError::MissedBlindsDue
+        );
+
+        apply_player_action(table.key(), table, player_state, action, raise_amount, ctx.remaining_accounts)
+    }
+
+    // Anyone may act for a player who has run out of time: they check if they can, else fold
+    pub fn crank_timeout(ctx: Context<CrankTimeout>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        let player_state = &mut ctx.accounts.player_state;
+
+        require!(
+            !table.paused,
+            PokerError::TablePaused
+        );
+        require!(
+            table.game_state.street().is_some_and(|street| street != Street::Showdown),
+            PokerError::WrongGameState
+        );
+        require!(
+            player_state.status == PlayerStatus::Active,
+            PokerError::PlayerNotActive
+        );
+        let deadline = table
+            .turn_started_at
+            .checked_add(table.action_timeout)
+            .ok_or(PokerError::InvalidTableConfig)?;
+        require!(
+            table.action_timeout > 0 && Clock::get()?.unix_timestamp >= deadline,
+            PokerError::ActionNotTimedOut
+        );
+        // Anyone can crank, so the turn mustn't pass over a player left out of the accounts
+        let (_, player_accounts) =
+            split_remaining_accounts(ctx.remaining_accounts, &table.hand_history);
+        require_dealt_in_states(&table.key(), table, &player_accounts)?;
+
+        let action = if player_state.current_bet == table.current_bet {
+            PlayerActionType::Check
+        } else {
+            PlayerActionType::Fold
+        };
+        msg!("Player at position {} timed out", player_state.position);
+        apply_player_action(table.key(), table, player_state, action, 0, ctx.remaining_accounts)
+    }
+
+    // Read-only: simulate it to learn what the player to act may do
+    pub fn legal_actions(ctx: Context<LegalActions>) -> Result<LegalActionsView> {
+        let view = ctx.accounts.table.legal_actions(&ctx.accounts.player_state)?;
+        msg!("Seat {} may call {} or raise {}-{}", view.position, view.call_amount, view.min_raise, view.max_raise);
+        Ok(view)
+    }
+
+    pub fn advance_street(ctx: Context<AdvanceStreet>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+
+        require!(
+            !table.paused,
+            PokerError::TablePaused
+        );
+
+        table.game_state = next_street(table.game_state)?;
+
+        // Reset betting for new street
+        let mut betting = table.betting();
+        betting.new_street();
+        table.set_betting(betting);
+
+        // First to act is after button
+        table.current_player_index = table.next_dealt_in(table.button_position)?;
+        table.turn_started_at = Clock::get()?.unix_timestamp;
+
+        let (history_account, _) = split_remaining_accounts(ctx.remaining_accounts, &table.hand_history);
+        record_hand_actions(
+            table,
+            history_account,
+            vec![HandAction::Street { state: table.game_state, board: table.community_cards }],
+        )?;
+
+        emit!(StreetAdvanced {
+            table: table.key(),
+            hand_number: table.hand_number,
+            street: table.game_state,
+            board: table.community_cards,
+            pot: table.pot,
+        });
+
+        msg!("Advanced to {:?}", table.game_state);
+        Ok(())
+    }
+
+    pub fn advance_street_auto(ctx: Context<AdvanceStreetAuto>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+
+        require!(
+            !table.paused,
+            PokerError::TablePaused
+        );
+
+        // Validate betting round is complete using remaining_accounts
+        let (history_account, player_accounts) =
+            split_remaining_accounts(ctx.remaining_accounts, &table.hand_history);
+        require!(
+            !player_accounts.is_empty(),
+            PokerError::BettingRoundNotComplete
+        );
+
+        let round_complete = is_betting_round_complete(table, &player_accounts)?;
+        require!(
+            round_complete,
+            PokerError::BettingRoundNotComplete
+        );
+
+        // Reset all player states for new street
+        reset_player_states_for_street(&player_accounts)?;
+
+        // Advance to next street
+        table.game_state = next_street(table.game_state)?;
+
+        // Reset betting for new street
+        let mut betting = table.betting();
+        betting.new_street();
+        table.set_betting(betting);
+
+        // Find first active player to act after button
+        let first_to_act = if player_accounts.is_empty() {
+            table.next_dealt_in(table.button_position)?
+        } else {
+            find_next_active_player(
+                &table.key(),
+                table,
+                table.button_position,
+                &player_accounts,
+            )?
+        };
+        table.current_player_index = first_to_act;
+        table.turn_started_at = Clock::get()?.unix_timestamp;
+
+        record_hand_actions(
+            table,
+            history_account,
+            vec![HandAction::Street { state: table.game_state, board: table.community_cards }],
+        )?;
+
+        emit!(StreetAdvanced {
+            table: table.key(),
+            hand_number: table.hand_number,
+            street: table.game_state,
+            board: table.community_cards,
+            pot: table.pot,
+        });
+
+        msg!("Advanced to {:?} with validation", table.game_state);
+        Ok(())
+    }
+
+    pub fn check_auto_win(ctx: Context<CheckAutoWin>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+
+        require!(
+            !table.paused,
+            PokerError::TablePaused
+        );
+        // Only a hand still being bet can be won by default, and only once
+        require!(
+            table.game_state.street().is_some_and(|street| street != Street::Showdown),
+            PokerError::WrongGameState
+        );
+        // Raked tables pass their fee vault and player stats alongside the player states
+        let (fee_vault, other_accounts) =
+            split_remaining_accounts(ctx.remaining_accounts, &table.fee_vault);
+        let (_, other_accounts) = split_player_stats(&other_accounts);
+        let (history_account, other_accounts) =
+            split_remaining_accounts(&other_accounts, &table.hand_history);
+        let (tournament_account, player_accounts) =
+            split_remaining_accounts(&other_accounts, &table.tournament);
+        require!(
+            !player_accounts.is_empty(),
+            PokerError::NoActivePlayersRemaining
+        );
+
+        // Count active players (not folded, not all-in)
+        let mut active_count = 0;
+        let mut last_active_position = 0u8;
+
+        for account_info in &player_accounts {
+            let data = account_info.try_borrow_data()?;
+            let player_state = PlayerState::try_deserialize(&mut &data[..])?;
+
+            if player_state.status == PlayerStatus::Active {
+                active_count += 1;
+                last_active_position = player_state.position;
+            }
+        }
+
+        // If only one active player remains, they win automatically
+        if active_count == 1 {
+            let contributions =
+                hand_contributions(&table.key(), table.hand_number, &player_accounts, Vec::new())?;
+
+            // Find the winner's account in remaining_accounts
+            for account_info in &player_accounts {
+                let mut data = account_info.try_borrow_mut_data()?;
+                let mut player_state = PlayerState::try_deserialize(&mut &data[..])?;
+
+                if player_state.position == last_active_position {
+                    // Award pot to winner, less rake
+                    let rake = collect_rake(table, fee_vault, &contributions, ctx.remaining_accounts)?;
+                    player_state.stack += table.pot;
+                    player_state.try_serialize(&mut &mut data[..])?;
+
+                    drop(data);
+                    let mut actions =
+                        showdown_actions(&table.key(), &player_accounts, last_active_position)?;
+                    if rake > 0 {
+                        actions.push(HandAction::Rake { amount: rake });
+                    }
+                    actions.push(HandAction::Payout { seat: last_active_position, amount: table.pot });
+                    record_hand_actions(table, history_account, actions)?;
+                    table.hand_history = Pubkey::default();
+
+                    emit!(HandComplete {
+                        table: table.key(),
+                        hand_number: table.hand_number,
+                        winner: player_state.player,
+                        position: last_active_position,
+                        pot: table.pot,
+                        rake,
+                    });
+
+                    table.pot = 0;
+                    table.game_state = GameState::HandComplete;
+                    release_leaving_seats(table.key(), table)?;
+
+                    // Players who went all in and lost are busted by the winner
+                    let winner = player_state.player;
+                    record_tournament_busts(&table.key(), table, winner, tournament_account, &player_accounts)?;
+
+                    msg!("Auto-win: Player at position {} wins by default", last_active_position);
+                    return Ok(());
+                }
+            }
+        }
+
+        // If multiple active players, do nothing (hand continues)
+        msg!("No auto-win: {} active players remaining", active_count);
+        Ok(())
+    }
+
+    pub fn end_hand(ctx: Context<EndHand>, winner_position: u8) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        let winner_state = &mut ctx.accounts.winner_state;
+
+        require!(
+            !table.paused,
+            PokerError::TablePaused
+        );
+        require!(
+            winner_position < table.max_seats,
+            PokerError::InvalidPosition
+        );
+        require!(
+            table.players[winner_position as usize] == winner_state.player,
+            PokerError::InvalidWinner
+        );
+
+        // Award pot to winner, less rake
+        let (fee_vault, other_accounts) =
+            split_remaining_accounts(ctx.remaining_accounts, &table.fee_vault);
+        let (_, other_accounts) = split_player_stats(&other_accounts);
+        let (history_account, other_accounts) =
+            split_remaining_accounts(&other_accounts, &table.hand_history);
+        let (tournament_account, player_accounts) =
+            split_remaining_accounts(&other_accounts, &table.tournament);
+        let contributions = hand_contributions(
+            &table.key(),
+            table.hand_number,
+            &player_accounts,
+            vec![(winner_state.player, winner_state.committed_this_hand(table.hand_number))],
+        )?;
+        let rake = collect_rake(table, fee_vault, &contributions, ctx.remaining_accounts)?;
+        let mut actions = showdown_actions(&table.key(), &player_accounts, winner_position)?;
+        if rake > 0 {
+            actions.push(HandAction::Rake { amount: rake });
+        }
+        actions.push(HandAction::Payout { seat: winner_position, amount: table.pot });
+        record_hand_actions(table, history_account, actions)?;
+        table.hand_history = Pubkey::default();
+        winner_state.stack += table.pot;
+
+        emit!(HandComplete {
+            table: table.key(),
+            hand_number: table.hand_number,
+            winner: winner_state.player,
+            position: winner_position,
+            pot: table.pot,
+            rake,
+        });
+
+        table.pot = 0;
+        table.game_state = GameState::HandComplete;
+        release_leaving_seats(table.key(), table)?;
+
+        // Tournament tables pass the tournament and every seated player so busts are
+        // credited to the player who won the hand
+        record_tournament_busts(&table.key(), table, winner_state.player, tournament_account, &player_accounts)?;
+
+        msg!("Hand complete. Winner: position {}", winner_position);
+        Ok(())
+    }
+
+    pub fn set_blind_structure(
+        ctx: Context<SetBlindStructure>,
+        levels: Vec<BlindLevel>,
+        duration_unit: LevelDurationUnit,
+        level_duration: u64,
+    ) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+
+        require!(
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
+        );
+        // Replacing a tournament's schedule would reset its level clock
+        require!(
+            table.tournament == Pubkey::default() || table.blind_structure == Pubkey::default(),
+            PokerError::TournamentTable
+        );
+        require!(
+            !levels.is_empty() && levels.len() <= MAX_BLIND_LEVELS && level_duration > 0,
+            PokerError::InvalidBlindStructure
+        );
+        require!(
+            levels.iter().all(|level| level.small_blind > 0 && level.big_blind >= level.small_blind),
+            PokerError::InvalidBlindStructure
+        );
+
+        let blind_structure = &mut ctx.accounts.blind_structure;
+        blind_structure.table = table.key();
+        blind_structure.bump = ctx.bumps.blind_structure;
+        blind_structure.levels = levels;
+        blind_structure.duration_unit = duration_unit;
+        blind_structure.level_duration = level_duration;
+        blind_structure.current_level = 0;
+        blind_structure.level_started_hand = 0;
+        blind_structure.level_started_at = 0;
+
+        // Play at the first level until the clock starts with the next hand
+        let level = blind_structure.levels[0];
+        table.blind_structure = blind_structure.key();
+        table.small_blind = level.small_blind;
+        table.big_blind = level.big_blind;
+        table.ante = level.ante;
+
+        emit!(BlindsIncreased {
+            table: table.key(),
+            level: 0,
+            small_blind: table.small_blind,
+            big_blind: table.big_blind,
+            ante: table.ante,
+        });
+
+        msg!("Blind structure with {} levels set for table {}", blind_structure.levels.len(), table.table_id);
+        Ok(())
+    }
+
+    pub fn remove_blind_structure(ctx: Context<RemoveBlindStructure>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+
+        require!(
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
+        );
+        require!(table.tournament == Pubkey::default(), PokerError::TournamentTable);
+
+        // The table keeps playing the current level as fixed blinds
+        table.blind_structure = Pubkey::default();
+
+        msg!("Blind structure removed from table {}", table.table_id);
+        Ok(())
+    }
+
+    // Tournaments
+
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id: u64,
+        entry_fee: u64,
+        starting_stack: u64,
+        max_entrants: u8,
+        payout_bps: Vec<u16>,
+    ) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+
+        require_tournament_ready_table(table)?;
+        require!(
+            max_entrants >= 2 && max_entrants as usize <= MAX_TOURNAMENT_ENTRANTS,
+            PokerError::InvalidTournamentConfig
+        );
+        require!(
+            starting_stack > 0,
+            PokerError::InvalidTournamentConfig
+        );
+        validate_payout_bps(&payout_bps, max_entrants)?;
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.tournament_id = tournament_id;
+        tournament.creator = ctx.accounts.authority.key();
+        tournament.bump = ctx.bumps.tournament;
+        tournament.tables = vec![table.key()];
+        tournament.table_counts = vec![0];
+        tournament.seats_per_table = table.max_seats;
+        tournament.status = TournamentStatus::Registering;
+        tournament.entry_fee = entry_fee;
+        tournament.starting_stack = starting_stack;
+        tournament.max_entrants = max_entrants;
+        tournament.prize_pool = 0;
+        tournament.payout_bps = payout_bps;
+        tournament.entries = Vec::new();
+        tournament.players_remaining = 0;
+        tournament.deal_made = false;
+        tournament.bounty_type = BountyType::None;
+        tournament.bounty_bps = 0;
+        tournament.rebuy_fee = 0;
+        tournament.rebuy_chips = 0;
+        tournament.max_rebuys = 0;
+        tournament.rebuy_period_levels = 0;
+        tournament.addon_fee = 0;
+        tournament.addon_chips = 0;
+        tournament.blind_level = 0;
+        tournament.level_started_hand = 0;
+        tournament.level_started_at = 0;
+        tournament.hands_dealt = 0;
+
+        // The table now belongs to the tournament
+        table.tournament = tournament.key();
+        table.min_buy_in = starting_stack;
+        table.max_buy_in = starting_stack;
+
+        emit!(TournamentTableAdded {
+            tournament: tournament.key(),
+            table: table.key(),
+        });
+
+        msg!("Tournament {} created on table {}", tournament_id, table.table_id);
+        Ok(())
+    }
+
+    pub fn set_payout_structure(
+        ctx: Context<ConfigureTournament>,
+        payout_bps: Vec<u16>,
+    ) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+
+        require!(
+            tournament.status == TournamentStatus::Registering,
+            PokerError::RegistrationClosed
+        );
+        validate_payout_bps(&payout_bps, tournament.max_entrants)?;
+
+        tournament.payout_bps = payout_bps;
+
+        emit!(PayoutStructureSet {
+            tournament: tournament.key(),
+            payout_bps: tournament.payout_bps.clone(),
+        });
+
+        msg!("Tournament {} now pays {} places", tournament.tournament_id, tournament.payout_bps.len());
+        Ok(())
+    }
+
+    pub fn set_tournament_bounty(
+        ctx: Context<ConfigureTournament>,
+        bounty_type: BountyType,
+        bounty_bps: u16,
+    ) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+
+        // Every entry must be split the same way, so bounties are fixed before anyone registers
+        require!(
+            tournament.status == TournamentStatus::Registering && tournament.entries.is_empty(),
+            PokerError::RegistrationClosed
+        );
+        require!(
+            (bounty_type == BountyType::None) == (bounty_bps == 0)
+            && (bounty_bps as u64) < BPS_DENOMINATOR,
+            PokerError::InvalidTournamentConfig
+        );
+
+        tournament.bounty_type = bounty_type;
+        tournament.bounty_bps = bounty_bps;
+
+        emit!(TournamentBountySet {
+            tournament: tournament.key(),
+            bounty_type,
+            bounty_bps,
+        });
+
+        msg!("Tournament {} bounty set to {} bps of each entry", tournament.tournament_id, bounty_bps);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_tournament_rebuys(
+        ctx: Context<ConfigureTournament>,
+        rebuy_fee: u64,
+        rebuy_chips: u64,
+        max_rebuys: u8,
+        rebuy_period_levels: u8,
+        addon_fee: u64,
+        addon_chips: u64,
+    ) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+
+        require!(
+            tournament.status == TournamentStatus::Registering,
+            PokerError::RegistrationClosed
+        );
+        // A rebuy period needs something to buy; no period means no rebuys or add-on
+        require!(
+            (rebuy_period_levels == 0) == (max_rebuys == 0 && addon_chips == 0),
+            PokerError::InvalidTournamentConfig
+        );
+        require!(
+            (max_rebuys == 0 || (rebuy_fee > 0 && rebuy_chips > 0))
+            && (addon_fee == 0 || addon_chips > 0),
+            PokerError::InvalidTournamentConfig
+        );
+
+        tournament.rebuy_fee = rebuy_fee;
+        tournament.rebuy_chips = rebuy_chips;
+        tournament.max_rebuys = max_rebuys;
+        tournament.rebuy_period_levels = rebuy_period_levels;
+        tournament.addon_fee = addon_fee;
+        tournament.addon_chips = addon_chips;
+
+        emit!(TournamentRebuysSet {
+            tournament: tournament.key(),
+            rebuy_fee,
+            rebuy_chips,
+            max_rebuys,
+            rebuy_period_levels,
+            addon_fee,
+            addon_chips,
+        });
+
+        msg!("Tournament {} allows {} rebuys over {} levels", tournament.tournament_id, max_rebuys, rebuy_period_levels);
+        Ok(())
+    }
+
+    pub fn add_tournament_table(ctx: Context<AddTournamentTable>) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let table = &mut ctx.accounts.table;
+
+        require!(
+            tournament.status == TournamentStatus::Registering,
+            PokerError::RegistrationClosed
+        );
+        require!(
+            tournament.tables.len() < MAX_TOURNAMENT_TABLES,
+            PokerError::TooManyTables
+        );
+        require_tournament_ready_table(table)?;
+        // Balancing assumes every table in the tournament has the same number of seats
+        require!(
+            table.max_seats == tournament.seats_per_table,
+            PokerError::TableNotAvailable
+        );
+        // The tournament's level clock picks a level by index, so every table needs the same schedule
+        let blind_structure = &ctx.accounts.blind_structure;
+        let schedule = &ctx.accounts.tournament_blind_structure;
+        require!(
+            blind_structure.levels == schedule.levels
+            && blind_structure.duration_unit == schedule.duration_unit
+            && blind_structure.level_duration == schedule.level_duration,
+            PokerError::InvalidBlindStructure
+        );
+
+        tournament.tables.push(table.key());
+        tournament.table_counts.push(0);
+
+        table.tournament = tournament.key();
+        table.min_buy_in = tournament.starting_stack;
+        table.max_buy_in = tournament.starting_stack;
+
+        emit!(TournamentTableAdded {
+            tournament: tournament.key(),
+            table: table.key(),
+        });
+
+        msg!("Table {} added to tournament {}", table.table_id, tournament.tournament_id);
+        Ok(())
+    }
+
+    pub fn register_tournament(ctx: Context<RegisterTournament>) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let table = &mut ctx.accounts.table;
+        let player = ctx.accounts.player.key();
+
+        require!(
+            tournament.status == TournamentStatus::Registering,
+            PokerError::RegistrationClosed
+        );
+        require!(
+            tournament.entries.iter().all(|entry| entry.player != player),
+            PokerError::AlreadyRegistered
+        );
+        require!(
+            tournament.entries.len() < tournament.max_entrants as usize,
+            PokerError::TableFull
+        );
+        // Tables are added while registration is open, but the field can't fill until
+        // every entrant has a seat
+        require!(
+            tournament.tables.len() * tournament.seats_per_table as usize
+                >= tournament.max_entrants as usize,
+            PokerError::NotEnoughSeats
+        );
+
+        // Registrants are seated at the shortest table to keep the field balanced
+        let table_index = tournament
+            .tables
+            .iter()
+            .position(|key| *key == table.key())
+            .ok_or(PokerError::NotTournamentTable)?;
+        let shortest = *tournament.table_counts.iter().min().unwrap();
+        require!(
+            tournament.table_counts[table_index] == shortest,
+            PokerError::TableNotBalanced
+        );
+
+        // Entry fee goes straight into the tournament account as prize pool
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: tournament.to_account_info(),
+                },
+            ),
+            tournament.entry_fee,
+        )?;
+        let bounty = (tournament.entry_fee as u128 * tournament.bounty_bps as u128
+            / BPS_DENOMINATOR as u128) as u64;
+        tournament.prize_pool += tournament.entry_fee - bounty;
+
+        // Seat the registrant in the first empty seat
+        let position = table
+            .players
+            .iter()
+            .position(|seat| *seat == Pubkey::default())
+            .ok_or(PokerError::TableFull)? as u8;
+
+        let player_state = &mut ctx.accounts.player_state;
+        player_state.player = player;
+        player_state.table = table.key();
+        player_state.stack = tournament.starting_stack;
+        player_state.current_bet = 0;
+        player_state.position = position;
+        player_state.status = PlayerStatus::Active;
+        player_state.hole_cards_computation = Pubkey::default();
+        player_state.has_acted_this_street = false;
+        player_state.leaving = false;
+
+        table.players[position as usize] = player;
+        table.player_count += 1;
+
+        emit!(PlayerJoined {
+            table: table.key(),
+            player,
+            position,
+            stack: tournament.starting_stack,
+        });
+
+        tournament.entries.push(TournamentEntry {
+            player,
+            table_index: table_index as u8,
+            finish_place: 0,
+            paid: false,
+            deal_payout: None,
+            bounty,
+            bounties_won: 0,
+            eliminated_by: None,
+            busted_at: 0,
+            rebuys: 0,
+            addon_taken: false,
+        });
+        tournament.table_counts[table_index] += 1;
+
+        // Start as soon as the field is full
+        if tournament.entries.len() == tournament.max_entrants as usize {
+            tournament.status = TournamentStatus::Running;
+            tournament.players_remaining = tournament.max_entrants;
+
+            emit!(TournamentStarted {
+                tournament: tournament.key(),
+                tables: tournament.tables.len() as u8,
+                entrants: tournament.max_entrants,
+                prize_pool: tournament.prize_pool,
+            });
+        }
+
+        msg!("Player {} registered for tournament at position {}", player, position);
+        Ok(())
+    }
+
+    pub fn unregister_tournament(ctx: Context<UnregisterTournament>) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let table = &mut ctx.accounts.table;
+        let player_state = &ctx.accounts.player_state;
+        let player = ctx.accounts.player.key();
+
+        require!(
+            tournament.status == TournamentStatus::Registering,
+            PokerError::RegistrationClosed
+        );
+        let index = tournament
+            .entries
+            .iter()
+            .position(|entry| entry.player == player)
+            .ok_or(PokerError::NotRegistered)?;
+        let entry = tournament.entries.remove(index);
+
+        // The whole entry fee comes back, bounty included
+        tournament.prize_pool -= tournament.entry_fee - entry.bounty;
+        tournament.table_counts[entry.table_index as usize] -= 1;
+        tournament.sub_lamports(tournament.entry_fee)?;
+        ctx.accounts.player.add_lamports(tournament.entry_fee)?;
+
+        let position = player_state.position;
+        table.players[position as usize] = Pubkey::default();
+        table.player_count -= 1;
+
+        emit!(PlayerLeft {
+            table: table.key(),
+            player,
+            position,
+            cashed_out: 0,
+            forfeited: false,
+        });
+        emit!(TournamentUnregistered {
+            tournament: tournament.key(),
+            player,
+            refund: tournament.entry_fee,
+        });
+
+        msg!("Player {} unregistered from tournament {}", player, tournament.tournament_id);
+        Ok(())
+    }
+
+    // Refunds every entry. remaining_accounts: each registrant's wallet, in entry order.
+    // Registrants then stand up with leave_table.
+    pub fn cancel_tournament<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelTournament<'info>>,
+    ) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let signer = ctx.accounts.signer.key();
+
+        require!(
+            tournament.creator == signer
+            || ctx.accounts.config.as_ref().is_some_and(|config| config.admin == signer),
+            PokerError::Unauthorized
+        );
+        require!(
+            tournament.status == TournamentStatus::Registering,
+            PokerError::RegistrationClosed
+        );
+        require!(
+            ctx.remaining_accounts.len() == tournament.entries.len(),
+            PokerError::MissingPlayerAccounts
+        );
+
+        let entry_fee = tournament.entry_fee;
+        for (entry, wallet) in tournament.entries.iter_mut().zip(ctx.remaining_accounts) {
+            require!(wallet.key() == entry.player, PokerError::NotRegistered);
+            entry.paid = true;
+            entry.bounty = 0;
+        }
+        let refunded = entry_fee * tournament.entries.len() as u64;
+        tournament.sub_lamports(refunded)?;
+        for wallet in ctx.remaining_accounts {
+            wallet.add_lamports(entry_fee)?;
+        }
+        tournament.prize_pool = 0;
+        tournament.status = TournamentStatus::Cancelled;
+
+        emit!(TournamentCancelled {
+            tournament: tournament.key(),
+            entries_refunded: tournament.entries.len() as u8,
+            refunded,
+        });
+
+        msg!("Tournament {} cancelled, {} refunded", tournament.tournament_id, refunded);
+        Ok(())
+    }
+
+    pub fn eliminate_player(ctx: Context<EliminatePlayer>) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let table = &mut ctx.accounts.table;
+        let player_state = &ctx.accounts.player_state;
+
+        require!(
+            tournament.status == TournamentStatus::Running,
+            PokerError::TournamentNotRunning
+        );
+        require!(
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
+        );
+        require!(
+            player_state.stack == 0,
+            PokerError::PlayerNotBusted
+        );
+
+        let position = player_state.position as usize;
+        require!(
+            table.players[position] == player_state.player,
+            PokerError::NotAtTable
+        );
+
+        let finish_place = tournament.players_remaining;
+        let rebuy_open = tournament.blind_level < tournament.rebuy_period_levels;
+        let max_rebuys = tournament.max_rebuys;
+        let entry = tournament
+            .entries
+            .iter_mut()
+            .find(|entry| entry.player == player_state.player)
+            .ok_or(PokerError::NotRegistered)?;
+        require!(
+            entry.eliminated_by.is_some(),
+            PokerError::BustNotRecorded
+        );
+        // A player who can still rebuy gets a window to decide, unless they sign to decline
+        require!(
+            !rebuy_open
+            || entry.rebuys >= max_rebuys
+            || ctx.accounts.player.is_signer
+            || Clock::get()?.unix_timestamp >= entry.busted_at + REBUY_DECISION_SECONDS,
+            PokerError::RebuyStillAvailable
+        );
+        entry.finish_place = finish_place;
+        let table_index = entry.table_index as usize;
+
+        table.players[position] = Pubkey::default();
+        table.player_count -= 1;
+        tournament.table_counts[table_index] -= 1;
+        tournament.players_remaining -= 1;
+
+        emit!(PlayerEliminated {
+            tournament: tournament.key(),
+            table: table.key(),
+            player: player_state.player,
+            finish_place,
+        });
+
+        // Last player standing takes first place
+        if tournament.players_remaining == 1 {
+            let entry = tournament
+                .entries
+                .iter_mut()
+                .find(|entry| entry.finish_place == 0)
+                .ok_or(PokerError::NoActivePlayersRemaining)?;
+            entry.finish_place = 1;
+            let winner = entry.player;
+            tournament.status = TournamentStatus::Finished;
+
+            emit!(TournamentFinished {
+                tournament: tournament.key(),
+                winner,
+                prize_pool: tournament.prize_pool,
+            });
+        }
+
+        msg!("Player {} eliminated in place {}", player_state.player, finish_place);
+        Ok(())
+    }
+
+    pub fn rebuy(ctx: Context<TournamentPurchase>) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let table = &ctx.accounts.table;
+        let player_state = &mut ctx.accounts.player_state;
+
+        require!(
+            tournament.status == TournamentStatus::Running,
+            PokerError::TournamentNotRunning
+        );
+        require!(
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
+        );
+        require!(
+            tournament.blind_level < tournament.rebuy_period_levels,
+            PokerError::RebuyPeriodOver
+        );
+        require!(
+            player_state.stack <= tournament.starting_stack,
+            PokerError::StackTooLargeToRebuy
+        );
+
+        let fee = tournament.rebuy_fee;
+        let bounty = (fee as u128 * tournament.bounty_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        let max_rebuys = tournament.max_rebuys;
+        let entry = tournament
+            .entries
+            .iter_mut()
+            .find(|entry| entry.player == player_state.player)
+            .ok_or(PokerError::NotRegistered)?;
+        require!(
+            entry.finish_place == 0 && entry.rebuys < max_rebuys,
+            PokerError::RebuyLimitReached
+        );
+
+        // A busted player who rebuys is back in, with a fresh bounty on their head
+        entry.rebuys += 1;
+        entry.bounty += bounty;
+        entry.eliminated_by = None;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: tournament.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+        tournament.prize_pool += fee - bounty;
+        player_state.stack += tournament.rebuy_chips;
+
+        emit!(TournamentPurchased {
+            tournament: tournament.key(),
+            player: player_state.player,
+            addon: false,
+            fee,
+            chips: tournament.rebuy_chips,
+        });
+
+        msg!("Player {} rebought for {} chips", player_state.player, tournament.rebuy_chips);
+        Ok(())
+    }
+
+    pub fn addon(ctx: Context<TournamentPurchase>) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let table = &ctx.accounts.table;
+        let player_state = &mut ctx.accounts.player_state;
+
+        require!(
+            tournament.status == TournamentStatus::Running,
+            PokerError::TournamentNotRunning
+        );
+        require!(
+            table.game_state == GameState::WaitingForPlayers
+            || table.game_state == GameState::HandComplete,
+            PokerError::GameInProgress
+        );
+        // The add-on is offered once, in the first level after rebuys close
+        require!(
+            tournament.addon_chips > 0
+            && tournament.blind_level == tournament.rebuy_period_levels,
+            PokerError::AddonNotAvailable
+        );
+        require!(
+            player_state.stack > 0,
+            PokerError::EliminationPending
+        );
+
+        let entry = tournament
+            .entries
+            .iter_mut()
+            .find(|entry| entry.player == player_state.player)
+            .ok_or(PokerError::NotRegistered)?;
+        require!(!entry.addon_taken, PokerError::AddonNotAvailable);
+        entry.addon_taken = true;
+
+        let fee = tournament.addon_fee;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: tournament.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+        tournament.prize_pool += fee;
+        player_state.stack += tournament.addon_chips;
+
+        emit!(TournamentPurchased {
+            tournament: tournament.key(),
+            player: player_state.player,
+            addon: true,
+            fee,
+            chips: tournament.addon_chips,
+        });
+
+        msg!("Player {} took the add-on for {} chips", player_state.player, tournament.addon_chips);
+        Ok(())
+    }
+
+    pub fn move_tournament_player(ctx: Context<MoveTournamentPlayer>) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let from_table = &mut ctx.accounts.from_table;
+        let to_table = &mut ctx.accounts.to_table;
+        let from_state = &ctx.accounts.from_player_state;
+        let payer = ctx.accounts.payer.key();
+
+        // Seating is the tournament director's call: the creator or the program admin
+        require!(
+            tournament.creator == payer
+            || ctx.accounts.config.as_ref().is_some_and(|config| config.admin == payer),
+            PokerError::Unauthorized
+        );
+        require!(
+            tournament.status == TournamentStatus::Running,
+            PokerError::TournamentNotRunning
+        );
+        require!(
+            from_table.key() != to_table.key(),
+            PokerError::InvalidTableMove
+        );
+        // Both tables must be between hands so the chips are not in play
+        for table in [&**from_table, &**to_table] {
+            require!(
+                table.game_state == GameState::WaitingForPlayers
+                || table.game_state == GameState::HandComplete,
+                PokerError::GameInProgress
+            );
+        }
+        require!(
+            from_state.stack > 0,
+            PokerError::EliminationPending
+        );
+        require!(
+            from_table.players[from_state.position as usize] == from_state.player,
+            PokerError::NotAtTable
+        );
+
+        let from_index = tournament
+            .tables
+            .iter()
+            .position(|key| *key == from_table.key())
+            .ok_or(PokerError::NotTournamentTable)?;
+        let to_index = tournament
+            .tables
+            .iter()
+            .position(|key| *key == to_table.key())
+            .ok_or(PokerError::NotTournamentTable)?;
+
+        // Either rebalance a table that is two or more players longer, or break the shortest table
+        let from_count = tournament.table_counts[from_index];
+        let to_count = tournament.table_counts[to_index];
+        require!(
+            to_count > 0 && to_count < tournament.seats_per_table,
+            PokerError::TableFull
+        );
+        require!(
+            from_count >= to_count + 2 || is_table_breakable(tournament, from_index),
+            PokerError::TableNotBalanced
+        );
+
+        let position = to_table
+            .players
+            .iter()
+            .position(|seat| *seat == Pubkey::default())
+            .ok_or(PokerError::TableFull)? as u8;
+
+        // Chips and seat move together: the old player state closes as the new one opens
+        let to_state = &mut ctx.accounts.to_player_state;
+        to_state.player = from_state.player;
+        to_state.table = to_table.key();
+        to_state.stack = from_state.stack;
+        to_state.current_bet = 0;
+        to_state.position = position;
+        to_state.status = PlayerStatus::Active;
+        to_state.hole_cards_computation = Pubkey::default();
+        to_state.has_acted_this_street = false;
+        to_state.leaving = false;
+
+        from_table.players[from_state.position as usize] = Pubkey::default();
+        from_table.player_count -= 1;
+        to_table.players[position as usize] = from_state.player;
+        to_table.player_count += 1;
+
+        tournament.table_counts[from_index] -= 1;
+        tournament.table_counts[to_index] += 1;
+        let entry = tournament
+            .entries
+            .iter_mut()
+            .find(|entry| entry.player == from_state.player)
+            .ok_or(PokerError::NotRegistered)?;
+        entry.table_index = to_index as u8;
+
+        emit!(TournamentPlayerMoved {
+            tournament: tournament.key(),
+            player: from_state.player,
+            from_table: from_table.key(),
+            to_table: to_table.key(),
+            position,
+            stack: from_state.stack,
+        });
+
+        if tournament.table_counts[from_index] == 0 {
+            msg!("Table {} broken", from_table.table_id);
+        }
+
+        msg!("Moved player {} to table {} position {}", from_state.player, to_table.table_id, position);
+        Ok(())
+    }
+
+    pub fn propose_deal(ctx: Context<ProposeDeal>, kind: DealKind) -> Result<()> {
+        let tournament = &ctx.accounts.tournament;
+        let proposer = ctx.accounts.proposer.key();
+
+        require!(
+            tournament.status == TournamentStatus::Running,
+            PokerError::TournamentNotRunning
+        );
+        require!(
+            tournament.players_remaining <= tournament.seats_per_table,
+            PokerError::DealNotAllowed
+        );
+
+        let stacks = load_deal_stacks(tournament, ctx.remaining_accounts)?;
+        require!(
+            stacks.iter().any(|(player, _)| *player == proposer),
+            PokerError::NotInDeal
+        );
+
+        // Only the places still to be decided are up for negotiation
+        let prizes: Vec<u64> = (1..=tournament.players_remaining)
+            .map(|place| place_payout(tournament, place))
+            .collect();
+        let prize_pool: u64 = prizes.iter().sum();
+        let chips: Vec<u64> = stacks.iter().map(|(_, stack)| *stack).collect();
+        require!(
+            kind != DealKind::Icm || chips.len() <= MAX_ICM_DEAL_PLAYERS,
+            PokerError::TooManyForIcm
+        );
+        let amounts = match kind {
+            DealKind::Icm => icm_equities(&chips, &prizes),
+            DealKind::ChipChop => chip_chop_shares(&chips, prize_pool),
+        };
+
+        let deal = &mut ctx.accounts.deal;
+        deal.tournament = tournament.key();
+        deal.bump = ctx.bumps.deal;
+        deal.kind = kind;
+        deal.proposer = proposer;
+        deal.prize_pool = prize_pool;
+        deal.shares = stacks
+            .iter()
+            .zip(amounts)
+            .map(|((player, stack), amount)| DealShare {
+                player: *player,
+                stack: *stack,
+                amount,
+                accepted: *player == proposer,
+            })
+            .collect();
+
+        emit!(DealProposed {
+            tournament: tournament.key(),
+            proposer,
+            kind,
+            prize_pool,
+        });
+
+        msg!("Deal proposed by {} over {} lamports", proposer, prize_pool);
+        Ok(())
+    }
+
+    pub fn accept_deal(ctx: Context<AcceptDeal>) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let deal = &mut ctx.accounts.deal;
+        let player = ctx.accounts.player.key();
+
+        require!(
+            tournament.status == TournamentStatus::Running,
+            PokerError::TournamentNotRunning
+        );
+
+        // Any hand played since the proposal invalidates it
+        let stacks = load_deal_stacks(tournament, ctx.remaining_accounts)?;
+        require!(
+            stacks.len() == deal.shares.len()
+            && stacks
+                .iter()
+                .zip(deal.shares.iter())
+                .all(|((player, stack), share)| *player == share.player && *stack == share.stack),
+            PokerError::DealStale
+        );
+
+        let share = deal
+            .shares
+            .iter_mut()
+            .find(|share| share.player == player)
+            .ok_or(PokerError::NotInDeal)?;
+        share.accepted = true;
+
+        if deal.shares.iter().any(|share| !share.accepted) {
+            msg!("Player {} accepted the deal", player);
+            return Ok(());
+        }
+
+        // Everyone signed: finishing places follow chip counts and the deal sets the payouts
+        let mut ranked: Vec<&DealShare> = deal.shares.iter().collect();
+        ranked.sort_by_key(|share| std::cmp::Reverse(share.stack));
+        for (rank, share) in ranked.iter().enumerate() {
+            let entry = tournament
+                .entries
+                .iter_mut()
+                .find(|entry| entry.player == share.player)
+                .ok_or(PokerError::NotRegistered)?;
+            entry.finish_place = rank as u8 + 1;
+            entry.deal_payout = Some(share.amount);
+        }
+        tournament.deal_made = true;
+        tournament.status = TournamentStatus::Finished;
+
+        emit!(DealAccepted {
+            tournament: tournament.key(),
+            kind: deal.kind,
+            prize_pool: deal.prize_pool,
+        });
+
+        msg!("Deal accepted by all {} players", deal.shares.len());
+
+        // The payouts now live on the tournament, so the proposer gets the rent back
+        ctx.accounts.deal.close(ctx.accounts.proposer.to_account_info())
+
+    }
+
+    pub fn reject_deal(ctx: Context<RejectDeal>) -> Result<()> {
+        let deal = &ctx.accounts.deal;
+        let player = ctx.accounts.player.key();
+
+        require!(
+            deal.shares.iter().any(|share| share.player == player),
+            PokerError::NotInDeal
+        );
+
+        emit!(DealRejected {
+            tournament: deal.tournament,
+            player,
+        });
+
+        msg!("Player {} rejected the deal", player);
+        Ok(())
+    }
+
+    pub fn claim_tournament_payout(ctx: Context<ClaimTournamentPayout>) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let player = ctx.accounts.player.key();
+
+        require!(
+            tournament.status == TournamentStatus::Finished,
+            PokerError::TournamentNotFinished
+        );
+
+        let entry = *tournament
+            .entries
+            .iter()
+            .find(|entry| entry.player == player)
+            .ok_or(PokerError::NotRegistered)?;
+
+        require!(!entry.paid, PokerError::PayoutAlreadyClaimed);
+
+        // Players in a deal are paid what they agreed; everyone else by the payout table.
+        // Bounties won are paid on top, and survivors take back the bounty on their own head.
+        let finish_place = entry.finish_place;
+        let payout = match entry.deal_payout {
+            Some(amount) => amount,
+            None => place_payout(tournament, finish_place),
+        } + entry.bounties_won + entry.bounty;
+        require!(payout > 0, PokerError::NoPayoutDue);
+
+        tournament
+            .entries
+            .iter_mut()
+            .find(|entry| entry.player == player)
+            .ok_or(PokerError::NotRegistered)?
+            .paid = true;
+
+        tournament.sub_lamports(payout)?;
+        ctx.accounts.player.add_lamports(payout)?;
+
+        emit!(TournamentPayoutClaimed {
+            tournament: tournament.key(),
+            player,
+            finish_place,
+            amount: payout,
+        });
+
+        msg!("Player {} paid {} for place {}", player, payout, finish_place);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(table_id: u64)]
+pub struct InitializeTable<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PokerTable::INIT_SPACE,
+        seeds = [b"table", table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub table: Account<'info, PokerTable>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinTable<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        init,
+        payer = player,
+        space = 8 + PlayerState::INIT_SPACE,
+        seeds = [b"player", table.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub player_state: Account<'info, PlayerState>,
+    // Private tables only seat players the authority has invited
+    #[account(
+        seeds = [b"invite", table.key().as_ref(), player.key().as_ref()],
+        bump = invite.bump
+    )]
+    pub invite: Option<Account<'info, TableInvite>>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveTable<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+    // Closed by hand: a player leaving mid-hand keeps the account until the hand completes
+    #[account(
+        mut,
+        seeds = [b"player", table.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub player_state: Account<'info, PlayerState>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SitOut<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        mut,
+        seeds = [b"player", table.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub player_state: Account<'info, PlayerState>,
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureTable<'info> {
+    #[account(mut, has_one = authority)]
+    pub table: Account<'info, PokerTable>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct InvitePlayer<'info> {
+    #[account(has_one = authority)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TableInvite::INIT_SPACE,
+        seeds = [b"invite", table.key().as_ref(), player.as_ref()],
+        bump
+    )]
+    pub invite: Account<'info, TableInvite>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeInvite<'info> {
+    #[account(has_one = authority)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        mut,
+        close = authority,
+        has_one = table,
+        seeds = [b"invite", table.key().as_ref(), invite.player.as_ref()],
+        bump = invite.bump
+    )]
+    pub invite: Account<'info, TableInvite>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenHandHistory<'info> {
+    #[account(mut, has_one = authority)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + HandHistory::INIT_SPACE,
+        seeds = [b"hand_history", table.key().as_ref(), (table.hand_number + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub hand_history: Account<'info, HandHistory>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseHandHistory<'info> {
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        mut,
+        close = payer,
+        has_one = table,
+        has_one = payer,
+        seeds = [b"hand_history", table.key().as_ref(), hand_history.hand_number.to_le_bytes().as_ref()],
+        bump = hand_history.bump
+    )]
+    pub hand_history: Account<'info, HandHistory>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRake<'info> {
+    #[account(mut, has_one = authority)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [b"fee_vault", table.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawRake<'info> {
+    #[account(
+        mut,
+        has_one = rake_authority,
+        seeds = [b"fee_vault", fee_vault.table.as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    pub rake_authority: Signer<'info>,
+    /// CHECK: Any account may receive the rake
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePlayerStats<'info> {
+    #[account(
+        init,
+        payer = player,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRakeback<'info> {
+    #[account(
+        mut,
+        has_one = player,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = upgrade_authority,
+        space = 8 + ProgramConfig::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    // Only the program's upgrade authority may appoint the admin
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Poker>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(upgrade_authority.key()) @ PokerError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TableAdmin<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Option<Account<'info, ProgramConfig>>,
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTable<'info> {
+    #[account(mut, close = creator, has_one = creator, has_one = authority)]
+    pub table: Account<'info, PokerTable>,
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+    // Gets back the rent of the table's invites
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct JoinWaitlist<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + Waitlist::INIT_SPACE,
+        seeds = [b"waitlist", table.key().as_ref()],
+        bump
+    )]
+    pub waitlist: Account<'info, Waitlist>,
+    #[account(
+        seeds = [b"invite", table.key().as_ref(), player.key().as_ref()],
+        bump = invite.bump
+    )]
+    pub invite: Option<Account<'info, TableInvite>>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveWaitlist<'info> {
+    #[account(
+        mut,
+        seeds = [b"waitlist", waitlist.table.as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Account<'info, Waitlist>,
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TopUp<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        mut,
+        seeds = [b"player", table.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub player_state: Account<'info, PlayerState>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StartHand<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+}
+
+#[derive(Accounts)]
+pub struct PostBlinds<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        mut,
+        seeds = [b"player", table.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub player_state: Account<'info, PlayerState>,
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PlayerAction<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        mut,
+        seeds = [b"player", table.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub player_state: Account<'info, PlayerState>,
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CrankTimeout<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        mut,
+        seeds = [b"player", table.key().as_ref(), table.players[table.current_player_index as usize].as_ref()],
+        bump
+    )]
+    pub player_state: Account<'info, PlayerState>,
+}
+
+#[derive(Accounts)]
+pub struct LegalActions<'info> {
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        seeds = [b"player", table.key().as_ref(), table.players[table.current_player_index as usize].as_ref()],
+        bump
+    )]
+    pub player_state: Account<'info, PlayerState>,
+}
+
+#[derive(Accounts)]
+pub struct AdvanceStreet<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+}
+
+#[derive(Accounts)]
+pub struct AdvanceStreetAuto<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+}
+
+#[derive(Accounts)]
+pub struct CheckAutoWin<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+}
 
-        require!(
-            winner_position < MAX_PLAYERS as u8,
-            PokerError::InvalidPosition
-        );
-        require!(
-            table.players[winner_position as usize] == winner_state.player,
-            PokerError::InvalidWinner
-        );
+#[derive(Accounts)]
+pub struct EndHand<'info> {
+    #[account(mut)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        mut,
+        seeds = [b"player", table.key().as_ref(), winner_state.player.as_ref()],
+        bump
+    )]
+    pub winner_state: Account<'info, PlayerState>,
+}
 
-        // Award pot to winner
-        winner_state.stack += table.pot;
-        table.pot = 0;
-        table.game_state = GameState::HandComplete;
+#[derive(Accounts)]
+pub struct SetBlindStructure<'info> {
+    #[account(mut, has_one = authority)]
+    pub table: Account<'info, PokerTable>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + BlindStructure::INIT_SPACE,
+        seeds = [b"blind_structure", table.key().as_ref()],
+        bump
+    )]
+    pub blind_structure: Account<'info, BlindStructure>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
 
-        msg!("Hand complete. Winner: position {}", winner_position);
-        Ok(())
-    }
+#[derive(Accounts)]
+pub struct RemoveBlindStructure<'info> {
+    #[account(mut, has_one = authority, has_one = blind_structure)]
+    pub table: Account<'info, PokerTable>,
+    #[account(mut, close = authority)]
+    pub blind_structure: Account<'info, BlindStructure>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(table_id: u64)]
-pub struct InitializeTable<'info> {
+#[instruction(tournament_id: u64)]
+pub struct CreateTournament<'info> {
     #[account(
         init,
-        payer = creator,
-        space = 8 + PokerTable::INIT_SPACE,
-        seeds = [b"table", table_id.to_le_bytes().as_ref()],
+        payer = authority,
+        space = 8 + Tournament::INIT_SPACE,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
         bump
     )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(mut, has_one = authority)]
     pub table: Account<'info, PokerTable>,
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct JoinTable<'info> {
-    #[account(mut)]
+pub struct ConfigureTournament<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddTournamentTable<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(mut, has_one = authority)]
+    pub table: Account<'info, PokerTable>,
+    #[account(address = table.blind_structure)]
+    pub blind_structure: Account<'info, BlindStructure>,
+    #[account(
+        seeds = [b"blind_structure", tournament.tables[0].as_ref()],
+        bump = tournament_blind_structure.bump
+    )]
+    pub tournament_blind_structure: Account<'info, BlindStructure>,
+    pub creator: Signer<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterTournament<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(mut, constraint = table.tournament == tournament.key() @ PokerError::NotTournamentTable)]
     pub table: Account<'info, PokerTable>,
     #[account(
         init,
@@ -645,8 +3893,14 @@ pub struct JoinTable<'info> {
 }
 
 #[derive(Accounts)]
-pub struct LeaveTable<'info> {
-    #[account(mut)]
+pub struct UnregisterTournament<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(mut, constraint = table.tournament == tournament.key() @ PokerError::NotTournamentTable)]
     pub table: Account<'info, PokerTable>,
     #[account(
         mut,
@@ -660,27 +3914,50 @@ pub struct LeaveTable<'info> {
 }
 
 #[derive(Accounts)]
-pub struct StartHand<'info> {
-    #[account(mut)]
-    pub table: Account<'info, PokerTable>,
+pub struct CancelTournament<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Option<Account<'info, ProgramConfig>>,
+    pub signer: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct PostBlinds<'info> {
-    #[account(mut)]
+pub struct EliminatePlayer<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(mut, constraint = table.tournament == tournament.key() @ PokerError::NotTournamentTable)]
     pub table: Account<'info, PokerTable>,
     #[account(
         mut,
-        seeds = [b"player", table.key().as_ref(), player.key().as_ref()],
+        close = player,
+        seeds = [b"player", table.key().as_ref(), player_state.player.as_ref()],
         bump
     )]
     pub player_state: Account<'info, PlayerState>,
-    pub player: Signer<'info>,
+    /// CHECK: Receives the busted player's account rent; must be the player themselves,
+    /// who signs to decline a rebuy they're still entitled to
+    #[account(mut, address = player_state.player)]
+    pub player: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
-pub struct PlayerAction<'info> {
-    #[account(mut)]
+pub struct TournamentPurchase<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(constraint = table.tournament == tournament.key() @ PokerError::NotTournamentTable)]
     pub table: Account<'info, PokerTable>,
     #[account(
         mut,
@@ -688,48 +3965,126 @@ pub struct PlayerAction<'info> {
         bump
     )]
     pub player_state: Account<'info, PlayerState>,
+    #[account(mut)]
     pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct AdvanceStreet<'info> {
+pub struct MoveTournamentPlayer<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(mut, constraint = from_table.tournament == tournament.key() @ PokerError::NotTournamentTable)]
+    pub from_table: Account<'info, PokerTable>,
+    #[account(mut, constraint = to_table.tournament == tournament.key() @ PokerError::NotTournamentTable)]
+    pub to_table: Account<'info, PokerTable>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"player", from_table.key().as_ref(), from_player_state.player.as_ref()],
+        bump
+    )]
+    pub from_player_state: Account<'info, PlayerState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PlayerState::INIT_SPACE,
+        seeds = [b"player", to_table.key().as_ref(), from_player_state.player.as_ref()],
+        bump
+    )]
+    pub to_player_state: Account<'info, PlayerState>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Option<Account<'info, ProgramConfig>>,
     #[account(mut)]
-    pub table: Account<'info, PokerTable>,
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct AdvanceStreetAuto<'info> {
+pub struct ProposeDeal<'info> {
+    #[account(
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Deal::INIT_SPACE,
+        seeds = [b"deal", tournament.key().as_ref()],
+        bump
+    )]
+    pub deal: Account<'info, Deal>,
     #[account(mut)]
-    pub table: Account<'info, PokerTable>,
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct CheckAutoWin<'info> {
+pub struct AcceptDeal<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        mut,
+        has_one = tournament,
+        has_one = proposer,
+        seeds = [b"deal", tournament.key().as_ref()],
+        bump = deal.bump
+    )]
+    pub deal: Account<'info, Deal>,
+    /// CHECK: Gets the deal account rent back once everyone accepts; checked against deal.proposer
     #[account(mut)]
-    pub table: Account<'info, PokerTable>,
+    pub proposer: UncheckedAccount<'info>,
+    pub player: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct EndHand<'info> {
+pub struct RejectDeal<'info> {
+    #[account(
+        mut,
+        close = proposer,
+        has_one = proposer,
+        seeds = [b"deal", deal.tournament.as_ref()],
+        bump = deal.bump
+    )]
+    pub deal: Account<'info, Deal>,
+    /// CHECK: Gets the deal account rent back; checked against deal.proposer
     #[account(mut)]
-    pub table: Account<'info, PokerTable>,
+    pub proposer: UncheckedAccount<'info>,
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTournamentPayout<'info> {
     #[account(
         mut,
-        seeds = [b"player", table.key().as_ref(), winner_state.player.as_ref()],
-        bump
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
     )]
-    pub winner_state: Account<'info, PlayerState>,
+    pub tournament: Account<'info, Tournament>,
+    #[account(mut)]
+    pub player: Signer<'info>,
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct PokerTable {
     pub table_id: u64,
-    pub creator: Pubkey,           // Who created the table (for reference only, no authority)
+    pub creator: Pubkey,           // Who created the table (paid its rent)
+    pub authority: Pubkey,         // Who may change the table's configuration
     pub bump: u8,                  // PDA bump seed
     pub player_count: u8,
+    pub max_seats: u8,             // Seats at the table (2-10), fixed at creation
     #[max_len(MAX_PLAYERS)]
-    pub players: Vec<Pubkey>,      // Fixed-size array of player pubkeys (default = empty seat)
+    pub players: Vec<Pubkey>,      // One pubkey per seat (default = empty seat)
     pub button_position: u8,
     pub current_player_index: u8,
     pub pot: u64,
@@ -746,19 +4101,388 @@ pub struct PokerTable {
     pub blinds_posted: u16,        // Bitmask: bit N = player at position N posted blind
     pub last_raise_amount: u64,    // Size of last raise for min-raise validation
     pub last_aggressor_index: u8,  // Position of last player who raised
+    pub tournament: Pubkey,        // Tournament that owns this table (default = cash game)
+    pub blind_structure: Pubkey,   // Blind schedule applied by start_hand (default = fixed blinds)
+    pub ante: u64,
+    pub sitting_out: u16,          // Bitmask: bit N = player at position N is sitting out
+    pub missed_small_blinds: u16,  // Bitmask: bit N = player at position N owes a small blind
+    pub missed_big_blinds: u16,    // Bitmask: bit N = player at position N owes a big blind
+    pub leaving: u16,              // Bitmask: bit N = player at position N stood up mid-hand
+    pub waitlist: Pubkey,          // Queue for seats at this table (default = none yet)
+    pub seat_freed_at: i64,        // Unix timestamp a seat last opened up
+    pub visibility: TableVisibility,
+    pub action_timeout: i64,       // Seconds a player has to act (0 = no limit)
+    pub paused: bool,              // Frozen by the authority or program admin
+    pub fee_vault: Pubkey,         // Where rake goes (default = unraked table)
+    pub rake_bps: u16,             // Share of each pot taken as rake
+    pub rake_cap: u64,             // Most rake taken from a single pot (0 = no cap)
+    pub no_flop_no_drop: bool,     // Hands that end preflop aren't raked
+    pub rakeback_bps: u16,         // Share of each player's rake returned to them
+    pub rake_attribution: RakeAttribution,
+    pub hand_history: Pubkey,      // History being recorded for the current hand (default = none)
+    pub turn_started_at: i64,      // Unix timestamp the player to act was given the turn
+}
+
+impl PokerTable {
+    // Public tables are open to anyone; private tables need the player's invite account
+    pub fn can_join(&self, invite: Option<&TableInvite>) -> bool {
+        self.visibility == TableVisibility::Public || invite.is_some()
+    }
+
+    // A seat is dealt in when it's occupied and the player isn't sitting out
+    pub fn is_dealt_in(&self, seat: u8) -> bool {
+        self.players[seat as usize] != Pubkey::default()
+            && self.sitting_out & (1u16 << seat) == 0
+    }
+
+    // Next seat after `from` with a player dealt in
+    pub fn next_dealt_in(&self, from: u8) -> Result<u8> {
+        poker_engine::next_seat(self.max_seats, from, |seat| self.is_dealt_in(seat))
+            .ok_or(PokerError::NotEnoughPlayers.into())
+    }
+
+    // Seats posting the small and big blind. Heads-up, the button posts the small
+    // blind and so acts first preflop.
+    pub fn blind_seats(&self) -> Result<(u8, u8)> {
+        let dealt_in = (0..self.max_seats).filter(|seat| self.is_dealt_in(*seat)).count();
+        let small_blind = if dealt_in == 2 {
+            self.button_position
+        } else {
+            self.next_dealt_in(self.button_position)?
+        };
+        Ok((small_blind, self.next_dealt_in(small_blind)?))
+    }
+
+    // Next occupied seat after `from`, whether or not the player is sitting out
+    pub fn next_occupied(&self, from: u8) -> u8 {
+        poker_engine::next_seat(self.max_seats, from, |seat| {
+            self.players[seat as usize] != Pubkey::default()
+        })
+        .unwrap_or(from)
+    }
+
+    // What the player to act may do. Raise amounts are above the current bet, as
+    // player_action takes them.
+    pub fn legal_actions(&self, player_state: &PlayerState) -> Result<LegalActionsView> {
+        require!(!self.paused, PokerError::TablePaused);
+        require!(
+            self.game_state.street().is_some_and(|street| street != Street::Showdown),
+            PokerError::WrongGameState
+        );
+        require!(
+            self.players[self.current_player_index as usize] == player_state.player,
+            PokerError::NotYourTurn
+        );
+
+        let legal = poker_engine::legal_actions(&self.betting(), &player_state.seat())
+            .map_err(PokerError::from)?;
+        // Owed blinds have to be posted before anything else
+        let missed_blinds_due =
+            (self.missed_small_blinds | self.missed_big_blinds) & (1u16 << player_state.position) != 0;
+        let allowed = !missed_blinds_due;
+
+        Ok(LegalActionsView {
+            player: player_state.player,
+            position: player_state.position,
+            missed_blinds_due,
+            can_fold: allowed,
+            can_check: allowed && legal.can_check,
+            can_call: allowed && legal.can_call(),
+            call_amount: if allowed { legal.call_amount } else { 0 },
+            can_raise: allowed && legal.can_raise(),
+            min_raise: legal.min_raise,
+            max_raise: if allowed { legal.max_raise } else { 0 },
+        })
+    }
+
+    pub fn betting(&self) -> Betting {
+        Betting {
+            pot: self.pot,
+            current_bet: self.current_bet,
+            last_raise_amount: self.last_raise_amount,
+            big_blind: self.big_blind,
+            raises: self.street_bet_count,
+            last_aggressor: self.last_aggressor_index,
+        }
+    }
+
+    pub fn set_betting(&mut self, betting: Betting) {
+        self.pot = betting.pot;
+        self.current_bet = betting.current_bet;
+        self.last_raise_amount = betting.last_raise_amount;
+        self.street_bet_count = betting.raises;
+        self.last_aggressor_index = betting.last_aggressor;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerState {
+    pub player: Pubkey,
+    pub table: Pubkey,
+    pub stack: u64,                      // Current chip stack
+    pub current_bet: u64,                // Amount bet in current round
+    pub position: u8,                    // Seat position (0-8)
+    pub status: PlayerStatus,
+    pub hole_cards_computation: Pubkey,  // TODO: Is this how we interact with the API??
+    pub has_acted_this_street: bool,
+    pub leaving: bool,                   // Forfeited mid-hand; seat is freed when the hand ends
+    pub committed: u64,                  // Chips put into the pot during `committed_hand`
+    pub committed_hand: u64,
+}
+
+impl PlayerState {
+    // Track chips put into the pot this hand so a paused hand can be refunded
+    pub fn commit(&mut self, hand_number: u64, amount: u64) {
+        if self.committed_hand != hand_number {
+            self.committed = 0;
+            self.committed_hand = hand_number;
+        }
+        self.committed += amount;
+    }
+
+    pub fn committed_this_hand(&self, hand_number: u64) -> u64 {
+        if self.committed_hand == hand_number { self.committed } else { 0 }
+    }
+
+    pub fn seat(&self) -> Seat {
+        Seat {
+            stack: self.stack,
+            current_bet: self.current_bet,
+            status: self.status.into(),
+            has_acted: self.has_acted_this_street,
+        }
+    }
+
+    pub fn set_seat(&mut self, seat: Seat) {
+        self.stack = seat.stack;
+        self.current_bet = seat.current_bet;
+        self.status = seat.status.into();
+        self.has_acted_this_street = seat.has_acted;
+    }
+}
+
+// On-chain log of a single hand, in the order things happened
+#[account]
+#[derive(InitSpace)]
+pub struct HandHistory {
+    pub table: Pubkey,
+    pub hand_number: u64,
+    pub bump: u8,
+    pub payer: Pubkey,                   // Gets the rent back when the history is closed
+    #[max_len(MAX_HAND_ACTIONS)]
+    pub actions: Vec<HandAction>,
+    pub truncated: bool,                 // Actions past MAX_HAND_ACTIONS weren't recorded
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum HandAction {
+    Button { seat: u8 },
+    Dealt { seat: u8, stack: u64 },                    // Stack at the start of the hand
+    Ante { seat: u8, amount: u64 },
+    Blind { seat: u8, amount: u64 },
+    MissedBlinds { seat: u8, amount: u64, live: bool },
+    Action { seat: u8, action: PlayerActionType, amount: u64 },  // Amount put in the pot
+    Forfeit { seat: u8 },
+    Street { state: GameState, board: [u8; 5] },
+    Showdown { seat: u8, cards: Option<[u8; 2]> },   // None = hand not revealed
+    Rake { amount: u64 },
+    Payout { seat: u8, amount: u64 },
+    Refund { seat: u8, amount: u64 },
+}
+
+// Cross-table record of a player's rake, shared by every table they play at
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerStats {
+    pub player: Pubkey,
+    pub bump: u8,
+    pub rake_contributed: u64,           // Rake attributed to this player across all tables
+    pub rakeback_earned: u64,            // Lamports moved here from fee vaults as rakeback
+    pub rakeback_claimed: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct FeeVault {
+    pub table: Pubkey,
+    pub bump: u8,
+    pub rake_authority: Pubkey,          // Can withdraw collected rake
+    pub total_collected: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramConfig {
+    pub admin: Pubkey,                   // Can pause and refund any table
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Tournament {
+    pub tournament_id: u64,
+    pub creator: Pubkey,
+    pub bump: u8,
+    #[max_len(MAX_TOURNAMENT_TABLES)]
+    pub tables: Vec<Pubkey>,           // Tables the tournament is played on (one for a sit-and-go)
+    #[max_len(MAX_TOURNAMENT_TABLES)]
+    pub table_counts: Vec<u8>,         // Players seated at each table (0 = table broken)
+    pub seats_per_table: u8,
+    pub status: TournamentStatus,
+    pub entry_fee: u64,                // Lamports per entry, all of it goes to the prize pool
+    pub starting_stack: u64,           // Chips each registrant is seated with
+    pub max_entrants: u8,              // Tournament starts when this many have registered
+    pub prize_pool: u64,
+    #[max_len(MAX_PAYOUT_PLACES)]
+    pub payout_bps: Vec<u16>,          // Share of the prize pool per finishing place, in basis points
+    #[max_len(MAX_TOURNAMENT_ENTRANTS)]
+    pub entries: Vec<TournamentEntry>,
+    pub players_remaining: u8,
+    pub deal_made: bool,               // Remaining players settled the prize pool with a deal
+    pub bounty_type: BountyType,
+    pub bounty_bps: u16,               // Share of each entry fee put on the entrant's head
+    pub rebuy_fee: u64,
+    pub rebuy_chips: u64,
+    pub max_rebuys: u8,                // Per player
+    pub rebuy_period_levels: u8,       // Rebuys allowed while the blind level is below this
+    pub addon_fee: u64,
+    pub addon_chips: u64,              // 0 = no add-on
+    pub blind_level: u8,               // Level every table plays at; the tournament keeps the clock
+    pub level_started_hand: u64,       // Value of hands_dealt when the level started (0 = not started)
+    pub level_started_at: i64,
+    pub hands_dealt: u64,              // Hands dealt across every table, for levels counted in hands
+}
+
+impl Tournament {
+    // Nothing left to play: won outright, settled by a deal, or cancelled before it began
+    pub fn is_over(&self) -> bool {
+        matches!(self.status, TournamentStatus::Finished | TournamentStatus::Cancelled)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Deal {
+    pub tournament: Pubkey,
+    pub bump: u8,
+    pub kind: DealKind,
+    pub proposer: Pubkey,
+    pub prize_pool: u64,               // Lamports still to be decided when the deal was proposed
+    #[max_len(MAX_PLAYERS)]
+    pub shares: Vec<DealShare>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct DealShare {
+    pub player: Pubkey,
+    pub stack: u64,                    // Chip count the deal was computed from
+    pub amount: u64,
+    pub accepted: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum BountyType {
+    None,
+    Standard,
+    Progressive,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum DealKind {
+    Icm,
+    ChipChop,
+}
+
+// One per invited player, so a private table's guest list isn't capped by the table account
+#[account]
+#[derive(InitSpace)]
+pub struct TableInvite {
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Waitlist {
+    pub table: Pubkey,
+    pub bump: u8,
+    #[max_len(MAX_WAITLIST)]
+    pub players: Vec<Pubkey>,          // Front of the queue gets first claim on a freed seat
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BlindStructure {
+    pub table: Pubkey,
+    pub bump: u8,
+    #[max_len(MAX_BLIND_LEVELS)]
+    pub levels: Vec<BlindLevel>,
+    pub duration_unit: LevelDurationUnit,
+    pub level_duration: u64,           // Hands or seconds each level lasts
+    pub current_level: u8,
+    pub level_started_hand: u64,       // First hand_number of the current level (0 = clock not started)
+    pub level_started_at: i64,         // Unix timestamp the current level started
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct BlindLevel {
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub ante: u64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct PlayerState {
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum LevelDurationUnit {
+    Hands,
+    Seconds,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct TournamentEntry {
     pub player: Pubkey,
-    pub table: Pubkey,
-    pub stack: u64,                      // Current chip stack
-    pub current_bet: u64,                // Amount bet in current round
-    pub position: u8,                    // Seat position (0-8)
-    pub status: PlayerStatus,
-    pub hole_cards_computation: Pubkey,  // TODO: Is this how we interact with the API??
-    pub has_acted_this_street: bool,
+    pub table_index: u8,               // Index into Tournament.tables of the player's current table
+    pub finish_place: u8,              // 0 while still in the tournament
+    pub paid: bool,
+    pub deal_payout: Option<u64>,      // Set when the player finished by deal
+    pub bounty: u64,                   // Lamports paid to whoever eliminates this player
+    pub bounties_won: u64,             // Lamports collected for eliminating others
+    pub eliminated_by: Option<Pubkey>,
+    pub busted_at: i64,                // Unix timestamp of the bust that eliminated_by records
+    pub rebuys: u8,
+    pub addon_taken: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum TournamentStatus {
+    Registering,
+    Running,
+    Finished,
+    Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum RakeAttribution {
+    Contributed,  // In proportion to the chips each player put in the pot
+    Dealt,        // Split evenly between everyone dealt in
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum TableVisibility {
+    Public,
+    Private,  // Players holding a TableInvite only
+}
+
+// Fields left as None keep their current value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug)]
+pub struct TableConfigUpdate {
+    pub small_blind: Option<u64>,
+    pub big_blind: Option<u64>,
+    pub min_buy_in: Option<u64>,
+    pub max_buy_in: Option<u64>,
+    pub action_timeout: Option<i64>,
+    pub visibility: Option<TableVisibility>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
@@ -772,14 +4496,63 @@ pub enum GameState {
     HandComplete,
 }
 
+impl GameState {
+    // The betting street, for states in which a hand is being played
+    pub fn street(self) -> Option<Street> {
+        match self {
+            GameState::PreFlop => Some(Street::PreFlop),
+            GameState::Flop => Some(Street::Flop),
+            GameState::Turn => Some(Street::Turn),
+            GameState::River => Some(Street::River),
+            GameState::Showdown => Some(Street::Showdown),
+            GameState::WaitingForPlayers | GameState::HandComplete => None,
+        }
+    }
+}
+
+impl From<Street> for GameState {
+    fn from(street: Street) -> Self {
+        match street {
+            Street::PreFlop => GameState::PreFlop,
+            Street::Flop => GameState::Flop,
+            Street::Turn => GameState::Turn,
+            Street::River => GameState::River,
+            Street::Showdown => GameState::Showdown,
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
 pub enum PlayerStatus {
     Active,
     Folded,
     AllIn,
+    SittingOut,
+}
+
+impl From<PlayerStatus> for SeatStatus {
+    fn from(status: PlayerStatus) -> Self {
+        match status {
+            PlayerStatus::Active => SeatStatus::Active,
+            PlayerStatus::Folded => SeatStatus::Folded,
+            PlayerStatus::AllIn => SeatStatus::AllIn,
+            PlayerStatus::SittingOut => SeatStatus::SittingOut,
+        }
+    }
+}
+
+impl From<SeatStatus> for PlayerStatus {
+    fn from(status: SeatStatus) -> Self {
+        match status {
+            SeatStatus::Active => PlayerStatus::Active,
+            SeatStatus::Folded => PlayerStatus::Folded,
+            SeatStatus::AllIn => PlayerStatus::AllIn,
+            SeatStatus::SittingOut => PlayerStatus::SittingOut,
+        }
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
 pub enum PlayerActionType {
     Fold,
     Check,
@@ -787,10 +4560,63 @@ pub enum PlayerActionType {
     Raise,
 }
 
+// Returned by the legal_actions view
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LegalActionsView {
+    pub player: Pubkey,
+    pub position: u8,
+    pub missed_blinds_due: bool,   // Must post_missed_blinds before acting
+    pub can_fold: bool,
+    pub can_check: bool,
+    pub can_call: bool,
+    pub call_amount: u64,          // Capped at the player's stack
+    pub can_raise: bool,
+    pub min_raise: u64,            // Smallest raise_amount player_action accepts
+    pub max_raise: u64,            // Largest raise_amount the player's stack covers
+}
+
+impl PlayerActionType {
+    pub fn to_action(self, raise_amount: u64) -> Action {
+        match self {
+            PlayerActionType::Fold => Action::Fold,
+            PlayerActionType::Check => Action::Check,
+            PlayerActionType::Call => Action::Call,
+            PlayerActionType::Raise => Action::Raise { amount: raise_amount },
+        }
+    }
+}
+
 #[event]
 pub struct TableCreated {
+    pub table: Pubkey,
     pub table_id: u64,
     pub creator: Pubkey,
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub min_buy_in: u64,
+    pub max_buy_in: u64,
+    pub max_seats: u8,
+    pub visibility: TableVisibility,
+}
+
+#[event]
+pub struct TableClosed {
+    pub table: Pubkey,
+    pub table_id: u64,
+}
+
+#[event]
+pub struct TableAuthorityChanged {
+    pub table: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct InviteUpdated {
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub invited: bool,
 }
 
 #[event]
@@ -798,29 +4624,339 @@ pub struct PlayerJoined {
     pub table: Pubkey,
     pub player: Pubkey,
     pub position: u8,
+    pub stack: u64,
+}
+
+#[event]
+pub struct PlayerLeft {
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub position: u8,
+    pub cashed_out: u64,
+    pub forfeited: bool,    // Stood up mid-hand; the seat is released when the hand ends
+}
+
+#[event]
+pub struct SeatReleased {
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub position: u8,
+}
+
+#[event]
+pub struct PlayerSatOut {
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub sitting_out: bool,
+}
+
+#[event]
+pub struct RakeCollected {
+    pub table: Pubkey,
+    pub hand_number: u64,
+    pub pot: u64,
+    pub rake: u64,
+}
+
+#[event]
+pub struct RakeConfigured {
+    pub table: Pubkey,
+    pub rake_bps: u16,
+    pub rake_cap: u64,
+    pub no_flop_no_drop: bool,
+    pub rake_authority: Pubkey,
+    pub rakeback_bps: u16,
+    pub rake_attribution: RakeAttribution,
+}
+
+#[event]
+pub struct PlayerStatsCreated {
+    pub player: Pubkey,
+    pub player_stats: Pubkey,
+}
+
+#[event]
+pub struct RakeWithdrawn {
+    pub table: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RakebackClaimed {
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TablePaused {
+    pub table: Pubkey,
+    pub paused: bool,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct HandRefunded {
+    pub table: Pubkey,
+    pub hand_number: u64,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct TableConfigUpdated {
+    pub table: Pubkey,
+    pub authority: Pubkey,
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub min_buy_in: u64,
+    pub max_buy_in: u64,
+    pub action_timeout: i64,
+    pub visibility: TableVisibility,
+}
+
+#[event]
+pub struct WaitlistUpdated {
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub joined: bool,
+    pub waiting: u8,
+}
+
+#[event]
+pub struct MissedBlindsPosted {
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub position: u8,
+    pub dead: u64,
+    pub live: u64,
+}
+
+#[event]
+pub struct PlayerToppedUp {
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub stack: u64,
+}
+
+#[event]
+pub struct HandHistoryOpened {
+    pub table: Pubkey,
+    pub hand_history: Pubkey,
+    pub hand_number: u64,
+}
+
+#[event]
+pub struct HandHistoryClosed {
+    pub table: Pubkey,
+    pub hand_history: Pubkey,
+    pub hand_number: u64,
 }
 
 #[event]
 pub struct HandStarted {
     pub table: Pubkey,
     pub hand_number: u64,
+    pub button_position: u8,
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub ante: u64,
+    pub pot: u64,           // Antes already collected
+}
+
+#[event]
+pub struct AntePosted {
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub position: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct BlindPosted {
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub position: u8,
+    pub amount: u64,
+    pub pot: u64,
 }
 
 #[event]
 pub struct PlayerActioned {
+    pub table: Pubkey,
     pub player: Pubkey,
+    pub position: u8,
     pub action: PlayerActionType,
-    pub amount: u64,
+    pub amount: u64,        // Chips put into the pot by this action
+    pub stack: u64,
+    pub current_bet: u64,
+    pub pot: u64,
+}
+
+#[event]
+pub struct StreetAdvanced {
+    pub table: Pubkey,
+    pub hand_number: u64,
+    pub street: GameState,
+    pub board: [u8; 5],
+    pub pot: u64,
 }
 
 #[event]
 pub struct HandComplete {
     pub table: Pubkey,
+    pub hand_number: u64,
     pub winner: Pubkey,
-    pub pot: u64,
+    pub position: u8,
+    pub pot: u64,           // Paid to the winner, after rake
+    pub rake: u64,
+}
+
+#[event]
+pub struct BlindsIncreased {
+    pub table: Pubkey,
+    pub level: u8,
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub ante: u64,
+}
+
+#[event]
+pub struct PayoutStructureSet {
+    pub tournament: Pubkey,
+    pub payout_bps: Vec<u16>,
+}
+
+#[event]
+pub struct TournamentBountySet {
+    pub tournament: Pubkey,
+    pub bounty_type: BountyType,
+    pub bounty_bps: u16,
+}
+
+#[event]
+pub struct TournamentRebuysSet {
+    pub tournament: Pubkey,
+    pub rebuy_fee: u64,
+    pub rebuy_chips: u64,
+    pub max_rebuys: u8,
+    pub rebuy_period_levels: u8,
+    pub addon_fee: u64,
+    pub addon_chips: u64,
+}
+
+#[event]
+pub struct TournamentStarted {
+    pub tournament: Pubkey,
+    pub tables: u8,
+    pub entrants: u8,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct TournamentUnregistered {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub refund: u64,
+}
+
+#[event]
+pub struct TournamentCancelled {
+    pub tournament: Pubkey,
+    pub entries_refunded: u8,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct PlayerEliminated {
+    pub tournament: Pubkey,
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub finish_place: u8,
+}
+
+#[event]
+pub struct TournamentTableAdded {
+    pub tournament: Pubkey,
+    pub table: Pubkey,
+}
+
+#[event]
+pub struct TournamentPlayerMoved {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub from_table: Pubkey,
+    pub to_table: Pubkey,
+    pub position: u8,
+    pub stack: u64,
+}
+
+#[event]
+pub struct BountyCollected {
+    pub tournament: Pubkey,
+    pub eliminator: Pubkey,
+    pub eliminated: Pubkey,
+    pub paid: u64,
+    pub added_to_bounty: u64,
+}
+
+#[event]
+pub struct TournamentPurchased {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub addon: bool,
+    pub fee: u64,
+    pub chips: u64,
+}
+
+#[event]
+pub struct DealProposed {
+    pub tournament: Pubkey,
+    pub proposer: Pubkey,
+    pub kind: DealKind,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct DealAccepted {
+    pub tournament: Pubkey,
+    pub kind: DealKind,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct DealRejected {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+}
+
+#[event]
+pub struct TournamentFinished {
+    pub tournament: Pubkey,
+    pub winner: Pubkey,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct TournamentPayoutClaimed {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub finish_place: u8,
+    pub amount: u64,
 }
 
 
+impl From<RuleError> for PokerError {
+    fn from(error: RuleError) -> Self {
+        match error {
+            RuleError::PlayerNotActive => PokerError::PlayerNotActive,
+            RuleError::CannotCheck => PokerError::CannotCheck,
+            RuleError::InsufficientFunds => PokerError::InsufficientFunds,
+            RuleError::RaiseTooSmall => PokerError::RaiseTooSmall,
+        }
+    }
+}
+
 #[error_code]
 pub enum PokerError {
     #[msg("Table is full")]
@@ -861,4 +4997,122 @@ pub enum PokerError {
     AlreadyPostedBlind,
     #[msg("Betting round is not complete")]
     BettingRoundNotComplete,
+    #[msg("Player is not sitting out")]
+    NotSittingOut,
+    #[msg("Missed blinds must be posted first")]
+    MissedBlindsDue,
+    #[msg("Player has no missed blinds")]
+    NoMissedBlinds,
+    #[msg("Table is run by a tournament")]
+    TournamentTable,
+    #[msg("Table is not available for a tournament")]
+    TableNotAvailable,
+    #[msg("Invalid tournament configuration")]
+    InvalidTournamentConfig,
+    #[msg("Payout percentages must cover the prize pool exactly")]
+    InvalidPayoutStructure,
+    #[msg("Tournament registration is closed")]
+    RegistrationClosed,
+    #[msg("Player is already registered")]
+    AlreadyRegistered,
+    #[msg("Player is not registered in this tournament")]
+    NotRegistered,
+    #[msg("Tournament account missing from remaining accounts")]
+    MissingTournamentAccount,
+    #[msg("Blind structure account missing")]
+    MissingBlindStructureAccount,
+    #[msg("Invalid blind structure")]
+    InvalidBlindStructure,
+    #[msg("Player state accounts are required")]
+    MissingPlayerAccounts,
+    #[msg("Table does not belong to this tournament")]
+    NotTournamentTable,
+    #[msg("Tournament already has the maximum number of tables")]
+    TooManyTables,
+    #[msg("Move would not balance the tables")]
+    TableNotBalanced,
+    #[msg("Player must move to a different table")]
+    InvalidTableMove,
+    #[msg("Tournament is not running")]
+    TournamentNotRunning,
+    #[msg("Tournament is not finished")]
+    TournamentNotFinished,
+    #[msg("Busted players must be eliminated first")]
+    EliminationPending,
+    #[msg("Player still has chips")]
+    PlayerNotBusted,
+    #[msg("Payout already claimed")]
+    PayoutAlreadyClaimed,
+    #[msg("No payout for this finishing place")]
+    NoPayoutDue,
+    #[msg("Bust must be recorded by the hand that caused it before elimination")]
+    BustNotRecorded,
+    #[msg("Rebuy period is over")]
+    RebuyPeriodOver,
+    #[msg("Stack is above the starting stack")]
+    StackTooLargeToRebuy,
+    #[msg("Rebuy limit reached")]
+    RebuyLimitReached,
+    #[msg("Add-on is not available")]
+    AddonNotAvailable,
+    #[msg("Deals are only allowed at the final table")]
+    DealNotAllowed,
+    #[msg("Player is not part of the deal")]
+    NotInDeal,
+    #[msg("Chip counts changed since the deal was proposed")]
+    DealStale,
+    #[msg("Player is already seated at this table")]
+    AlreadySeated,
+    #[msg("Player is already on the waitlist")]
+    AlreadyWaitlisted,
+    #[msg("Player is not on the waitlist")]
+    NotWaitlisted,
+    #[msg("Waitlist is full")]
+    WaitlistFull,
+    #[msg("Table waitlist account must be provided")]
+    MissingWaitlistAccount,
+    #[msg("Seat is reserved for the first player on the waitlist")]
+    SeatReserved,
+    #[msg("Table is private and the player is not invited")]
+    NotInvited,
+    #[msg("Invalid table configuration")]
+    InvalidTableConfig,
+    #[msg("Tables must have between 2 and 10 seats")]
+    InvalidSeatCount,
+    #[msg("Table still has players or chips in the pot")]
+    TableNotEmpty,
+    #[msg("Signer is not the table authority or program admin")]
+    Unauthorized,
+    #[msg("Table is paused")]
+    TablePaused,
+    #[msg("Table must be paused first")]
+    TableNotPaused,
+    #[msg("Committed chips don't add up to the pot")]
+    RefundMismatch,
+    #[msg("Table fee vault account must be provided")]
+    MissingFeeVaultAccount,
+    #[msg("Collected rake must be withdrawn first")]
+    RakeNotWithdrawn,
+    #[msg("Player stats account must be provided for rake attribution")]
+    MissingPlayerStatsAccount,
+    #[msg("No rakeback to claim")]
+    NothingToClaim,
+    #[msg("Hand history account must be provided")]
+    MissingHandHistoryAccount,
+    #[msg("Player still has time to act")]
+    ActionNotTimedOut,
+    #[msg("Too many players left for an ICM deal")]
+    TooManyForIcm,
+    #[msg("Tournament tables don't seat every entrant")]
+    NotEnoughSeats,
+    #[msg("Busted player can still rebuy")]
+    RebuyStillAvailable,
+    #[msg("Table has a free seat")]
+    TableNotFull,
+    #[msg("Player account passed more than once")]
+    DuplicatePlayerAccount,
+    #[msg("Account that paid the rent must be provided")]
+    MissingRentPayerAccount,
+    #[msg("Rake authority can only be set when the fee vault is created")]
+    RakeAuthorityLocked,
 }
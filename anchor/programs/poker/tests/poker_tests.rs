@@ -1,4 +1,5 @@
-use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
+use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize, Event};
+use base64::Engine;
 use anchor_litesvm::{LiteSVM, TransactionHelpers};
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
@@ -9,14 +10,238 @@ use solana_sdk::{
 
 // Import poker program types
 use poker::{
-    GameState, PlayerActionType, PlayerState, PlayerStatus, PokerTable,
-    SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT, MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT, ID as POKER_PROGRAM_ID,
+    BlindLevel, BlindStructure, BountyType, Deal, DealKind, FeeVault, GameState, HandAction, HandHistory, LegalActionsView, LevelDurationUnit, PlayerActionType, PlayerState, PlayerStatus, PokerTable, Tournament,
+    AntePosted, BlindPosted, DealRejected, HandComplete, HandHistoryClosed, HandStarted, PayoutStructureSet, PlayerActioned, PlayerJoined,
+    PlayerStatsCreated, StreetAdvanced, TableCreated, TournamentBountySet, TournamentRebuysSet,
+    PlayerStats, RakeAttribution, TableConfigUpdate, TableInvite, TableVisibility, TournamentStatus, Waitlist, MAX_ACTION_TIMEOUT, MAX_ICM_DEAL_PLAYERS, MAX_PLAYERS, MAX_SEATS_DEFAULT,
+    REBUY_DECISION_SECONDS, SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT, MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT, ID as POKER_PROGRAM_ID,
 };
 
 const SOL: u64 = 1_000_000_000;
 
 // HELPERS
 
+/// Decode every event of type T from a transaction's logs
+fn decode_events<T: Event>(logs: &[String]) -> Vec<T> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|data| base64::engine::general_purpose::STANDARD.decode(data).ok())
+        .filter(|data| data.starts_with(T::DISCRIMINATOR))
+        .filter_map(|data| T::deserialize(&mut &data[T::DISCRIMINATOR.len()..]).ok())
+        .collect()
+}
+
+/// Fresh VM with the poker program loaded
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(POKER_PROGRAM_ID, "../../target/deploy/poker.so").unwrap();
+    svm
+}
+
+/// New keypair funded with 10 SOL
+fn funded_keypair(svm: &mut LiteSVM) -> Keypair {
+    let keypair = Keypair::new();
+    svm.airdrop(&keypair.pubkey(), 10 * SOL).unwrap();
+    keypair
+}
+
+/// Decode an Anchor account, skipping its discriminator
+fn read_account<T: AnchorDeserialize>(svm: &LiteSVM, address: &Pubkey) -> T {
+    let account = svm.get_account(address).unwrap();
+    AnchorDeserialize::deserialize(&mut &account.data[8..]).unwrap()
+}
+
+/// A table with default blinds and one player seated per buy-in, in seat order.
+/// Returns the creator, the table and each player with their state PDA.
+fn setup_table(svm: &mut LiteSVM, buy_ins: &[u64]) -> (Keypair, Pubkey, Vec<(Keypair, Pubkey)>) {
+    let creator = funded_keypair(svm);
+    let (table_pda, _) = derive_table_pda(1);
+    svm.send_instruction(build_initialize_table_ix(
+        table_pda, &creator, 1,
+        SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT,
+        MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT,
+    ), &[&creator]).unwrap().assert_success();
+
+    let mut players = Vec::new();
+    for (seat, buy_in) in buy_ins.iter().enumerate() {
+        let player = funded_keypair(svm);
+        let (player_pda, _) = derive_player_pda(&table_pda, &player.pubkey());
+        svm.send_instruction(
+            build_join_table_ix(table_pda, player_pda, &player, *buy_in, seat as u8),
+            &[&player],
+        ).unwrap().assert_success();
+        players.push((player, player_pda));
+    }
+    (creator, table_pda, players)
+}
+
+/// Post both blinds for the hand just dealt
+fn post_blinds(svm: &mut LiteSVM, table: Pubkey, players: &[(Keypair, Pubkey)]) {
+    let table_state: PokerTable = read_account(svm, &table);
+    let (small_blind, big_blind) = table_state.blind_seats().unwrap();
+    for seat in [small_blind, big_blind] {
+        let (player, player_pda) = &players[seat as usize];
+        svm.send_instruction(build_post_blinds_ix(table, *player_pda, player), &[player]).unwrap().assert_success();
+    }
+}
+
+/// Act for whichever player's turn it is
+fn act(
+    svm: &mut LiteSVM,
+    table: Pubkey,
+    players: &[(Keypair, Pubkey)],
+    action: PlayerActionType,
+    raise_amount: u64,
+) -> std::result::Result<(), String> {
+    let table_state: PokerTable = read_account(svm, &table);
+    let (player, player_pda) = &players[table_state.current_player_index as usize];
+    svm.send_instruction(build_player_action_ix(table, *player_pda, player, action, raise_amount), &[player])
+        .map(|_| ())
+        .map_err(|err| format!("{:?}", err))
+}
+
+/// Raise that puts the player to act all in
+fn all_in_raise(svm: &LiteSVM, table: Pubkey, players: &[(Keypair, Pubkey)]) -> u64 {
+    let table_state: PokerTable = read_account(svm, &table);
+    let state: PlayerState = read_account(svm, &players[table_state.current_player_index as usize].1);
+    state.stack + state.current_bet - table_state.current_bet
+}
+
+/// A sit-and-go on table 1 with 10/20 blinds for ten hands, 1 SOL entry and 1500 chips
+struct SitAndGo {
+    creator: Keypair,
+    table: Pubkey,
+    blind_structure: Pubkey,
+    tournament: Pubkey,
+    /// Registered players with their state PDAs, in seat order
+    players: Vec<(Keypair, Pubkey)>,
+}
+
+impl SitAndGo {
+    fn create(svm: &mut LiteSVM, max_entrants: u8, payout_bps: Vec<u16>) -> SitAndGo {
+        let creator = funded_keypair(svm);
+        let (table, _) = derive_table_pda(1);
+        let (blind_structure, _) = derive_blind_structure_pda(&table);
+        let (tournament, _) = derive_tournament_pda(1);
+
+        svm.send_instruction(build_initialize_table_ix(
+            table, &creator, 1,
+            SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT,
+            MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT,
+        ), &[&creator]).unwrap().assert_success();
+        svm.send_instruction(build_set_blind_structure_ix(
+            table, blind_structure, &creator,
+            vec![
+                BlindLevel { small_blind: 10, big_blind: 20, ante: 0 },
+                BlindLevel { small_blind: 20, big_blind: 40, ante: 0 },
+            ],
+            LevelDurationUnit::Hands,
+            10,
+        ), &[&creator]).unwrap().assert_success();
+        svm.send_instruction(build_create_tournament_ix(
+            tournament, table, &creator, 1,
+            SOL, 1500, max_entrants,
+            payout_bps,
+        ), &[&creator]).unwrap().assert_success();
+
+        SitAndGo { creator, table, blind_structure, tournament, players: Vec::new() }
+    }
+
+    /// Register a new funded player
+    fn register(&mut self, svm: &mut LiteSVM) -> std::result::Result<(), String> {
+        let player = funded_keypair(svm);
+        let (player_pda, _) = derive_player_pda(&self.table, &player.pubkey());
+        svm.send_instruction(
+            build_register_tournament_ix(self.tournament, self.table, player_pda, &player),
+            &[&player],
+        ).map_err(|err| format!("{:?}", err))?;
+        self.players.push((player, player_pda));
+        Ok(())
+    }
+
+    fn register_all(&mut self, svm: &mut LiteSVM, count: usize) {
+        for _ in 0..count {
+            self.register(svm).unwrap();
+        }
+    }
+
+    fn player_states(&self) -> Vec<Pubkey> {
+        self.players.iter().map(|(_, player_pda)| *player_pda).collect()
+    }
+
+    fn start_hand(&self, svm: &mut LiteSVM) -> std::result::Result<(), String> {
+        let mut remaining = vec![self.tournament, self.blind_structure];
+        remaining.extend(self.player_states());
+        svm.send_instruction(build_start_hand_with_accounts_ix(self.table, remaining), &[&self.creator])
+            .map(|_| ())
+            .map_err(|err| format!("{:?}", err))
+    }
+
+    /// The player to act shoves, the other calls, and `winner` takes the pot at showdown
+    fn play_all_in_hand(&self, svm: &mut LiteSVM, winner: usize) {
+        self.all_in_to_showdown(svm);
+        let mut remaining = vec![self.tournament];
+        remaining.extend(self.player_states());
+        svm.send_instruction(
+            build_end_hand_with_accounts_ix(self.table, self.players[winner].1, winner as u8, remaining),
+            &[&self.creator],
+        ).unwrap().assert_success();
+    }
+
+    /// Deal a hand where the first to act shoves and is called, and run it to showdown
+    fn all_in_to_showdown(&self, svm: &mut LiteSVM) {
+        self.start_hand(svm).unwrap();
+        post_blinds(svm, self.table, &self.players);
+        let shove = all_in_raise(svm, self.table, &self.players);
+        act(svm, self.table, &self.players, PlayerActionType::Raise, shove).unwrap();
+        act(svm, self.table, &self.players, PlayerActionType::Call, 0).unwrap();
+        for _ in 0..4 {
+            svm.send_instruction(build_advance_street_ix(self.table), &[&self.creator]).unwrap();
+            svm.expire_blockhash();
+        }
+    }
+
+    /// check_auto_win with the tournament and every player's state
+    fn check_auto_win(&self, svm: &mut LiteSVM) -> std::result::Result<(), String> {
+        let mut remaining = vec![self.tournament];
+        remaining.extend(self.player_states());
+        svm.send_instruction(build_check_auto_win_ix(self.table, remaining), &[&self.creator])
+            .map(|_| ())
+            .map_err(|err| format!("{:?}", err))
+    }
+
+    fn eliminate(&self, svm: &mut LiteSVM, index: usize) -> std::result::Result<(), String> {
+        let (player, player_pda) = &self.players[index];
+        svm.send_instruction(
+            build_eliminate_player_ix(self.tournament, self.table, *player_pda, player.pubkey()),
+            &[&self.creator],
+        ).map(|_| ()).map_err(|err| format!("{:?}", err))
+    }
+
+    fn state(&self, svm: &LiteSVM) -> Tournament {
+        read_account(svm, &self.tournament)
+    }
+
+    /// The table followed by every player's state, as deals read them
+    fn deal_accounts(&self) -> Vec<Pubkey> {
+        let mut accounts = vec![self.table];
+        accounts.extend(self.player_states());
+        accounts
+    }
+
+    fn propose_deal(&self, svm: &mut LiteSVM, proposer: usize, kind: DealKind) -> std::result::Result<(), String> {
+        let (player, _) = &self.players[proposer];
+        svm.send_instruction(
+            build_propose_deal_ix(self.tournament, player, kind, self.deal_accounts()),
+            &[player],
+        ).map(|_| ()).map_err(|err| format!("{:?}", err))
+    }
+
+    fn deal(&self, svm: &LiteSVM) -> Deal {
+        read_account(svm, &derive_deal_pda(&self.tournament).0)
+    }
+}
+
 /// Derive table PDA
 fn derive_table_pda(table_id: u64) -> (Pubkey, u8) {
     Pubkey::find_program_address(
@@ -26,6 +251,13 @@ fn derive_table_pda(table_id: u64) -> (Pubkey, u8) {
 }
 
 /// Derive player state PDA
+fn derive_invite_pda(table: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"invite", table.as_ref(), player.as_ref()],
+        &POKER_PROGRAM_ID,
+    )
+}
+
 fn derive_player_pda(table: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[b"player", table.as_ref(), player.as_ref()],
@@ -42,6 +274,27 @@ fn build_initialize_table_ix(
     big_blind: u64,
     min_buy_in: u64,
     max_buy_in: u64,
+) -> Instruction {
+    build_initialize_table_with_options_ix(
+        table, creator, table_id,
+        small_blind, big_blind,
+        min_buy_in, max_buy_in,
+        MAX_SEATS_DEFAULT, TableVisibility::Public,
+    )
+}
+
+/// Build initialize_table instruction with a seat count and visibility
+#[allow(clippy::too_many_arguments)]
+fn build_initialize_table_with_options_ix(
+    table: Pubkey,
+    creator: &Keypair,
+    table_id: u64,
+    small_blind: u64,
+    big_blind: u64,
+    min_buy_in: u64,
+    max_buy_in: u64,
+    max_seats: u8,
+    visibility: TableVisibility,
 ) -> Instruction {
     #[derive(AnchorSerialize)]
     struct InitializeTableArgs {
@@ -50,6 +303,8 @@ fn build_initialize_table_ix(
         big_blind: u64,
         min_buy_in: u64,
         max_buy_in: u64,
+        max_seats: u8,
+        visibility: TableVisibility,
     }
 
     let discriminator = anchor_lang::solana_program::hash::hash(b"global:initialize_table")
@@ -62,6 +317,8 @@ fn build_initialize_table_ix(
         big_blind,
         min_buy_in,
         max_buy_in,
+        max_seats,
+        visibility,
     };
 
     let mut data = discriminator;
@@ -85,6 +342,18 @@ fn build_join_table_ix(
     player: &Keypair,
     buy_in_amount: u64,
     position: u8,
+) -> Instruction {
+    build_join_table_with_invite_ix(table, player_state, player, buy_in_amount, position, None)
+}
+
+/// Build join_table instruction carrying the player's invite to a private table
+fn build_join_table_with_invite_ix(
+    table: Pubkey,
+    player_state: Pubkey,
+    player: &Keypair,
+    buy_in_amount: u64,
+    position: u8,
+    invite: Option<Pubkey>,
 ) -> Instruction {
     #[derive(AnchorSerialize)]
     struct JoinTableArgs {
@@ -109,6 +378,7 @@ fn build_join_table_ix(
         accounts: vec![
             AccountMeta::new(table, false),
             AccountMeta::new(player_state, false),
+            AccountMeta::new_readonly(invite.unwrap_or(POKER_PROGRAM_ID), false),
             AccountMeta::new(player.pubkey(), true),
             AccountMeta::new_readonly(system_program::ID, false),
         ],
@@ -116,89 +386,85 @@ fn build_join_table_ix(
     }
 }
 
-/// Build start_hand instruction
-fn build_start_hand_ix(table: Pubkey, _signer: &Keypair) -> Instruction {
-    let discriminator = anchor_lang::solana_program::hash::hash(b"global:start_hand")
+/// Build invite_player instruction
+fn build_invite_player_ix(table: Pubkey, authority: &Keypair, player: Pubkey) -> Instruction {
+    let mut data = anchor_lang::solana_program::hash::hash(b"global:invite_player")
         .to_bytes()[..8]
         .to_vec();
+    player.serialize(&mut data).unwrap();
 
     Instruction {
         program_id: POKER_PROGRAM_ID,
-        accounts: vec![AccountMeta::new(table, false)],
-        data: discriminator,
+        accounts: vec![
+            AccountMeta::new_readonly(table, false),
+            AccountMeta::new(derive_invite_pda(&table, &player).0, false),
+            AccountMeta::new(authority.pubkey(), true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
     }
 }
 
-/// Build player_action instruction
-fn build_player_action_ix(
-    table: Pubkey,
-    player_state: Pubkey,
-    player: &Keypair,
-    action: PlayerActionType,
-    raise_amount: u64,
-) -> Instruction {
-    #[derive(AnchorSerialize)]
-    struct PlayerActionArgs {
-        action: PlayerActionType,
-        raise_amount: u64,
-    }
-
-    let discriminator = anchor_lang::solana_program::hash::hash(b"global:player_action")
+/// Build revoke_invite instruction
+fn build_revoke_invite_ix(table: Pubkey, authority: &Keypair, player: Pubkey) -> Instruction {
+    let data = anchor_lang::solana_program::hash::hash(b"global:revoke_invite")
         .to_bytes()[..8]
         .to_vec();
 
-    let args = PlayerActionArgs {
-        action,
-        raise_amount,
-    };
-
-    let mut data = discriminator;
-    args.serialize(&mut data).unwrap();
-
     Instruction {
         program_id: POKER_PROGRAM_ID,
         accounts: vec![
-            AccountMeta::new(table, false),
-            AccountMeta::new(player_state, false),
-            AccountMeta::new_readonly(player.pubkey(), true),
+            AccountMeta::new_readonly(table, false),
+            AccountMeta::new(derive_invite_pda(&table, &player).0, false),
+            AccountMeta::new(authority.pubkey(), true),
         ],
         data,
     }
 }
 
-/// Build post_blinds instruction
-fn build_post_blinds_ix(
-    table: Pubkey,
-    player_state: Pubkey,
-    player: &Keypair,
-) -> Instruction {
-    let discriminator = anchor_lang::solana_program::hash::hash(b"global:post_blinds")
+/// Build update_table_config instruction
+fn build_update_table_config_ix(table: Pubkey, authority: &Keypair, update: TableConfigUpdate) -> Instruction {
+    let mut data = anchor_lang::solana_program::hash::hash(b"global:update_table_config")
         .to_bytes()[..8]
         .to_vec();
+    update.serialize(&mut data).unwrap();
 
     Instruction {
         program_id: POKER_PROGRAM_ID,
         accounts: vec![
             AccountMeta::new(table, false),
-            AccountMeta::new(player_state, false),
-            AccountMeta::new_readonly(player.pubkey(), true),
+            AccountMeta::new_readonly(authority.pubkey(), true),
         ],
-        data: discriminator,
+        data,
     }
 }
 
-/// Build advance_street_auto instruction
-fn build_advance_street_auto_ix(
+/// Build set_table_authority instruction
+fn build_set_table_authority_ix(table: Pubkey, authority: &Keypair, new_authority: Pubkey) -> Instruction {
+    let mut ix = build_update_table_config_ix(table, authority, TableConfigUpdate::default());
+    ix.data = anchor_lang::solana_program::hash::hash(b"global:set_table_authority")
+        .to_bytes()[..8]
+        .to_vec();
+    new_authority.serialize(&mut ix.data).unwrap();
+    ix
+}
+
+/// Build close_table instruction
+fn build_close_table_ix(
     table: Pubkey,
+    creator: Pubkey,
+    authority: &Keypair,
     remaining_accounts: Vec<Pubkey>,
 ) -> Instruction {
-    let discriminator = anchor_lang::solana_program::hash::hash(b"global:advance_street_auto")
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:close_table")
         .to_bytes()[..8]
         .to_vec();
 
-    let mut accounts = vec![AccountMeta::new(table, false)];
-
-    // Add remaining_accounts as writable
+    let mut accounts = vec![
+        AccountMeta::new(table, false),
+        AccountMeta::new(creator, false),
+        AccountMeta::new(authority.pubkey(), true),
+    ];
     for account in remaining_accounts {
         accounts.push(AccountMeta::new(account, false));
     }
@@ -210,18 +476,25 @@ fn build_advance_street_auto_ix(
     }
 }
 
-/// Build check_auto_win instruction
-fn build_check_auto_win_ix(
+/// Build set_table_paused or emergency_refund instruction (signed by the table authority,
+/// so the optional program config is left out)
+fn build_table_admin_ix(
+    name: &str,
     table: Pubkey,
+    signer: &Keypair,
+    args: Vec<u8>,
     remaining_accounts: Vec<Pubkey>,
 ) -> Instruction {
-    let discriminator = anchor_lang::solana_program::hash::hash(b"global:check_auto_win")
+    let mut data = anchor_lang::solana_program::hash::hash(format!("global:{}", name).as_bytes())
         .to_bytes()[..8]
         .to_vec();
+    data.extend(args);
 
-    let mut accounts = vec![AccountMeta::new(table, false)];
-
-    // Add remaining_accounts as writable
+    let mut accounts = vec![
+        AccountMeta::new(table, false),
+        AccountMeta::new_readonly(POKER_PROGRAM_ID, false),
+        AccountMeta::new_readonly(signer.pubkey(), true),
+    ];
     for account in remaining_accounts {
         accounts.push(AccountMeta::new(account, false));
     }
@@ -229,93 +502,957 @@ fn build_check_auto_win_ix(
     Instruction {
         program_id: POKER_PROGRAM_ID,
         accounts,
-        data: discriminator,
+        data,
     }
 }
 
-// ========== TABLE MANAGEMENT TESTS ==========
-
-#[test]
-fn test_initialize_table() {
-    let mut svm = LiteSVM::new();
-    svm.add_program_from_file(
-        POKER_PROGRAM_ID,
-        "../../target/deploy/poker.so",
-    ).unwrap();
-
-    // Create funded creator
-    let creator = Keypair::new();
-    svm.airdrop(&creator.pubkey(), 10 * SOL).unwrap();
+/// Derive fee vault PDA
+fn derive_fee_vault_pda(table: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fee_vault", table.as_ref()], &POKER_PROGRAM_ID)
+}
 
-    // Derive table PDA
-    let table_id = 1u64;
-    let (table_pda, _bump) = derive_table_pda(table_id);
+/// Build set_rake instruction
+#[allow(clippy::too_many_arguments)]
+fn build_set_rake_ix(
+    table: Pubkey,
+    authority: &Keypair,
+    rake_bps: u16,
+    rake_cap: u64,
+    no_flop_no_drop: bool,
+    rake_authority: Pubkey,
+    rakeback_bps: u16,
+    rake_attribution: RakeAttribution,
+) -> Instruction {
+    let mut data = anchor_lang::solana_program::hash::hash(b"global:set_rake")
+        .to_bytes()[..8]
+        .to_vec();
+    (rake_bps, rake_cap, no_flop_no_drop, rake_authority, rakeback_bps, rake_attribution)
+        .serialize(&mut data)
+        .unwrap();
 
-    // Build and send instruction
-    let ix = build_initialize_table_ix(
-        table_pda,
-        &creator,
-        table_id,
-        SMALL_BLIND_DEFAULT,
-        BIG_BLIND_DEFAULT,
-        MIN_BUY_IN_DEFAULT,
-        MAX_BUY_IN_DEFAULT,
-    );
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(table, false),
+            AccountMeta::new(derive_fee_vault_pda(&table).0, false),
+            AccountMeta::new(authority.pubkey(), true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
 
-    let tx_result = svm.send_instruction(ix, &[&creator]).unwrap();
-    tx_result.assert_success();
+/// Build withdraw_rake instruction
+fn build_withdraw_rake_ix(table: Pubkey, rake_authority: &Keypair, destination: Pubkey, amount: u64) -> Instruction {
+    let mut data = anchor_lang::solana_program::hash::hash(b"global:withdraw_rake")
+        .to_bytes()[..8]
+        .to_vec();
+    amount.serialize(&mut data).unwrap();
 
-    // Verify table state
-    let table_account = svm.get_account(&table_pda)
-        .expect("Table account should exist after initialization");
-    let table_data = &table_account.data[8..]; // Skip discriminator
-    let table: PokerTable = AnchorDeserialize::deserialize(&mut &table_data[..]).unwrap();
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(derive_fee_vault_pda(&table).0, false),
+            AccountMeta::new_readonly(rake_authority.pubkey(), true),
+            AccountMeta::new(destination, false),
+        ],
+        data,
+    }
+}
 
-    assert_eq!(table.table_id, table_id);
-    assert_eq!(table.creator, creator.pubkey());
-    assert_eq!(table.player_count, 0);
-    assert_eq!(table.game_state, GameState::WaitingForPlayers);
-    assert_eq!(table.small_blind, SMALL_BLIND_DEFAULT);
-    assert_eq!(table.big_blind, BIG_BLIND_DEFAULT);
+/// Derive player stats PDA
+fn derive_player_stats_pda(player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"player_stats", player.as_ref()], &POKER_PROGRAM_ID)
 }
 
-#[test]
-fn test_join_table_success() {
-    let mut svm = anchor_litesvm::LiteSVM::new();
-    svm.add_program_from_file(
-        POKER_PROGRAM_ID,
-        "../../target/deploy/poker.so",
-    ).unwrap();
+/// Build create_player_stats or claim_rakeback instruction
+fn build_player_stats_ix(name: &str, player: &Keypair) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(format!("global:{}", name).as_bytes())
+        .to_bytes()[..8]
+        .to_vec();
 
-    // Setup: Create table
-    let creator = Keypair::new();
-    svm.airdrop(&creator.pubkey(), 10 * SOL).unwrap();
+    let mut accounts = vec![
+        AccountMeta::new(derive_player_stats_pda(&player.pubkey()).0, false),
+        AccountMeta::new(player.pubkey(), true),
+    ];
+    if name == "create_player_stats" {
+        accounts.push(AccountMeta::new_readonly(system_program::ID, false));
+    }
 
-    let table_id = 1u64;
-    let (table_pda, _) = derive_table_pda(table_id);
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts,
+        data: discriminator,
+    }
+}
 
-    let init_ix = build_initialize_table_ix(
-        table_pda,
-        &creator,
-        table_id,
-        SMALL_BLIND_DEFAULT,
-        BIG_BLIND_DEFAULT,
-        MIN_BUY_IN_DEFAULT,
-        MAX_BUY_IN_DEFAULT,
-    );
-    svm.send_instruction(init_ix, &[&creator]).unwrap();
+/// Build legal_actions instruction for the player whose turn it is
+fn build_legal_actions_ix(table: Pubkey, player_state: Pubkey) -> Instruction {
+    let data = anchor_lang::solana_program::hash::hash(b"global:legal_actions")
+        .to_bytes()[..8]
+        .to_vec();
 
-    // Join table
-    let player = Keypair::new();
-    svm.airdrop(&player.pubkey(), 10 * SOL).unwrap();
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(table, false),
+            AccountMeta::new_readonly(player_state, false),
+        ],
+        data,
+    }
+}
 
-    let (player_pda, _) = derive_player_pda(&table_pda, &player.pubkey());
-    let buy_in = 5000u64;
-    let position = 0u8;
+fn build_crank_timeout_ix(table: Pubkey, player_state: Pubkey, player_states: Vec<Pubkey>) -> Instruction {
+    let data = anchor_lang::solana_program::hash::hash(b"global:crank_timeout")
+        .to_bytes()[..8]
+        .to_vec();
 
-    let join_ix = build_join_table_ix(table_pda, player_pda, &player, buy_in, position);
-    let result = svm.send_instruction(join_ix, &[&player]).unwrap();
-    result.assert_success();
+    let mut accounts = vec![
+        AccountMeta::new(table, false),
+        AccountMeta::new(player_state, false),
+    ];
+    accounts.extend(player_states.into_iter().map(|pda| AccountMeta::new_readonly(pda, false)));
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
+/// Derive hand history PDA
+fn derive_hand_history_pda(table: &Pubkey, hand_number: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"hand_history", table.as_ref(), hand_number.to_le_bytes().as_ref()],
+        &POKER_PROGRAM_ID,
+    )
+}
+
+/// Build open_hand_history or close_hand_history instruction
+fn build_hand_history_ix(name: &str, table: Pubkey, signer: &Keypair, hand_number: u64) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(format!("global:{}", name).as_bytes())
+        .to_bytes()[..8]
+        .to_vec();
+
+    let mut accounts = vec![
+        AccountMeta::new(table, false),
+        AccountMeta::new(derive_hand_history_pda(&table, hand_number).0, false),
+        AccountMeta::new(signer.pubkey(), true),
+    ];
+    if name == "open_hand_history" {
+        accounts.push(AccountMeta::new_readonly(system_program::ID, false));
+    }
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts,
+        data: discriminator,
+    }
+}
+
+/// Derive waitlist PDA
+fn derive_waitlist_pda(table: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"waitlist", table.as_ref()], &POKER_PROGRAM_ID)
+}
+
+/// Build join_waitlist instruction, with the player's invite for a private table
+fn build_join_waitlist_ix(table: Pubkey, player: &Keypair, invite: Option<Pubkey>) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:join_waitlist")
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(table, false),
+            AccountMeta::new(derive_waitlist_pda(&table).0, false),
+            AccountMeta::new_readonly(invite.unwrap_or(POKER_PROGRAM_ID), false),
+            AccountMeta::new(player.pubkey(), true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: discriminator,
+    }
+}
+
+/// Build sit_out or sit_in instruction
+fn build_sit_ix(name: &str, table: Pubkey, player: &Keypair) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(format!("global:{}", name).as_bytes())
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(table, false),
+            AccountMeta::new(derive_player_pda(&table, &player.pubkey()).0, false),
+            AccountMeta::new_readonly(player.pubkey(), true),
+        ],
+        data: discriminator,
+    }
+}
+
+/// Build post_missed_blinds instruction
+fn build_post_missed_blinds_ix(table: Pubkey, player: &Keypair, live: bool) -> Instruction {
+    let mut ix = build_post_blinds_ix(table, derive_player_pda(&table, &player.pubkey()).0, player);
+    ix.data = anchor_lang::solana_program::hash::hash(b"global:post_missed_blinds")
+        .to_bytes()[..8]
+        .to_vec();
+    live.serialize(&mut ix.data).unwrap();
+    ix
+}
+
+/// Build top_up instruction
+fn build_top_up_ix(
+    table: Pubkey,
+    player_state: Pubkey,
+    player: &Keypair,
+    amount: u64,
+) -> Instruction {
+    let mut data = anchor_lang::solana_program::hash::hash(b"global:top_up")
+        .to_bytes()[..8]
+        .to_vec();
+    amount.serialize(&mut data).unwrap();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(table, false),
+            AccountMeta::new(player_state, false),
+            AccountMeta::new(player.pubkey(), true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// Build leave_table instruction
+fn build_leave_table_ix(table: Pubkey, player_state: Pubkey, player: &Keypair) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:leave_table")
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(table, false),
+            AccountMeta::new(player_state, false),
+            AccountMeta::new(player.pubkey(), true),
+        ],
+        data: discriminator,
+    }
+}
+
+/// Build start_hand instruction
+fn build_start_hand_ix(table: Pubkey, _signer: &Keypair) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:start_hand")
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![AccountMeta::new(table, false)],
+        data: discriminator,
+    }
+}
+
+/// Build player_action instruction
+fn build_player_action_ix(
+    table: Pubkey,
+    player_state: Pubkey,
+    player: &Keypair,
+    action: PlayerActionType,
+    raise_amount: u64,
+) -> Instruction {
+    #[derive(AnchorSerialize)]
+    struct PlayerActionArgs {
+        action: PlayerActionType,
+        raise_amount: u64,
+    }
+
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:player_action")
+        .to_bytes()[..8]
+        .to_vec();
+
+    let args = PlayerActionArgs {
+        action,
+        raise_amount,
+    };
+
+    let mut data = discriminator;
+    args.serialize(&mut data).unwrap();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(table, false),
+            AccountMeta::new(player_state, false),
+            AccountMeta::new_readonly(player.pubkey(), true),
+        ],
+        data,
+    }
+}
+
+/// Build post_blinds instruction
+fn build_post_blinds_ix(
+    table: Pubkey,
+    player_state: Pubkey,
+    player: &Keypair,
+) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:post_blinds")
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(table, false),
+            AccountMeta::new(player_state, false),
+            AccountMeta::new_readonly(player.pubkey(), true),
+        ],
+        data: discriminator,
+    }
+}
+
+/// Build advance_street_auto instruction
+fn build_advance_street_auto_ix(
+    table: Pubkey,
+    remaining_accounts: Vec<Pubkey>,
+) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:advance_street_auto")
+        .to_bytes()[..8]
+        .to_vec();
+
+    let mut accounts = vec![AccountMeta::new(table, false)];
+
+    // Add remaining_accounts as writable
+    for account in remaining_accounts {
+        accounts.push(AccountMeta::new(account, false));
+    }
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts,
+        data: discriminator,
+    }
+}
+
+/// Build check_auto_win instruction
+fn build_check_auto_win_ix(
+    table: Pubkey,
+    remaining_accounts: Vec<Pubkey>,
+) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:check_auto_win")
+        .to_bytes()[..8]
+        .to_vec();
+
+    let mut accounts = vec![AccountMeta::new(table, false)];
+
+    // Add remaining_accounts as writable
+    for account in remaining_accounts {
+        accounts.push(AccountMeta::new(account, false));
+    }
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts,
+        data: discriminator,
+    }
+}
+
+/// Derive tournament PDA
+fn derive_tournament_pda(tournament_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"tournament", &tournament_id.to_le_bytes()],
+        &POKER_PROGRAM_ID,
+    )
+}
+
+/// Build start_hand instruction with player states (and tournament) as remaining_accounts
+fn build_start_hand_with_accounts_ix(
+    table: Pubkey,
+    remaining_accounts: Vec<Pubkey>,
+) -> Instruction {
+    let mut ix = build_start_hand_ix(table, &Keypair::new());
+    for account in remaining_accounts {
+        ix.accounts.push(AccountMeta::new(account, false));
+    }
+    ix
+}
+
+/// Build advance_street instruction
+fn build_advance_street_ix(table: Pubkey) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:advance_street")
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![AccountMeta::new(table, false)],
+        data: discriminator,
+    }
+}
+
+/// Build end_hand instruction
+fn build_end_hand_ix(table: Pubkey, winner_state: Pubkey, winner_position: u8) -> Instruction {
+    let mut data = anchor_lang::solana_program::hash::hash(b"global:end_hand")
+        .to_bytes()[..8]
+        .to_vec();
+    winner_position.serialize(&mut data).unwrap();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(table, false),
+            AccountMeta::new(winner_state, false),
+        ],
+        data,
+    }
+}
+
+/// Derive blind structure PDA
+fn derive_blind_structure_pda(table: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"blind_structure", table.as_ref()],
+        &POKER_PROGRAM_ID,
+    )
+}
+
+/// Build set_blind_structure instruction
+fn build_set_blind_structure_ix(
+    table: Pubkey,
+    blind_structure: Pubkey,
+    creator: &Keypair,
+    levels: Vec<BlindLevel>,
+    duration_unit: LevelDurationUnit,
+    level_duration: u64,
+) -> Instruction {
+    #[derive(AnchorSerialize)]
+    struct SetBlindStructureArgs {
+        levels: Vec<BlindLevel>,
+        duration_unit: LevelDurationUnit,
+        level_duration: u64,
+    }
+
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:set_blind_structure")
+        .to_bytes()[..8]
+        .to_vec();
+
+    let args = SetBlindStructureArgs {
+        levels,
+        duration_unit,
+        level_duration,
+    };
+
+    let mut data = discriminator;
+    args.serialize(&mut data).unwrap();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(table, false),
+            AccountMeta::new(blind_structure, false),
+            AccountMeta::new(creator.pubkey(), true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+fn build_remove_blind_structure_ix(table: Pubkey, blind_structure: Pubkey, creator: &Keypair) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:remove_blind_structure")
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(table, false),
+            AccountMeta::new(blind_structure, false),
+            AccountMeta::new(creator.pubkey(), true),
+        ],
+        data: discriminator,
+    }
+}
+
+/// Build end_hand instruction with the tournament and player states as remaining_accounts
+fn build_end_hand_with_accounts_ix(
+    table: Pubkey,
+    winner_state: Pubkey,
+    winner_position: u8,
+    remaining_accounts: Vec<Pubkey>,
+) -> Instruction {
+    let mut ix = build_end_hand_ix(table, winner_state, winner_position);
+    for account in remaining_accounts {
+        ix.accounts.push(AccountMeta::new(account, false));
+    }
+    ix
+}
+
+/// Build set_payout_structure instruction
+fn build_set_payout_structure_ix(tournament: Pubkey, creator: &Keypair, payout_bps: Vec<u16>) -> Instruction {
+    let mut data = anchor_lang::solana_program::hash::hash(b"global:set_payout_structure")
+        .to_bytes()[..8]
+        .to_vec();
+    payout_bps.serialize(&mut data).unwrap();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tournament, false),
+            AccountMeta::new_readonly(creator.pubkey(), true),
+        ],
+        data,
+    }
+}
+
+/// Build set_tournament_bounty instruction
+fn build_set_tournament_bounty_ix(
+    tournament: Pubkey,
+    creator: &Keypair,
+    bounty_type: BountyType,
+    bounty_bps: u16,
+) -> Instruction {
+    let mut data = anchor_lang::solana_program::hash::hash(b"global:set_tournament_bounty")
+        .to_bytes()[..8]
+        .to_vec();
+    bounty_type.serialize(&mut data).unwrap();
+    bounty_bps.serialize(&mut data).unwrap();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tournament, false),
+            AccountMeta::new_readonly(creator.pubkey(), true),
+        ],
+        data,
+    }
+}
+
+/// Build set_tournament_rebuys instruction
+#[allow(clippy::too_many_arguments)]
+fn build_set_tournament_rebuys_ix(
+    tournament: Pubkey,
+    creator: &Keypair,
+    rebuy_fee: u64,
+    rebuy_chips: u64,
+    max_rebuys: u8,
+    rebuy_period_levels: u8,
+    addon_fee: u64,
+    addon_chips: u64,
+) -> Instruction {
+    let mut data = anchor_lang::solana_program::hash::hash(b"global:set_tournament_rebuys")
+        .to_bytes()[..8]
+        .to_vec();
+    (rebuy_fee, rebuy_chips, max_rebuys, rebuy_period_levels, addon_fee, addon_chips)
+        .serialize(&mut data)
+        .unwrap();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tournament, false),
+            AccountMeta::new_readonly(creator.pubkey(), true),
+        ],
+        data,
+    }
+}
+
+/// Build rebuy (or addon) instruction
+fn build_tournament_purchase_ix(
+    name: &str,
+    tournament: Pubkey,
+    table: Pubkey,
+    player: &Keypair,
+) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(format!("global:{}", name).as_bytes())
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tournament, false),
+            AccountMeta::new_readonly(table, false),
+            AccountMeta::new(derive_player_pda(&table, &player.pubkey()).0, false),
+            AccountMeta::new(player.pubkey(), true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: discriminator,
+    }
+}
+
+/// Build create_tournament instruction
+#[allow(clippy::too_many_arguments)]
+fn build_create_tournament_ix(
+    tournament: Pubkey,
+    table: Pubkey,
+    authority: &Keypair,
+    tournament_id: u64,
+    entry_fee: u64,
+    starting_stack: u64,
+    max_entrants: u8,
+    payout_bps: Vec<u16>,
+) -> Instruction {
+    #[derive(AnchorSerialize)]
+    struct CreateTournamentArgs {
+        tournament_id: u64,
+        entry_fee: u64,
+        starting_stack: u64,
+        max_entrants: u8,
+        payout_bps: Vec<u16>,
+    }
+
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:create_tournament")
+        .to_bytes()[..8]
+        .to_vec();
+
+    let args = CreateTournamentArgs {
+        tournament_id,
+        entry_fee,
+        starting_stack,
+        max_entrants,
+        payout_bps,
+    };
+
+    let mut data = discriminator;
+    args.serialize(&mut data).unwrap();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tournament, false),
+            AccountMeta::new(table, false),
+            AccountMeta::new(authority.pubkey(), true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// Build register_tournament instruction
+fn build_register_tournament_ix(
+    tournament: Pubkey,
+    table: Pubkey,
+    player_state: Pubkey,
+    player: &Keypair,
+) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:register_tournament")
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tournament, false),
+            AccountMeta::new(table, false),
+            AccountMeta::new(player_state, false),
+            AccountMeta::new(player.pubkey(), true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: discriminator,
+    }
+}
+
+/// Build add_tournament_table instruction
+fn build_add_tournament_table_ix(
+    tournament: Pubkey,
+    first_table: Pubkey,
+    table: Pubkey,
+    creator: &Keypair,
+    authority: &Keypair,
+) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:add_tournament_table")
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tournament, false),
+            AccountMeta::new(table, false),
+            AccountMeta::new_readonly(derive_blind_structure_pda(&table).0, false),
+            AccountMeta::new_readonly(derive_blind_structure_pda(&first_table).0, false),
+            AccountMeta::new_readonly(creator.pubkey(), true),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+        ],
+        data: discriminator,
+    }
+}
+
+/// Build move_tournament_player instruction
+fn build_move_tournament_player_ix(
+    tournament: Pubkey,
+    from_table: Pubkey,
+    to_table: Pubkey,
+    player: Pubkey,
+    payer: &Keypair,
+) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:move_tournament_player")
+        .to_bytes()[..8]
+        .to_vec();
+
+    let (from_player_state, _) = derive_player_pda(&from_table, &player);
+    let (to_player_state, _) = derive_player_pda(&to_table, &player);
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tournament, false),
+            AccountMeta::new(from_table, false),
+            AccountMeta::new(to_table, false),
+            AccountMeta::new(from_player_state, false),
+            AccountMeta::new(to_player_state, false),
+            AccountMeta::new_readonly(POKER_PROGRAM_ID, false),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: discriminator,
+    }
+}
+
+/// Build unregister_tournament instruction
+fn build_unregister_tournament_ix(tournament: Pubkey, table: Pubkey, player: &Keypair) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:unregister_tournament")
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tournament, false),
+            AccountMeta::new(table, false),
+            AccountMeta::new(derive_player_pda(&table, &player.pubkey()).0, false),
+            AccountMeta::new(player.pubkey(), true),
+        ],
+        data: discriminator,
+    }
+}
+
+/// Build cancel_tournament instruction (signed by the creator, so the optional
+/// program config is left out) refunding each registrant's wallet
+fn build_cancel_tournament_ix(tournament: Pubkey, signer: &Keypair, wallets: Vec<Pubkey>) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:cancel_tournament")
+        .to_bytes()[..8]
+        .to_vec();
+
+    let mut accounts = vec![
+        AccountMeta::new(tournament, false),
+        AccountMeta::new_readonly(POKER_PROGRAM_ID, false),
+        AccountMeta::new_readonly(signer.pubkey(), true),
+    ];
+    accounts.extend(wallets.into_iter().map(|wallet| AccountMeta::new(wallet, false)));
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts,
+        data: discriminator,
+    }
+}
+
+/// Build eliminate_player instruction
+fn build_eliminate_player_ix(
+    tournament: Pubkey,
+    table: Pubkey,
+    player_state: Pubkey,
+    player: Pubkey,
+) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:eliminate_player")
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tournament, false),
+            AccountMeta::new(table, false),
+            AccountMeta::new(player_state, false),
+            AccountMeta::new(player, false),
+        ],
+        data: discriminator,
+    }
+}
+
+/// Derive deal PDA
+fn derive_deal_pda(tournament: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"deal", tournament.as_ref()],
+        &POKER_PROGRAM_ID,
+    )
+}
+
+/// Build propose_deal instruction
+fn build_propose_deal_ix(
+    tournament: Pubkey,
+    proposer: &Keypair,
+    kind: DealKind,
+    remaining_accounts: Vec<Pubkey>,
+) -> Instruction {
+    let mut data = anchor_lang::solana_program::hash::hash(b"global:propose_deal")
+        .to_bytes()[..8]
+        .to_vec();
+    kind.serialize(&mut data).unwrap();
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(tournament, false),
+        AccountMeta::new(derive_deal_pda(&tournament).0, false),
+        AccountMeta::new(proposer.pubkey(), true),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    for account in remaining_accounts {
+        accounts.push(AccountMeta::new_readonly(account, false));
+    }
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
+/// Build accept_deal instruction
+fn build_accept_deal_ix(
+    tournament: Pubkey,
+    proposer: Pubkey,
+    player: &Keypair,
+    remaining_accounts: Vec<Pubkey>,
+) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:accept_deal")
+        .to_bytes()[..8]
+        .to_vec();
+
+    let mut accounts = vec![
+        AccountMeta::new(tournament, false),
+        AccountMeta::new(derive_deal_pda(&tournament).0, false),
+        AccountMeta::new(proposer, false),
+        AccountMeta::new_readonly(player.pubkey(), true),
+    ];
+    for account in remaining_accounts {
+        accounts.push(AccountMeta::new_readonly(account, false));
+    }
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts,
+        data: discriminator,
+    }
+}
+
+/// Build reject_deal instruction
+fn build_reject_deal_ix(tournament: Pubkey, proposer: Pubkey, player: &Keypair) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:reject_deal")
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(derive_deal_pda(&tournament).0, false),
+            AccountMeta::new(proposer, false),
+            AccountMeta::new_readonly(player.pubkey(), true),
+        ],
+        data: discriminator,
+    }
+}
+
+/// Build claim_tournament_payout instruction
+fn build_claim_tournament_payout_ix(tournament: Pubkey, player: &Keypair) -> Instruction {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"global:claim_tournament_payout")
+        .to_bytes()[..8]
+        .to_vec();
+
+    Instruction {
+        program_id: POKER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tournament, false),
+            AccountMeta::new(player.pubkey(), true),
+        ],
+        data: discriminator,
+    }
+}
+
+// ========== TABLE MANAGEMENT TESTS ==========
+
+#[test]
+fn test_initialize_table() {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(
+        POKER_PROGRAM_ID,
+        "../../target/deploy/poker.so",
+    ).unwrap();
+
+    // Create funded creator
+    let creator = Keypair::new();
+    svm.airdrop(&creator.pubkey(), 10 * SOL).unwrap();
+
+    // Derive table PDA
+    let table_id = 1u64;
+    let (table_pda, _bump) = derive_table_pda(table_id);
+
+    // Build and send instruction
+    let ix = build_initialize_table_ix(
+        table_pda,
+        &creator,
+        table_id,
+        SMALL_BLIND_DEFAULT,
+        BIG_BLIND_DEFAULT,
+        MIN_BUY_IN_DEFAULT,
+        MAX_BUY_IN_DEFAULT,
+    );
+
+    let tx_result = svm.send_instruction(ix, &[&creator]).unwrap();
+    tx_result.assert_success();
+
+    // Verify table state
+    let table_account = svm.get_account(&table_pda)
+        .expect("Table account should exist after initialization");
+    let table_data = &table_account.data[8..]; // Skip discriminator
+    let table: PokerTable = AnchorDeserialize::deserialize(&mut &table_data[..]).unwrap();
+
+    assert_eq!(table.table_id, table_id);
+    assert_eq!(table.creator, creator.pubkey());
+    assert_eq!(table.player_count, 0);
+    assert_eq!(table.game_state, GameState::WaitingForPlayers);
+    assert_eq!(table.small_blind, SMALL_BLIND_DEFAULT);
+    assert_eq!(table.big_blind, BIG_BLIND_DEFAULT);
+}
+
+#[test]
+fn test_join_table_success() {
+    let mut svm = anchor_litesvm::LiteSVM::new();
+    svm.add_program_from_file(
+        POKER_PROGRAM_ID,
+        "../../target/deploy/poker.so",
+    ).unwrap();
+
+    // Setup: Create table
+    let creator = Keypair::new();
+    svm.airdrop(&creator.pubkey(), 10 * SOL).unwrap();
+
+    let table_id = 1u64;
+    let (table_pda, _) = derive_table_pda(table_id);
+
+    let init_ix = build_initialize_table_ix(
+        table_pda,
+        &creator,
+        table_id,
+        SMALL_BLIND_DEFAULT,
+        BIG_BLIND_DEFAULT,
+        MIN_BUY_IN_DEFAULT,
+        MAX_BUY_IN_DEFAULT,
+    );
+    svm.send_instruction(init_ix, &[&creator]).unwrap();
+
+    // Join table
+    let player = Keypair::new();
+    svm.airdrop(&player.pubkey(), 10 * SOL).unwrap();
+
+    let (player_pda, _) = derive_player_pda(&table_pda, &player.pubkey());
+    let buy_in = 5000u64;
+    let position = 0u8;
+
+    let join_ix = build_join_table_ix(table_pda, player_pda, &player, buy_in, position);
+    let result = svm.send_instruction(join_ix, &[&player]).unwrap();
+    result.assert_success();
 
     // Verify player state
     let player_account = svm.get_account(&player_pda)
@@ -323,150 +1460,1515 @@ fn test_join_table_success() {
     let player_data = &player_account.data[8..];
     let player_state: PlayerState = AnchorDeserialize::deserialize(&mut &player_data[..]).unwrap();
 
-    assert_eq!(player_state.player, player.pubkey());
-    assert_eq!(player_state.stack, buy_in);
-    assert_eq!(player_state.position, position);
-    assert_eq!(player_state.status, PlayerStatus::Active);
+    assert_eq!(player_state.player, player.pubkey());
+    assert_eq!(player_state.stack, buy_in);
+    assert_eq!(player_state.position, position);
+    assert_eq!(player_state.status, PlayerStatus::Active);
+
+    // Verify table updated
+    let table_account = svm.get_account(&table_pda).unwrap();
+    let table_data = &table_account.data[8..];
+    let table: PokerTable = AnchorDeserialize::deserialize(&mut &table_data[..]).unwrap();
+
+    assert_eq!(table.player_count, 1);
+    assert_eq!(table.players[position as usize], player.pubkey());
+}
+
+#[test]
+fn test_start_hand_with_two_players() {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(
+        POKER_PROGRAM_ID,
+        "../../target/deploy/poker.so",
+    ).unwrap();
+
+    // Setup: Create table and add 2 players
+    let creator = Keypair::new();
+    svm.airdrop(&creator.pubkey(), 10 * SOL).unwrap();
+
+    let table_id = 1u64;
+    let (table_pda, _) = derive_table_pda(table_id);
+
+    // Initialize table
+    let init_ix = build_initialize_table_ix(
+        table_pda,
+        &creator,
+        table_id,
+        SMALL_BLIND_DEFAULT,
+        BIG_BLIND_DEFAULT,
+        MIN_BUY_IN_DEFAULT,
+        MAX_BUY_IN_DEFAULT,
+    );
+    svm.send_instruction(init_ix, &[&creator]).unwrap();
+
+    // Add two players
+    let player1 = Keypair::new();
+    let player2 = Keypair::new();
+    svm.airdrop(&player1.pubkey(), 10 * SOL).unwrap();
+    svm.airdrop(&player2.pubkey(), 10 * SOL).unwrap();
+
+    let (player1_pda, _) = derive_player_pda(&table_pda, &player1.pubkey());
+    let (player2_pda, _) = derive_player_pda(&table_pda, &player2.pubkey());
+
+    let join1_ix = build_join_table_ix(table_pda, player1_pda, &player1, 5000, 0);
+    let join2_ix = build_join_table_ix(table_pda, player2_pda, &player2, 5000, 1);
+    svm.send_instruction(join1_ix, &[&player1]).unwrap();
+    svm.send_instruction(join2_ix, &[&player2]).unwrap();
+
+    // Start hand (pass creator as dummy signer for LiteSVM)
+    let start_ix = build_start_hand_ix(table_pda, &creator);
+    let result = svm.send_instruction(start_ix, &[&creator]).unwrap();
+    result.assert_success();
+
+    // Verify game state changed
+    let table_account = svm.get_account(&table_pda).unwrap();
+    let table_data = &table_account.data[8..];
+    let table: PokerTable = AnchorDeserialize::deserialize(&mut &table_data[..]).unwrap();
+
+    assert_eq!(table.game_state, GameState::PreFlop);
+    assert_eq!(table.hand_number, 1);
+    assert_eq!(table.current_bet, BIG_BLIND_DEFAULT);
+}
+
+#[test]
+fn test_player_fold() {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(
+        POKER_PROGRAM_ID,
+        "../../target/deploy/poker.so",
+    ).unwrap();
+
+    // Setup table with 2 players and start hand
+    let creator = Keypair::new();
+    svm.airdrop(&creator.pubkey(), 10 * SOL).unwrap();
+
+    let table_id = 1u64;
+    let (table_pda, _) = derive_table_pda(table_id);
+
+    let init_ix = build_initialize_table_ix(
+        table_pda,
+        &creator,
+        table_id,
+        SMALL_BLIND_DEFAULT,
+        BIG_BLIND_DEFAULT,
+        MIN_BUY_IN_DEFAULT,
+        MAX_BUY_IN_DEFAULT,
+    );
+    svm.send_instruction(init_ix, &[&creator]).unwrap();
+
+    let player1 = Keypair::new();
+    let player2 = Keypair::new();
+    svm.airdrop(&player1.pubkey(), 10 * SOL).unwrap();
+    svm.airdrop(&player2.pubkey(), 10 * SOL).unwrap();
+
+    let (player1_pda, _) = derive_player_pda(&table_pda, &player1.pubkey());
+    let (player2_pda, _) = derive_player_pda(&table_pda, &player2.pubkey());
+
+    let join1_ix = build_join_table_ix(table_pda, player1_pda, &player1, 5000, 0);
+    let join2_ix = build_join_table_ix(table_pda, player2_pda, &player2, 5000, 1);
+    svm.send_instruction(join1_ix, &[&player1]).unwrap();
+    svm.send_instruction(join2_ix, &[&player2]).unwrap();
+
+    let start_ix = build_start_hand_ix(table_pda, &creator);
+    svm.send_instruction(start_ix, &[&creator]).unwrap();
+
+    // Determine who acts first
+    let table_account = svm.get_account(&table_pda).unwrap();
+    let table_data = &table_account.data[8..];
+    let table: PokerTable = AnchorDeserialize::deserialize(&mut &table_data[..]).unwrap();
+
+    let current_player_pubkey = table.players[table.current_player_index as usize];
+    let (acting_player, acting_player_pda) = if current_player_pubkey == player1.pubkey() {
+        (&player1, player1_pda)
+    } else {
+        (&player2, player2_pda)
+    };
+
+    // Player folds
+    let fold_ix = build_player_action_ix(
+        table_pda,
+        acting_player_pda,
+        acting_player,
+        PlayerActionType::Fold,
+        0,
+    );
+    let result = svm.send_instruction(fold_ix, &[acting_player]).unwrap();
+    result.assert_success();
+
+    // Verify player status changed
+    let player_account = svm.get_account(&acting_player_pda).unwrap();
+    let player_data = &player_account.data[8..];
+    let player_state: PlayerState = AnchorDeserialize::deserialize(&mut &player_data[..]).unwrap();
+
+    assert_eq!(player_state.status, PlayerStatus::Folded);
+}
+
+#[test]
+fn test_buy_ins_and_top_ups_are_escrowed_by_the_table() {
+    let mut svm = setup_svm();
+    let (_, table_pda, players) = setup_table(&mut svm, &[5000, 5000]);
+    let table_size = svm.get_account(&table_pda).unwrap().data.len();
+    let rent = svm.minimum_balance_for_rent_exemption(table_size);
+    assert_eq!(svm.get_balance(&table_pda).unwrap(), rent + 10_000);
+
+    let (player1, player1_pda) = &players[0];
+    svm.send_instruction(build_top_up_ix(table_pda, *player1_pda, player1, 3000), &[player1]).unwrap().assert_success();
+    assert_eq!(read_account::<PlayerState>(&svm, player1_pda).stack, 8000);
+    assert_eq!(svm.get_balance(&table_pda).unwrap(), rent + 13_000);
+}
+
+#[test]
+fn test_top_up_cannot_exceed_max_buy_in() {
+    let mut svm = setup_svm();
+    let (_, table_pda, players) = setup_table(&mut svm, &[8000, 5000]);
+    let (player1, player1_pda) = &players[0];
+
+    // Even by overflowing the stack
+    for amount in [MAX_BUY_IN_DEFAULT - 8000 + 1, u64::MAX] {
+        let err = svm.send_instruction(build_top_up_ix(table_pda, *player1_pda, player1, amount), &[player1]).unwrap_err();
+        assert!(format!("{:?}", err).contains("InvalidBuyIn"));
+    }
+    assert_eq!(read_account::<PlayerState>(&svm, player1_pda).stack, 8000);
+}
+
+#[test]
+fn test_top_up_rejected_while_a_hand_is_running() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = setup_table(&mut svm, &[5000, 5000]);
+    svm.send_instruction(build_start_hand_ix(table_pda, &creator), &[&creator]).unwrap().assert_success();
+
+    let (player2, player2_pda) = &players[1];
+    let err = svm.send_instruction(build_top_up_ix(table_pda, *player2_pda, player2, 1000), &[player2]).unwrap_err();
+    assert!(format!("{:?}", err).contains("GameInProgress"));
+}
+
+// Three 5000 stacks with player3 sitting out the first hand, which has been started.
+// Seat 2 would have been the small blind.
+fn hand_with_player_sitting_out(svm: &mut LiteSVM) -> (Keypair, Pubkey, Vec<(Keypair, Pubkey)>) {
+    let (creator, table_pda, players) = setup_table(svm, &[5000, 5000, 5000]);
+    let player3 = &players[2].0;
+    svm.send_instruction(build_sit_ix("sit_out", table_pda, player3), &[player3]).unwrap().assert_success();
+    let player_pdas = players.iter().map(|(_, pda)| *pda).collect();
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, player_pdas), &[&creator]).unwrap().assert_success();
+    (creator, table_pda, players)
+}
+
+#[test]
+fn test_sitting_out_player_is_dealt_out_and_owes_the_missed_blind() {
+    let mut svm = setup_svm();
+    let (_, table_pda, players) = hand_with_player_sitting_out(&mut svm);
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!(table.button_position, 1);
+    assert_eq!(table.sitting_out, 1u16 << 2);
+    assert_eq!(table.missed_small_blinds, 1u16 << 2);
+    assert_eq!(read_account::<PlayerState>(&svm, &players[2].1).status, PlayerStatus::SittingOut);
+}
+
+#[test]
+fn test_blinds_skip_a_player_sitting_out() {
+    let mut svm = setup_svm();
+    let (_, table_pda, players) = hand_with_player_sitting_out(&mut svm);
+
+    // The hand is heads-up: the button (seat 1) posts small, seat 0 posts big
+    assert_eq!(read_account::<PokerTable>(&svm, &table_pda).blind_seats().unwrap(), (1, 0));
+    post_blinds(&mut svm, table_pda, &players);
+    assert_eq!(read_account::<PlayerState>(&svm, &players[1].1).current_bet, SMALL_BLIND_DEFAULT);
+    assert_eq!(read_account::<PlayerState>(&svm, &players[0].1).current_bet, BIG_BLIND_DEFAULT);
+}
+
+#[test]
+fn test_sitting_in_waits_for_the_hand_to_end() {
+    let mut svm = setup_svm();
+    let (_, table_pda, players) = hand_with_player_sitting_out(&mut svm);
+    let player3 = &players[2].0;
+    let err = svm.send_instruction(build_sit_ix("sit_in", table_pda, player3), &[player3]).unwrap_err();
+    assert!(format!("{:?}", err).contains("GameInProgress"));
+}
+
+#[test]
+fn test_returning_player_posts_the_missed_blind_dead() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = hand_with_player_sitting_out(&mut svm);
+    post_blinds(&mut svm, table_pda, &players);
+    act(&mut svm, table_pda, &players, PlayerActionType::Fold, 0).unwrap();
+    svm.send_instruction(build_end_hand_ix(table_pda, players[0].1, 0), &[&creator]).unwrap().assert_success();
+
+    let (player3, player3_pda) = &players[2];
+    svm.send_instruction(build_sit_ix("sit_in", table_pda, player3), &[player3]).unwrap().assert_success();
+    let player_pdas = players.iter().map(|(_, pda)| *pda).collect();
+    svm.expire_blockhash();
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, player_pdas), &[&creator]).unwrap().assert_success();
+    svm.send_instruction(build_post_missed_blinds_ix(table_pda, player3, false), &[player3]).unwrap().assert_success();
+
+    // Dead money goes in the pot without counting toward the player's bet
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!((table.sitting_out, table.missed_small_blinds), (0, 0));
+    assert_eq!(table.pot, SMALL_BLIND_DEFAULT);
+    let p3_state: PlayerState = read_account(&svm, player3_pda);
+    assert_eq!((p3_state.stack, p3_state.current_bet), (5000 - SMALL_BLIND_DEFAULT, 0));
+}
+
+// ========== NEW GAME LOGIC TESTS ==========
+
+// Three 5000 stacks with a hand started and player2 standing up from it
+fn hand_with_player_leaving(svm: &mut LiteSVM) -> (Keypair, Pubkey, Vec<(Keypair, Pubkey)>) {
+    let (creator, table_pda, players) = setup_table(svm, &[5000, 5000, 5000]);
+    let player_pdas = players.iter().map(|(_, pda)| *pda).collect();
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, player_pdas), &[&creator]).unwrap().assert_success();
+    let (player2, player2_pda) = &players[1];
+    svm.send_instruction(build_leave_table_ix(table_pda, *player2_pda, player2), &[player2]).unwrap().assert_success();
+    (creator, table_pda, players)
+}
+
+#[test]
+fn test_leaving_mid_hand_folds_and_cashes_out_the_stack() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = setup_table(&mut svm, &[5000, 5000, 5000]);
+    let player_pdas = players.iter().map(|(_, pda)| *pda).collect();
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, player_pdas), &[&creator]).unwrap().assert_success();
+    let table_lamports = svm.get_balance(&table_pda).unwrap();
+
+    let (player2, player2_pda) = &players[1];
+    svm.send_instruction(build_leave_table_ix(table_pda, *player2_pda, player2), &[player2]).unwrap().assert_success();
+    assert_eq!(svm.get_balance(&table_pda).unwrap(), table_lamports - 5000);
+    let p2_state: PlayerState = read_account(&svm, player2_pda);
+    assert_eq!((p2_state.status, p2_state.stack), (PlayerStatus::Folded, 0));
+    assert!(p2_state.leaving);
+}
+
+#[test]
+fn test_leaving_mid_hand_holds_the_seat_until_the_hand_ends() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = hand_with_player_leaving(&mut svm);
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!(table.players[1], players[1].0.pubkey());
+    assert_eq!(table.leaving, 1u16 << 1);
+
+    svm.send_instruction(build_end_hand_ix(table_pda, players[0].1, 0), &[&creator]).unwrap().assert_success();
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!(table.players[1], Pubkey::default());
+    assert_eq!((table.player_count, table.leaving), (2, 0));
+}
+
+#[test]
+fn test_forfeited_player_closes_their_account_after_the_hand() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = hand_with_player_leaving(&mut svm);
+    let (player2, player2_pda) = &players[1];
+
+    svm.expire_blockhash();
+    let err = svm.send_instruction(build_leave_table_ix(table_pda, *player2_pda, player2), &[player2]).unwrap_err();
+    assert!(format!("{:?}", err).contains("CannotLeaveNow"));
+
+    svm.send_instruction(build_end_hand_ix(table_pda, players[0].1, 0), &[&creator]).unwrap().assert_success();
+    svm.expire_blockhash();
+    svm.send_instruction(build_leave_table_ix(table_pda, *player2_pda, player2), &[player2]).unwrap().assert_success();
+    assert!(svm.get_account(player2_pda).is_none_or(|account| account.lamports == 0));
+}
+
+#[test]
+fn test_waitlist_holds_freed_seat_for_first_in_queue() {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(POKER_PROGRAM_ID, "../../target/deploy/poker.so").unwrap();
+
+    let creator = Keypair::new();
+    svm.airdrop(&creator.pubkey(), 10 * SOL).unwrap();
+
+    let table_id = 1u64;
+    let (table_pda, _) = derive_table_pda(table_id);
+    let (waitlist_pda, _) = derive_waitlist_pda(&table_pda);
+    svm.send_instruction(build_initialize_table_ix(
+        table_pda, &creator, table_id,
+        SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT,
+        MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT,
+    ), &[&creator]).unwrap();
+
+    let players: Vec<Keypair> = (0..MAX_SEATS_DEFAULT).map(|_| Keypair::new()).collect();
+    for (seat, player) in players.iter().enumerate() {
+        svm.airdrop(&player.pubkey(), 10 * SOL).unwrap();
+        let (player_pda, _) = derive_player_pda(&table_pda, &player.pubkey());
+        svm.send_instruction(build_join_table_ix(table_pda, player_pda, player, 5000, seat as u8), &[player]).unwrap().assert_success();
+    }
+
+    // Table is full, so two players queue up
+    let first = Keypair::new();
+    let second = Keypair::new();
+    svm.airdrop(&first.pubkey(), 10 * SOL).unwrap();
+    svm.airdrop(&second.pubkey(), 10 * SOL).unwrap();
+    let (first_pda, _) = derive_player_pda(&table_pda, &first.pubkey());
+    let (second_pda, _) = derive_player_pda(&table_pda, &second.pubkey());
+    assert!(svm.send_instruction(build_join_table_ix(table_pda, first_pda, &first, 5000, 0), &[&first]).is_err());
+
+    svm.send_instruction(build_join_waitlist_ix(table_pda, &first, None), &[&first]).unwrap().assert_success();
+    svm.send_instruction(build_join_waitlist_ix(table_pda, &second, None), &[&second]).unwrap().assert_success();
+
+    let waitlist_account = svm.get_account(&waitlist_pda).unwrap();
+    let waitlist: Waitlist = AnchorDeserialize::deserialize(&mut &waitlist_account.data[8..]).unwrap();
+    assert_eq!(waitlist.players, vec![first.pubkey(), second.pubkey()]);
+
+    // Seat 4 opens up; only the head of the queue may take it during the window
+    let (leaver_pda, _) = derive_player_pda(&table_pda, &players[4].pubkey());
+    svm.send_instruction(build_leave_table_ix(table_pda, leaver_pda, &players[4]), &[&players[4]]).unwrap().assert_success();
+
+    let mut ix = build_join_table_ix(table_pda, second_pda, &second, 5000, 4);
+    ix.accounts.push(AccountMeta::new(waitlist_pda, false));
+    assert!(svm.send_instruction(ix, &[&second]).is_err());
+
+    // The waitlist must be passed once the table has one
+    svm.expire_blockhash();
+    assert!(svm.send_instruction(build_join_table_ix(table_pda, first_pda, &first, 5000, 4), &[&first]).is_err());
+
+    let mut ix = build_join_table_ix(table_pda, first_pda, &first, 5000, 4);
+    ix.accounts.push(AccountMeta::new(waitlist_pda, false));
+    svm.send_instruction(ix, &[&first]).unwrap().assert_success();
+
+    let table_account = svm.get_account(&table_pda).unwrap();
+    let table: PokerTable = AnchorDeserialize::deserialize(&mut &table_account.data[8..]).unwrap();
+    assert_eq!(table.players[4], first.pubkey());
+
+    let waitlist_account = svm.get_account(&waitlist_pda).unwrap();
+    let waitlist: Waitlist = AnchorDeserialize::deserialize(&mut &waitlist_account.data[8..]).unwrap();
+    assert_eq!(waitlist.players, vec![second.pubkey()]);
+}
+
+#[test]
+fn test_waitlist_is_only_for_full_tables() {
+    let mut svm = setup_svm();
+    let (_creator, table_pda, _players) = setup_table(&mut svm, &[5000, 5000]);
+
+    let waiting = funded_keypair(&mut svm);
+    let err = svm.send_instruction(build_join_waitlist_ix(table_pda, &waiting, None), &[&waiting]).unwrap_err();
+    assert!(format!("{:?}", err).contains("TableNotFull"));
+    assert!(svm.get_account(&derive_waitlist_pda(&table_pda).0).is_none());
+}
+
+#[test]
+fn test_waitlisted_player_takes_seat_freed_by_a_hand() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = setup_table(&mut svm, &[5000; MAX_SEATS_DEFAULT as usize]);
+    let (waitlist_pda, _) = derive_waitlist_pda(&table_pda);
+    let waiting = funded_keypair(&mut svm);
+    svm.send_instruction(build_join_waitlist_ix(table_pda, &waiting, None), &[&waiting]).unwrap().assert_success();
+
+    // Seat 4 stands up mid-hand and is released when the hand completes
+    let player_states: Vec<Pubkey> = players.iter().map(|(_, player_pda)| *player_pda).collect();
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, player_states), &[&creator]).unwrap().assert_success();
+    let (leaver, leaver_pda) = &players[4];
+    svm.send_instruction(build_leave_table_ix(table_pda, *leaver_pda, leaver), &[leaver]).unwrap().assert_success();
+    svm.send_instruction(build_end_hand_ix(table_pda, players[0].1, 0), &[&creator]).unwrap().assert_success();
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!(table.game_state, GameState::HandComplete);
+
+    // The head of the queue sits down before the next deal
+    let (waiting_pda, _) = derive_player_pda(&table_pda, &waiting.pubkey());
+    let mut ix = build_join_table_ix(table_pda, waiting_pda, &waiting, 5000, 4);
+    ix.accounts.push(AccountMeta::new(waitlist_pda, false));
+    svm.send_instruction(ix, &[&waiting]).unwrap().assert_success();
+
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!(table.players[4], waiting.pubkey());
+    let waitlist: Waitlist = read_account(&svm, &waitlist_pda);
+    assert!(waitlist.players.is_empty());
+}
+
+#[test]
+fn test_private_table_waitlist_needs_an_invite() {
+    let mut svm = setup_svm();
+    let (table_pda, creator, guest) = private_table(&mut svm);
+    for seat in 0..MAX_SEATS_DEFAULT {
+        let player = funded_keypair(&mut svm);
+        svm.send_instruction(build_invite_player_ix(table_pda, &creator, player.pubkey()), &[&creator]).unwrap().assert_success();
+        let (player_pda, _) = derive_player_pda(&table_pda, &player.pubkey());
+        let (invite_pda, _) = derive_invite_pda(&table_pda, &player.pubkey());
+        svm.send_instruction(
+            build_join_table_with_invite_ix(table_pda, player_pda, &player, 5000, seat, Some(invite_pda)),
+            &[&player],
+        ).unwrap().assert_success();
+    }
+
+    let err = svm.send_instruction(build_join_waitlist_ix(table_pda, &guest, None), &[&guest]).unwrap_err();
+    assert!(format!("{:?}", err).contains("NotInvited"));
+
+    svm.send_instruction(build_invite_player_ix(table_pda, &creator, guest.pubkey()), &[&creator]).unwrap().assert_success();
+    let (invite_pda, _) = derive_invite_pda(&table_pda, &guest.pubkey());
+    svm.send_instruction(build_join_waitlist_ix(table_pda, &guest, Some(invite_pda)), &[&guest]).unwrap().assert_success();
+    let waitlist: Waitlist = read_account(&svm, &derive_waitlist_pda(&table_pda).0);
+    assert_eq!(waitlist.players, vec![guest.pubkey()]);
+}
+
+/// A private table plus the creator and one other funded player
+fn private_table(svm: &mut LiteSVM) -> (Pubkey, Keypair, Keypair) {
+    let creator = Keypair::new();
+    let guest = Keypair::new();
+    for keypair in [&creator, &guest] {
+        svm.airdrop(&keypair.pubkey(), 10 * SOL).unwrap();
+    }
+
+    let table_id = 1u64;
+    let (table_pda, _) = derive_table_pda(table_id);
+    svm.send_instruction(build_initialize_table_with_options_ix(
+        table_pda, &creator, table_id,
+        SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT,
+        MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT,
+        MAX_SEATS_DEFAULT, TableVisibility::Private,
+    ), &[&creator]).unwrap().assert_success();
+    (table_pda, creator, guest)
+}
+
+#[test]
+fn test_private_table_seats_invited_players() {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(POKER_PROGRAM_ID, "../../target/deploy/poker.so").unwrap();
+    let (table_pda, creator, guest) = private_table(&mut svm);
+
+    svm.send_instruction(build_invite_player_ix(table_pda, &creator, guest.pubkey()), &[&creator]).unwrap().assert_success();
+    let (invite_pda, _) = derive_invite_pda(&table_pda, &guest.pubkey());
+    let invite_account = svm.get_account(&invite_pda).unwrap();
+    let invite: TableInvite = AnchorDeserialize::deserialize(&mut &invite_account.data[8..]).unwrap();
+    assert_eq!((invite.table, invite.player), (table_pda, guest.pubkey()));
+
+    let (guest_pda, _) = derive_player_pda(&table_pda, &guest.pubkey());
+    svm.send_instruction(
+        build_join_table_with_invite_ix(table_pda, guest_pda, &guest, 5000, 0, Some(invite_pda)),
+        &[&guest],
+    ).unwrap().assert_success();
 
-    // Verify table updated
     let table_account = svm.get_account(&table_pda).unwrap();
-    let table_data = &table_account.data[8..];
-    let table: PokerTable = AnchorDeserialize::deserialize(&mut &table_data[..]).unwrap();
+    let table: PokerTable = AnchorDeserialize::deserialize(&mut &table_account.data[8..]).unwrap();
+    assert_eq!(table.players[0], guest.pubkey());
+}
 
-    assert_eq!(table.player_count, 1);
-    assert_eq!(table.players[position as usize], player.pubkey());
+#[test]
+fn test_private_table_refuses_uninvited_players() {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(POKER_PROGRAM_ID, "../../target/deploy/poker.so").unwrap();
+    let (table_pda, creator, guest) = private_table(&mut svm);
+    let (guest_pda, _) = derive_player_pda(&table_pda, &guest.pubkey());
+
+    let err = svm.send_instruction(build_join_table_ix(table_pda, guest_pda, &guest, 5000, 0), &[&guest]).unwrap_err();
+    assert!(format!("{:?}", err).contains("NotInvited"));
+
+    // Someone else's invite doesn't match the player's seeds
+    svm.send_instruction(build_invite_player_ix(table_pda, &creator, creator.pubkey()), &[&creator]).unwrap().assert_success();
+    let (creator_invite, _) = derive_invite_pda(&table_pda, &creator.pubkey());
+    assert!(svm.send_instruction(
+        build_join_table_with_invite_ix(table_pda, guest_pda, &guest, 5000, 0, Some(creator_invite)),
+        &[&guest],
+    ).is_err());
 }
 
 #[test]
-fn test_start_hand_with_two_players() {
+fn test_only_table_authority_invites_players() {
     let mut svm = LiteSVM::new();
-    svm.add_program_from_file(
-        POKER_PROGRAM_ID,
-        "../../target/deploy/poker.so",
-    ).unwrap();
+    svm.add_program_from_file(POKER_PROGRAM_ID, "../../target/deploy/poker.so").unwrap();
+    let (table_pda, _creator, guest) = private_table(&mut svm);
+
+    assert!(svm.send_instruction(build_invite_player_ix(table_pda, &guest, guest.pubkey()), &[&guest]).is_err());
+    assert!(svm.get_account(&derive_invite_pda(&table_pda, &guest.pubkey()).0).is_none());
+}
+
+#[test]
+fn test_revoked_invite_no_longer_seats_player() {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(POKER_PROGRAM_ID, "../../target/deploy/poker.so").unwrap();
+    let (table_pda, creator, guest) = private_table(&mut svm);
+
+    svm.send_instruction(build_invite_player_ix(table_pda, &creator, guest.pubkey()), &[&creator]).unwrap().assert_success();
+    svm.send_instruction(build_revoke_invite_ix(table_pda, &creator, guest.pubkey()), &[&creator]).unwrap().assert_success();
+    let (invite_pda, _) = derive_invite_pda(&table_pda, &guest.pubkey());
+    assert!(svm.get_account(&invite_pda).is_none());
+
+    let (guest_pda, _) = derive_player_pda(&table_pda, &guest.pubkey());
+    assert!(svm.send_instruction(
+        build_join_table_with_invite_ix(table_pda, guest_pda, &guest, 5000, 0, Some(invite_pda)),
+        &[&guest],
+    ).is_err());
+}
+
+fn bigger_blinds() -> TableConfigUpdate {
+    TableConfigUpdate {
+        small_blind: Some(25),
+        big_blind: Some(50),
+        max_buy_in: Some(20_000),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_only_table_authority_updates_config() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, _) = setup_table(&mut svm, &[]);
+    let manager = funded_keypair(&mut svm);
+
+    let err = svm.send_instruction(build_update_table_config_ix(table_pda, &manager, bigger_blinds()), &[&manager]).unwrap_err();
+    assert!(format!("{:?}", err).contains("ConstraintHasOne"));
+
+    // The creator can hand the role over, and then no longer holds it
+    svm.send_instruction(build_set_table_authority_ix(table_pda, &creator, manager.pubkey()), &[&creator]).unwrap().assert_success();
+    svm.send_instruction(build_update_table_config_ix(table_pda, &manager, bigger_blinds()), &[&manager]).unwrap().assert_success();
+    assert_eq!(read_account::<PokerTable>(&svm, &table_pda).authority, manager.pubkey());
+    let err = svm.send_instruction(build_update_table_config_ix(table_pda, &creator, bigger_blinds()), &[&creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("ConstraintHasOne"));
+}
+
+#[test]
+fn test_config_update_changes_only_fields_given() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, _) = setup_table(&mut svm, &[5000, 5000]);
+    svm.send_instruction(build_update_table_config_ix(table_pda, &creator, bigger_blinds()), &[&creator]).unwrap().assert_success();
+
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!((table.small_blind, table.big_blind), (25, 50));
+    assert_eq!((table.min_buy_in, table.max_buy_in), (MIN_BUY_IN_DEFAULT, 20_000));
+    assert_eq!(table.visibility, TableVisibility::Public);
+}
+
+#[test]
+fn test_config_update_keeps_blinds_ordered() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, _) = setup_table(&mut svm, &[]);
+    let backwards = TableConfigUpdate { small_blind: Some(100), ..Default::default() };
+    let err = svm.send_instruction(build_update_table_config_ix(table_pda, &creator, backwards), &[&creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("InvalidTableConfig"));
+    assert_eq!(read_account::<PokerTable>(&svm, &table_pda).small_blind, SMALL_BLIND_DEFAULT);
+}
+
+#[test]
+fn test_config_cannot_change_while_a_hand_is_running() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, _) = setup_table(&mut svm, &[5000, 5000]);
+    svm.send_instruction(build_start_hand_ix(table_pda, &creator), &[&creator]).unwrap().assert_success();
+
+    let private = TableConfigUpdate { visibility: Some(TableVisibility::Private), ..Default::default() };
+    let err = svm.send_instruction(build_update_table_config_ix(table_pda, &creator, private), &[&creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("GameInProgress"));
+}
+
+#[test]
+fn test_close_empty_table_returns_rent_to_creator() {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(POKER_PROGRAM_ID, "../../target/deploy/poker.so").unwrap();
 
-    // Setup: Create table and add 2 players
     let creator = Keypair::new();
     svm.airdrop(&creator.pubkey(), 10 * SOL).unwrap();
 
     let table_id = 1u64;
     let (table_pda, _) = derive_table_pda(table_id);
+    let (blind_structure_pda, _) = derive_blind_structure_pda(&table_pda);
+    svm.send_instruction(build_initialize_table_ix(
+        table_pda, &creator, table_id,
+        SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT,
+        MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT,
+    ), &[&creator]).unwrap();
+    svm.send_instruction(build_set_blind_structure_ix(
+        table_pda, blind_structure_pda, &creator,
+        vec![BlindLevel { small_blind: 10, big_blind: 20, ante: 0 }],
+        LevelDurationUnit::Hands,
+        1,
+    ), &[&creator]).unwrap().assert_success();
 
-    // Initialize table
-    let init_ix = build_initialize_table_ix(
-        table_pda,
-        &creator,
-        table_id,
-        SMALL_BLIND_DEFAULT,
-        BIG_BLIND_DEFAULT,
-        MIN_BUY_IN_DEFAULT,
-        MAX_BUY_IN_DEFAULT,
-    );
-    svm.send_instruction(init_ix, &[&creator]).unwrap();
-
-    // Add two players
     let player1 = Keypair::new();
-    let player2 = Keypair::new();
     svm.airdrop(&player1.pubkey(), 10 * SOL).unwrap();
-    svm.airdrop(&player2.pubkey(), 10 * SOL).unwrap();
-
     let (player1_pda, _) = derive_player_pda(&table_pda, &player1.pubkey());
-    let (player2_pda, _) = derive_player_pda(&table_pda, &player2.pubkey());
+    svm.send_instruction(build_join_table_ix(table_pda, player1_pda, &player1, 5000, 0), &[&player1]).unwrap();
 
-    let join1_ix = build_join_table_ix(table_pda, player1_pda, &player1, 5000, 0);
-    let join2_ix = build_join_table_ix(table_pda, player2_pda, &player2, 5000, 1);
-    svm.send_instruction(join1_ix, &[&player1]).unwrap();
-    svm.send_instruction(join2_ix, &[&player2]).unwrap();
+    // Can't close with someone seated
+    assert!(svm.send_instruction(
+        build_close_table_ix(table_pda, creator.pubkey(), &creator, vec![blind_structure_pda]),
+        &[&creator],
+    ).is_err());
 
-    // Start hand (pass creator as dummy signer for LiteSVM)
-    let start_ix = build_start_hand_ix(table_pda, &creator);
-    let result = svm.send_instruction(start_ix, &[&creator]).unwrap();
-    result.assert_success();
+    svm.send_instruction(build_leave_table_ix(table_pda, player1_pda, &player1), &[&player1]).unwrap().assert_success();
 
-    // Verify game state changed
-    let table_account = svm.get_account(&table_pda).unwrap();
-    let table_data = &table_account.data[8..];
-    let table: PokerTable = AnchorDeserialize::deserialize(&mut &table_data[..]).unwrap();
+    // The blind structure has to be closed along with the table
+    svm.expire_blockhash();
+    assert!(svm.send_instruction(build_close_table_ix(table_pda, creator.pubkey(), &creator, vec![]), &[&creator]).is_err());
 
-    assert_eq!(table.game_state, GameState::PreFlop);
-    assert_eq!(table.hand_number, 1);
-    assert_eq!(table.current_bet, BIG_BLIND_DEFAULT);
+    let rent = svm.get_balance(&table_pda).unwrap() + svm.get_balance(&blind_structure_pda).unwrap();
+    let creator_before = svm.get_balance(&creator.pubkey()).unwrap();
+    svm.send_instruction(
+        build_close_table_ix(table_pda, creator.pubkey(), &creator, vec![blind_structure_pda]),
+        &[&creator],
+    ).unwrap().assert_success();
+
+    assert!(svm.get_account(&table_pda).is_none_or(|account| account.lamports == 0));
+    assert!(svm.get_account(&blind_structure_pda).is_none_or(|account| account.lamports == 0));
+    assert!(svm.get_balance(&creator.pubkey()).unwrap() > creator_before + rent - SOL / 100);
+}
+
+#[test]
+fn test_close_table_returns_hand_history_and_invite_rent() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players, played_history) = recording_table(&mut svm, &[5000, 5000]);
+    deal_recorded_hand(&mut svm, &creator, table_pda, &players, played_history);
+    recorded_act(&mut svm, table_pda, &players, played_history, PlayerActionType::Fold);
+    let mut remaining: Vec<Pubkey> = players.iter().map(|(_, player_pda)| *player_pda).collect();
+    remaining.push(played_history);
+    svm.send_instruction(build_check_auto_win_ix(table_pda, remaining), &[&creator]).unwrap().assert_success();
+
+    // Hand 2's history is open and a player has an invite when everyone leaves
+    let (open_history, _) = derive_hand_history_pda(&table_pda, 2);
+    svm.send_instruction(build_hand_history_ix("open_hand_history", table_pda, &creator, 2), &[&creator])
+        .unwrap().assert_success();
+    let (invite, _) = derive_invite_pda(&table_pda, &players[0].0.pubkey());
+    svm.send_instruction(build_invite_player_ix(table_pda, &creator, players[0].0.pubkey()), &[&creator])
+        .unwrap().assert_success();
+    for (player, player_pda) in &players {
+        svm.send_instruction(build_leave_table_ix(table_pda, *player_pda, player), &[player]).unwrap().assert_success();
+    }
+
+    // The open history can't be left behind
+    let err = svm.send_instruction(
+        build_close_table_ix(table_pda, creator.pubkey(), &creator, vec![played_history, invite]),
+        &[&creator],
+    ).unwrap_err();
+    assert!(format!("{:?}", err).contains("MissingHandHistoryAccount"));
+
+    let closed = [table_pda, played_history, open_history, invite];
+    let rent: u64 = closed.iter().map(|account| svm.get_balance(account).unwrap()).sum();
+    let creator_before = svm.get_balance(&creator.pubkey()).unwrap();
+    svm.expire_blockhash();
+    svm.send_instruction(
+        build_close_table_ix(table_pda, creator.pubkey(), &creator, vec![played_history, open_history, invite]),
+        &[&creator],
+    ).unwrap().assert_success();
+
+    for account in closed {
+        assert!(svm.get_account(&account).is_none_or(|account| account.lamports == 0));
+    }
+    // Everything comes back to the creator, who opened the histories and sent the invite
+    assert_eq!(svm.get_balance(&creator.pubkey()).unwrap(), creator_before + rent - 5000);
+}
+
+// Two players with 5000 each and the blinds in for a hand
+fn blinds_in(svm: &mut LiteSVM) -> (Keypair, Pubkey, Vec<(Keypair, Pubkey)>) {
+    let (creator, table_pda, players) = setup_table(svm, &[5000, 5000]);
+    start_with_blinds(svm, &creator, table_pda, &players);
+    (creator, table_pda, players)
+}
+
+fn start_with_blinds(svm: &mut LiteSVM, creator: &Keypair, table: Pubkey, players: &[(Keypair, Pubkey)]) {
+    let player_pdas = players.iter().map(|(_, pda)| *pda).collect();
+    svm.expire_blockhash();
+    svm.send_instruction(build_start_hand_with_accounts_ix(table, player_pdas), &[creator]).unwrap().assert_success();
+    post_blinds(svm, table, players);
+}
+
+fn set_paused(svm: &mut LiteSVM, table: Pubkey, signer: &Keypair, paused: bool) -> std::result::Result<(), String> {
+    svm.expire_blockhash();
+    svm.send_instruction(build_table_admin_ix("set_table_paused", table, signer, vec![paused as u8], vec![]), &[signer])
+        .map(|_| ())
+        .map_err(|err| format!("{:?}", err))
+}
+
+fn emergency_refund(
+    svm: &mut LiteSVM,
+    table: Pubkey,
+    signer: &Keypair,
+    players: &[(Keypair, Pubkey)],
+) -> std::result::Result<(), String> {
+    let player_pdas = players.iter().map(|(_, pda)| *pda).collect();
+    svm.expire_blockhash();
+    svm.send_instruction(build_table_admin_ix("emergency_refund", table, signer, vec![], player_pdas), &[signer])
+        .map(|_| ())
+        .map_err(|err| format!("{:?}", err))
+}
+
+#[test]
+fn test_only_table_admin_pauses() {
+    let mut svm = setup_svm();
+    let (_, table_pda, players) = blinds_in(&mut svm);
+    let err = set_paused(&mut svm, table_pda, &players[0].0, true).unwrap_err();
+    assert!(err.contains("Unauthorized"));
+    assert!(!read_account::<PokerTable>(&svm, &table_pda).paused);
+}
+
+#[test]
+fn test_emergency_refund_needs_a_paused_table() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = blinds_in(&mut svm);
+    let err = emergency_refund(&mut svm, table_pda, &creator, &players).unwrap_err();
+    assert!(err.contains("TableNotPaused"));
+}
+
+#[test]
+fn test_paused_table_rejects_actions() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = blinds_in(&mut svm);
+    set_paused(&mut svm, table_pda, &creator, true).unwrap();
+    let err = act(&mut svm, table_pda, &players, PlayerActionType::Fold, 0).unwrap_err();
+    assert!(err.contains("TablePaused"));
+}
+
+#[test]
+fn test_emergency_refund_returns_committed_chips() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = blinds_in(&mut svm);
+    set_paused(&mut svm, table_pda, &creator, true).unwrap();
+    emergency_refund(&mut svm, table_pda, &creator, &players).unwrap();
+
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!(table.game_state, GameState::HandComplete);
+    assert_eq!(table.pot, 0);
+    for (_, player_pda) in &players {
+        let state: PlayerState = read_account(&svm, player_pda);
+        assert_eq!((state.stack, state.current_bet), (5000, 0));
+    }
+}
+
+#[test]
+fn test_resumed_table_starts_a_clean_hand() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = blinds_in(&mut svm);
+    set_paused(&mut svm, table_pda, &creator, true).unwrap();
+    emergency_refund(&mut svm, table_pda, &creator, &players).unwrap();
+    set_paused(&mut svm, table_pda, &creator, false).unwrap();
+
+    let player_pdas = players.iter().map(|(_, pda)| *pda).collect();
+    svm.expire_blockhash();
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, player_pdas), &[&creator]).unwrap().assert_success();
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!((table.game_state, table.hand_number, table.pot), (GameState::PreFlop, 2, 0));
+}
+
+// A two-seat table with its first `filled` seats taken by 5000 stacks
+fn heads_up_table(svm: &mut LiteSVM, filled: u8) -> (Keypair, Pubkey, Vec<(Keypair, Pubkey)>) {
+    let creator = funded_keypair(svm);
+    let (table_pda, _) = derive_table_pda(1);
+    svm.send_instruction(build_initialize_table_with_options_ix(
+        table_pda, &creator, 1,
+        SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT,
+        MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT,
+        2, TableVisibility::Public,
+    ), &[&creator]).unwrap().assert_success();
+
+    let mut players = Vec::new();
+    for seat in 0..filled {
+        let player = funded_keypair(svm);
+        let (player_pda, _) = derive_player_pda(&table_pda, &player.pubkey());
+        svm.send_instruction(build_join_table_ix(table_pda, player_pda, &player, 5000, seat), &[&player])
+            .unwrap()
+            .assert_success();
+        players.push((player, player_pda));
+    }
+    (creator, table_pda, players)
+}
+
+#[test]
+fn test_table_cannot_have_more_seats_than_max_players() {
+    let mut svm = setup_svm();
+    let creator = funded_keypair(&mut svm);
+    let (table_pda, _) = derive_table_pda(1);
+    let err = svm.send_instruction(build_initialize_table_with_options_ix(
+        table_pda, &creator, 1,
+        SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT,
+        MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT,
+        MAX_PLAYERS as u8 + 1, TableVisibility::Public,
+    ), &[&creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("InvalidSeatCount"));
+}
+
+#[test]
+fn test_heads_up_table_has_only_two_seats() {
+    let mut svm = setup_svm();
+    let (_, table_pda, _) = heads_up_table(&mut svm, 2);
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!((table.max_seats, table.players.len()), (2, 2));
+
+    let player3 = funded_keypair(&mut svm);
+    let (player3_pda, _) = derive_player_pda(&table_pda, &player3.pubkey());
+    let err = svm.send_instruction(build_join_table_ix(table_pda, player3_pda, &player3, 5000, 1), &[&player3]).unwrap_err();
+    assert!(format!("{:?}", err).contains("TableFull"));
+}
+
+#[test]
+fn test_heads_up_table_rejects_seats_past_the_last() {
+    let mut svm = setup_svm();
+    let (_, table_pda, _) = heads_up_table(&mut svm, 0);
+    let player = funded_keypair(&mut svm);
+    let (player_pda, _) = derive_player_pda(&table_pda, &player.pubkey());
+    let err = svm.send_instruction(build_join_table_ix(table_pda, player_pda, &player, 5000, 2), &[&player]).unwrap_err();
+    assert!(format!("{:?}", err).contains("InvalidPosition"));
+}
+
+#[test]
+fn test_heads_up_button_posts_small_blind_and_acts_first() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = heads_up_table(&mut svm, 2);
+    let player_pdas = players.iter().map(|(_, pda)| *pda).collect();
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, player_pdas), &[&creator]).unwrap().assert_success();
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!(table.button_position, 1);
+    assert_eq!(table.blind_seats().unwrap(), (1, 0));
+    assert_eq!(table.current_player_index, 1);
+
+    // Seat 0 is the big blind and can't act before the button
+    let (big_blind, big_blind_pda) = &players[0];
+    let err = svm.send_instruction(
+        build_player_action_ix(table_pda, *big_blind_pda, big_blind, PlayerActionType::Fold, 0),
+        &[big_blind],
+    ).unwrap_err();
+    assert!(format!("{:?}", err).contains("NotYourTurn"));
+
+    post_blinds(&mut svm, table_pda, &players);
+    assert_eq!(read_account::<PlayerState>(&svm, &players[1].1).current_bet, SMALL_BLIND_DEFAULT);
+    assert_eq!(read_account::<PlayerState>(&svm, big_blind_pda).current_bet, BIG_BLIND_DEFAULT);
+}
+
+#[test]
+fn test_heads_up_button_alternates_between_seats() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = heads_up_table(&mut svm, 2);
+    start_with_blinds(&mut svm, &creator, table_pda, &players);
+    act(&mut svm, table_pda, &players, PlayerActionType::Fold, 0).unwrap();
+    svm.send_instruction(build_end_hand_ix(table_pda, players[0].1, 0), &[&creator]).unwrap().assert_success();
+
+    // The button moves to the only other seat, taking the small blind with it
+    let player_pdas = players.iter().map(|(_, pda)| *pda).collect();
+    svm.expire_blockhash();
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, player_pdas), &[&creator]).unwrap().assert_success();
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!(table.button_position, 0);
+    assert_eq!(table.blind_seats().unwrap(), (0, 1));
+    assert_eq!(table.current_player_index, 0);
+}
+
+// Two 5000 stacks at a table raking 10% up to 100, with `operator` as the rake authority
+// and nothing taken from hands that end preflop
+fn raked_table(svm: &mut LiteSVM) -> (Keypair, Keypair, Pubkey, Vec<(Keypair, Pubkey)>) {
+    let (creator, table_pda, players) = setup_table(svm, &[5000, 5000]);
+    let operator = funded_keypair(svm);
+    svm.send_instruction(
+        build_set_rake_ix(table_pda, &creator, 1_000, 100, true, operator.pubkey(), 0, RakeAttribution::Contributed),
+        &[&creator],
+    ).unwrap().assert_success();
+    (creator, operator, table_pda, players)
+}
+
+// Player 2 takes the blinds, after a flop when `see_flop`
+fn play_raked_hand(
+    svm: &mut LiteSVM,
+    creator: &Keypair,
+    table: Pubkey,
+    players: &[(Keypair, Pubkey)],
+    see_flop: bool,
+) {
+    start_with_blinds(svm, creator, table, players);
+    if see_flop {
+        svm.send_instruction(build_advance_street_ix(table), &[creator]).unwrap().assert_success();
+    }
+    let mut end_hand = build_end_hand_ix(table, players[1].1, 1);
+    end_hand.accounts.push(AccountMeta::new(derive_fee_vault_pda(&table).0, false));
+    svm.send_instruction(end_hand, &[creator]).unwrap().assert_success();
+}
+
+fn vault_rent(svm: &LiteSVM, fee_vault: &Pubkey) -> u64 {
+    let size = svm.get_account(fee_vault).unwrap().data.len();
+    svm.minimum_balance_for_rent_exemption(size)
+}
+
+#[test]
+fn test_no_rake_from_hands_that_end_preflop() {
+    let mut svm = setup_svm();
+    let (creator, _, table_pda, players) = raked_table(&mut svm);
+    let (fee_vault_pda, _) = derive_fee_vault_pda(&table_pda);
+    play_raked_hand(&mut svm, &creator, table_pda, &players, false);
+    assert_eq!(svm.get_balance(&fee_vault_pda).unwrap(), vault_rent(&svm, &fee_vault_pda));
+}
+
+#[test]
+fn test_rake_taken_once_a_flop_is_seen() {
+    let mut svm = setup_svm();
+    let (creator, _, table_pda, players) = raked_table(&mut svm);
+    let (fee_vault_pda, _) = derive_fee_vault_pda(&table_pda);
+    play_raked_hand(&mut svm, &creator, table_pda, &players, true);
+
+    // 10% of the 30 chip pot
+    assert_eq!(svm.get_balance(&fee_vault_pda).unwrap(), vault_rent(&svm, &fee_vault_pda) + 3);
+    let stacks: u64 = players.iter().map(|(_, pda)| read_account::<PlayerState>(&svm, pda).stack).sum();
+    assert_eq!(stacks, 10_000 - 3);
+}
+
+#[test]
+fn test_only_rake_authority_withdraws_rake() {
+    let mut svm = setup_svm();
+    let (creator, operator, table_pda, players) = raked_table(&mut svm);
+    play_raked_hand(&mut svm, &creator, table_pda, &players, true);
+
+    let err = svm.send_instruction(build_withdraw_rake_ix(table_pda, &creator, operator.pubkey(), 3), &[&creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("ConstraintHasOne"));
+    svm.send_instruction(build_withdraw_rake_ix(table_pda, &operator, operator.pubkey(), 3), &[&operator]).unwrap().assert_success();
+}
+
+#[test]
+fn test_table_authority_cannot_take_over_the_rake() {
+    let mut svm = setup_svm();
+    let (creator, operator, table_pda, _) = raked_table(&mut svm);
+
+    let err = svm.send_instruction(
+        build_set_rake_ix(table_pda, &creator, 1_000, 100, true, creator.pubkey(), 0, RakeAttribution::Contributed),
+        &[&creator],
+    ).unwrap_err();
+    assert!(format!("{:?}", err).contains("RakeAuthorityLocked"));
+
+    // The rest of the rake can still be changed
+    svm.send_instruction(
+        build_set_rake_ix(table_pda, &creator, 500, 50, true, operator.pubkey(), 0, RakeAttribution::Contributed),
+        &[&creator],
+    ).unwrap().assert_success();
+    let fee_vault: FeeVault = read_account(&svm, &derive_fee_vault_pda(&table_pda).0);
+    assert_eq!(fee_vault.rake_authority, operator.pubkey());
+    assert_eq!(read_account::<PokerTable>(&svm, &table_pda).rake_bps, 500);
+}
+
+#[test]
+fn test_rake_withdrawal_leaves_vault_rent() {
+    let mut svm = setup_svm();
+    let (creator, operator, table_pda, players) = raked_table(&mut svm);
+    let (fee_vault_pda, _) = derive_fee_vault_pda(&table_pda);
+    play_raked_hand(&mut svm, &creator, table_pda, &players, true);
+
+    for amount in [4, u64::MAX] {
+        let err = svm.send_instruction(build_withdraw_rake_ix(table_pda, &operator, operator.pubkey(), amount), &[&operator]).unwrap_err();
+        assert!(format!("{:?}", err).contains("InsufficientFunds"));
+    }
+    svm.send_instruction(build_withdraw_rake_ix(table_pda, &operator, operator.pubkey(), 3), &[&operator]).unwrap().assert_success();
+    assert_eq!(svm.get_balance(&fee_vault_pda).unwrap(), vault_rent(&svm, &fee_vault_pda));
+}
+
+// Blinds of 100 and 200 are in at a table raking 10%, half of each player's share handed
+// back as rakeback. Only the players picked by `with_stats` have stats accounts. Returns the
+// end_hand for player 2 winning with the fee vault attached, and both stats addresses.
+fn rakeback_hand(
+    svm: &mut LiteSVM,
+    with_stats: [bool; 2],
+) -> (Keypair, Vec<(Keypair, Pubkey)>, [Pubkey; 2], Instruction) {
+    let creator = funded_keypair(svm);
+    let (table_pda, _) = derive_table_pda(1);
+    svm.send_instruction(build_initialize_table_ix(
+        table_pda, &creator, 1,
+        100, 200,
+        MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT,
+    ), &[&creator]).unwrap().assert_success();
+    svm.send_instruction(
+        build_set_rake_ix(table_pda, &creator, 1_000, 0, false, creator.pubkey(), 5_000, RakeAttribution::Contributed),
+        &[&creator],
+    ).unwrap().assert_success();
+
+    let mut players = Vec::new();
+    let mut stats = [Pubkey::default(); 2];
+    for seat in 0..2 {
+        let player = funded_keypair(svm);
+        let (player_pda, _) = derive_player_pda(&table_pda, &player.pubkey());
+        stats[seat] = derive_player_stats_pda(&player.pubkey()).0;
+        if with_stats[seat] {
+            svm.send_instruction(build_player_stats_ix("create_player_stats", &player), &[&player]).unwrap().assert_success();
+        }
+        svm.send_instruction(build_join_table_ix(table_pda, player_pda, &player, 5000, seat as u8), &[&player])
+            .unwrap()
+            .assert_success();
+        players.push((player, player_pda));
+    }
+    start_with_blinds(svm, &creator, table_pda, &players);
+
+    let mut end_hand = build_end_hand_ix(table_pda, players[1].1, 1);
+    end_hand.accounts.push(AccountMeta::new(derive_fee_vault_pda(&table_pda).0, false));
+    (creator, players, stats, end_hand)
+}
+
+#[test]
+fn test_rakeback_needs_every_contributors_state() {
+    let mut svm = setup_svm();
+    let (creator, _, stats, mut end_hand) = rakeback_hand(&mut svm, [true, true]);
+    for stats_pda in stats {
+        end_hand.accounts.push(AccountMeta::new(stats_pda, false));
+    }
+    let err = svm.send_instruction(end_hand, &[&creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("MissingPlayerAccounts"));
+}
+
+#[test]
+fn test_rakeback_follows_contributed_rake() {
+    let mut svm = setup_svm();
+    let (creator, players, stats, mut end_hand) = rakeback_hand(&mut svm, [true, true]);
+    let stats_rent = svm.get_balance(&stats[1]).unwrap();
+    for stats_pda in stats {
+        end_hand.accounts.push(AccountMeta::new(stats_pda, false));
+    }
+    end_hand.accounts.push(AccountMeta::new(players[0].1, false));
+    svm.send_instruction(end_hand, &[&creator]).unwrap().assert_success();
+
+    // 30 chips of rake from a 300 pot: 10 from the small blind (player2 on the
+    // button), 20 from the big blind
+    let stats1: PlayerStats = read_account(&svm, &stats[0]);
+    let stats2: PlayerStats = read_account(&svm, &stats[1]);
+    assert_eq!((stats1.rake_contributed, stats1.rakeback_earned), (20, 10));
+    assert_eq!((stats2.rake_contributed, stats2.rakeback_earned), (10, 5));
+    assert_eq!(svm.get_balance(&stats[1]).unwrap(), stats_rent + 5);
+}
+
+#[test]
+fn test_claimed_rakeback_cannot_be_claimed_again() {
+    let mut svm = setup_svm();
+    let (creator, players, stats, mut end_hand) = rakeback_hand(&mut svm, [true, true]);
+    let stats_rent = svm.get_balance(&stats[1]).unwrap();
+    for stats_pda in stats {
+        end_hand.accounts.push(AccountMeta::new(stats_pda, false));
+    }
+    end_hand.accounts.push(AccountMeta::new(players[0].1, false));
+    svm.send_instruction(end_hand, &[&creator]).unwrap().assert_success();
+
+    let player2 = &players[1].0;
+    svm.send_instruction(build_player_stats_ix("claim_rakeback", player2), &[player2]).unwrap().assert_success();
+    assert_eq!(svm.get_balance(&stats[1]).unwrap(), stats_rent);
+
+    svm.expire_blockhash();
+    let err = svm.send_instruction(build_player_stats_ix("claim_rakeback", player2), &[player2]).unwrap_err();
+    assert!(format!("{:?}", err).contains("NothingToClaim"));
+}
+
+#[test]
+fn test_player_stats_creation_is_reported() {
+    let mut svm = setup_svm();
+    let player = funded_keypair(&mut svm);
+    let result = svm.send_instruction(build_player_stats_ix("create_player_stats", &player), &[&player]).unwrap();
+    let created = decode_events::<PlayerStatsCreated>(result.logs());
+    let (stats_pda, _) = derive_player_stats_pda(&player.pubkey());
+    assert_eq!((created[0].player, created[0].player_stats), (player.pubkey(), stats_pda));
+}
+
+#[test]
+fn test_rakeback_needs_every_stats_address() {
+    let mut svm = setup_svm();
+    let (creator, players, stats, mut end_hand) = rakeback_hand(&mut svm, [true, false]);
+    end_hand.accounts.push(AccountMeta::new(stats[0], false));
+    end_hand.accounts.push(AccountMeta::new(players[0].1, false));
+    let err = svm.send_instruction(end_hand, &[&creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("MissingPlayerStatsAccount"));
+}
+
+#[test]
+fn test_rakeback_skips_players_without_stats() {
+    let mut svm = setup_svm();
+    let (creator, players, stats, mut end_hand) = rakeback_hand(&mut svm, [true, false]);
+    let fee_vault_pda = end_hand.accounts.last().unwrap().pubkey;
+    let vault_before = svm.get_balance(&fee_vault_pda).unwrap();
+
+    // A stats address that was never created is passed but left alone
+    for stats_pda in stats {
+        end_hand.accounts.push(AccountMeta::new(stats_pda, false));
+    }
+    end_hand.accounts.push(AccountMeta::new(players[0].1, false));
+    svm.send_instruction(end_hand, &[&creator]).unwrap().assert_success();
+
+    // 30 chips of rake: player1's 20 earns 10 back, player2's 10 stays in the vault
+    let stats1: PlayerStats = read_account(&svm, &stats[0]);
+    assert_eq!((stats1.rake_contributed, stats1.rakeback_earned), (20, 10));
+    assert!(svm.get_account(&stats[1]).is_none_or(|account| account.lamports == 0));
+    assert_eq!(svm.get_balance(&fee_vault_pda).unwrap(), vault_before + 20);
+}
+
+/// A table with hand 1's history opened by the authority
+fn recording_table(svm: &mut LiteSVM, buy_ins: &[u64]) -> (Keypair, Pubkey, Vec<(Keypair, Pubkey)>, Pubkey) {
+    let (creator, table_pda, players) = setup_table(svm, buy_ins);
+    let (history_pda, _) = derive_hand_history_pda(&table_pda, 1);
+    svm.send_instruction(build_hand_history_ix("open_hand_history", table_pda, &creator, 1), &[&creator])
+        .unwrap().assert_success();
+    (creator, table_pda, players, history_pda)
+}
+
+/// Deal hand 1 and post its blinds, passing the history account throughout
+fn deal_recorded_hand(svm: &mut LiteSVM, creator: &Keypair, table: Pubkey, players: &[(Keypair, Pubkey)], history: Pubkey) {
+    let mut start_hand = build_start_hand_with_accounts_ix(table, players.iter().map(|(_, player_pda)| *player_pda).collect());
+    start_hand.accounts.push(AccountMeta::new(history, false));
+    svm.send_instruction(start_hand, &[creator]).unwrap().assert_success();
+
+    let table_state: PokerTable = read_account(svm, &table);
+    let (small_blind, big_blind) = table_state.blind_seats().unwrap();
+    for seat in [small_blind, big_blind] {
+        let (player, player_pda) = &players[seat as usize];
+        let mut post_blinds = build_post_blinds_ix(table, *player_pda, player);
+        post_blinds.accounts.push(AccountMeta::new(history, false));
+        svm.send_instruction(post_blinds, &[player]).unwrap().assert_success();
+    }
+}
+
+/// Act for the player to act, passing the history account
+fn recorded_act(svm: &mut LiteSVM, table: Pubkey, players: &[(Keypair, Pubkey)], history: Pubkey, action: PlayerActionType) -> u8 {
+    let table_state: PokerTable = read_account(svm, &table);
+    let seat = table_state.current_player_index;
+    let (player, player_pda) = &players[seat as usize];
+    let mut ix = build_player_action_ix(table, *player_pda, player, action, 0);
+    ix.accounts.push(AccountMeta::new(history, false));
+    svm.send_instruction(ix, &[player]).unwrap().assert_success();
+    seat
+}
+
+#[test]
+fn test_hand_history_must_be_passed_once_opened() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players, _history_pda) = recording_table(&mut svm, &[5000, 5000]);
+
+    let start_hand = build_start_hand_with_accounts_ix(table_pda, vec![players[0].1, players[1].1]);
+    let err = svm.send_instruction(start_hand, &[&creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("MissingHandHistoryAccount"));
+}
+
+#[test]
+fn test_hand_history_records_blinds_actions_showdown_and_payout() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players, history_pda) = recording_table(&mut svm, &[5000, 5000]);
+    deal_recorded_hand(&mut svm, &creator, table_pda, &players, history_pda);
+    let caller = recorded_act(&mut svm, table_pda, &players, history_pda, PlayerActionType::Call);
+
+    let mut end_hand = build_end_hand_ix(table_pda, players[1].1, 1);
+    end_hand.accounts.push(AccountMeta::new(history_pda, false));
+    end_hand.accounts.push(AccountMeta::new(players[0].1, false));
+    svm.send_instruction(end_hand, &[&creator]).unwrap().assert_success();
+
+    let history: HandHistory = read_account(&svm, &history_pda);
+    assert_eq!((history.table, history.hand_number, history.truncated), (table_pda, 1, false));
+    let blinds: Vec<u64> = history.actions.iter().filter_map(|action| match action {
+        HandAction::Blind { amount, .. } => Some(*amount),
+        _ => None,
+    }).collect();
+    assert_eq!(blinds, vec![SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT]);
+    assert!(history.actions.contains(&HandAction::Action {
+        seat: caller,
+        action: PlayerActionType::Call,
+        amount: BIG_BLIND_DEFAULT - SMALL_BLIND_DEFAULT,
+    }));
+
+    // Both players reached showdown without their cards ever being revealed
+    assert_eq!(
+        history.actions[history.actions.len() - 3..],
+        [
+            HandAction::Showdown { seat: 0, cards: None },
+            HandAction::Showdown { seat: 1, cards: None },
+            HandAction::Payout { seat: 1, amount: 2 * BIG_BLIND_DEFAULT },
+        ],
+    );
+
+    // Recording stopped with the hand, so the log can be closed for its rent
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!(table.hand_history, Pubkey::default());
+    let result = svm.send_instruction(build_hand_history_ix("close_hand_history", table_pda, &creator, 1), &[&creator])
+        .unwrap();
+    let closed = decode_events::<HandHistoryClosed>(result.logs());
+    assert_eq!((closed[0].hand_history, closed[0].hand_number), (history_pda, 1));
+    assert!(svm.get_account(&history_pda).is_none_or(|account| account.lamports == 0));
+}
+
+#[test]
+fn test_hand_history_records_no_showdown_for_uncontested_pot() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players, history_pda) = recording_table(&mut svm, &[5000, 5000]);
+    deal_recorded_hand(&mut svm, &creator, table_pda, &players, history_pda);
+    let folder = recorded_act(&mut svm, table_pda, &players, history_pda, PlayerActionType::Fold);
+    let winner = 1 - folder;
+
+    let mut remaining: Vec<Pubkey> = players.iter().map(|(_, player_pda)| *player_pda).collect();
+    remaining.push(history_pda);
+    svm.send_instruction(build_check_auto_win_ix(table_pda, remaining), &[&creator]).unwrap().assert_success();
+
+    let history: HandHistory = read_account(&svm, &history_pda);
+    assert!(!history.actions.iter().any(|action| matches!(action, HandAction::Showdown { .. })));
+    assert!(matches!(history.actions.last(), Some(HandAction::Payout { seat, .. }) if *seat == winner));
+}
+
+#[test]
+fn test_hand_history_deals_in_only_players_not_sitting_out() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players, history_pda) = recording_table(&mut svm, &[5000, 5000, 5000]);
+    svm.send_instruction(build_sit_ix("sit_out", table_pda, &players[2].0), &[&players[2].0]).unwrap().assert_success();
+    deal_recorded_hand(&mut svm, &creator, table_pda, &players, history_pda);
+
+    let history: HandHistory = read_account(&svm, &history_pda);
+    let dealt: Vec<u8> = history.actions.iter().filter_map(|action| match action {
+        HandAction::Dealt { seat, .. } => Some(*seat),
+        _ => None,
+    }).collect();
+    assert_eq!(dealt, vec![0, 1]);
+}
+
+#[test]
+fn test_table_creation_is_reported() {
+    let mut svm = setup_svm();
+    let creator = funded_keypair(&mut svm);
+    let (table_pda, _) = derive_table_pda(1);
+    let result = svm.send_instruction(build_initialize_table_ix(
+        table_pda, &creator, 1,
+        SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT,
+        MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT,
+    ), &[&creator]).unwrap();
+    let created = decode_events::<TableCreated>(result.logs());
+    assert_eq!(created.len(), 1);
+    assert_eq!((created[0].table, created[0].creator), (table_pda, creator.pubkey()));
+    assert_eq!((created[0].big_blind, created[0].max_seats), (BIG_BLIND_DEFAULT, MAX_SEATS_DEFAULT));
+}
+
+#[test]
+fn test_joins_are_reported() {
+    let mut svm = setup_svm();
+    let (_, table_pda, _) = setup_table(&mut svm, &[]);
+    let player = funded_keypair(&mut svm);
+    let (player_pda, _) = derive_player_pda(&table_pda, &player.pubkey());
+    let result = svm.send_instruction(build_join_table_ix(table_pda, player_pda, &player, 5000, 3), &[&player]).unwrap();
+    let joined = decode_events::<PlayerJoined>(result.logs());
+    assert_eq!((joined[0].table, joined[0].player), (table_pda, player.pubkey()));
+    assert_eq!((joined[0].position, joined[0].stack), (3, 5000));
+}
+
+#[test]
+fn test_each_blind_reports_the_pot() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = setup_table(&mut svm, &[5000, 5000]);
+    let player_pdas = players.iter().map(|(_, pda)| *pda).collect();
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, player_pdas), &[&creator]).unwrap().assert_success();
+
+    let table: PokerTable = read_account(&svm, &table_pda);
+    let (small_blind, big_blind) = table.blind_seats().unwrap();
+    let mut pot = 0;
+    for (seat, amount) in [(small_blind, SMALL_BLIND_DEFAULT), (big_blind, BIG_BLIND_DEFAULT)] {
+        let (player, player_pda) = &players[seat as usize];
+        let result = svm.send_instruction(build_post_blinds_ix(table_pda, *player_pda, player), &[player]).unwrap();
+        let blind = &decode_events::<BlindPosted>(result.logs())[0];
+        pot += amount;
+        assert_eq!((blind.player, blind.position), (player.pubkey(), seat));
+        assert_eq!((blind.amount, blind.pot), (amount, pot));
+    }
+}
+
+#[test]
+fn test_actions_report_what_went_in_and_the_pot() {
+    let mut svm = setup_svm();
+    let (_, table_pda, players) = blinds_in(&mut svm);
+    let table: PokerTable = read_account(&svm, &table_pda);
+    let (caller, caller_pda) = &players[table.current_player_index as usize];
+    let result = svm.send_instruction(
+        build_player_action_ix(table_pda, *caller_pda, caller, PlayerActionType::Call, 0),
+        &[caller],
+    ).unwrap();
+    let action = &decode_events::<PlayerActioned>(result.logs())[0];
+    assert_eq!((action.player, action.action), (caller.pubkey(), PlayerActionType::Call));
+    assert_eq!(action.amount, BIG_BLIND_DEFAULT - SMALL_BLIND_DEFAULT);
+    assert_eq!((action.current_bet, action.pot), (BIG_BLIND_DEFAULT, 2 * BIG_BLIND_DEFAULT));
+}
+
+#[test]
+fn test_street_advances_are_reported() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, _) = blinds_in(&mut svm);
+    let result = svm.send_instruction(build_advance_street_ix(table_pda), &[&creator]).unwrap();
+    let street = &decode_events::<StreetAdvanced>(result.logs())[0];
+    assert_eq!((street.street, street.hand_number), (GameState::Flop, 1));
+    assert_eq!(street.pot, SMALL_BLIND_DEFAULT + BIG_BLIND_DEFAULT);
+}
+
+#[test]
+fn test_hand_completion_reports_the_payout() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = blinds_in(&mut svm);
+    // The payout is reported before the pot is cleared
+    let result = svm.send_instruction(build_end_hand_ix(table_pda, players[1].1, 1), &[&creator]).unwrap();
+    let complete = &decode_events::<HandComplete>(result.logs())[0];
+    assert_eq!((complete.winner, complete.position, complete.hand_number), (players[1].0.pubkey(), 1, 1));
+    assert_eq!((complete.pot, complete.rake), (SMALL_BLIND_DEFAULT + BIG_BLIND_DEFAULT, 0));
+}
+
+#[test]
+fn test_each_players_ante_is_reported() {
+    let mut svm = setup_svm();
+    let (creator, table, players) = setup_table(&mut svm, &[1000, 5000]);
+    let (blind_structure, _) = derive_blind_structure_pda(&table);
+    svm.send_instruction(build_set_blind_structure_ix(
+        table, blind_structure, &creator,
+        vec![BlindLevel { small_blind: 500, big_blind: 1000, ante: 1200 }],
+        LevelDurationUnit::Hands,
+        10,
+    ), &[&creator]).unwrap().assert_success();
+
+    // The short stack's ante is all they have
+    let remaining = vec![blind_structure, players[0].1, players[1].1];
+    let result = svm.send_instruction(build_start_hand_with_accounts_ix(table, remaining), &[&creator]).unwrap();
+    let started = &decode_events::<HandStarted>(result.logs())[0];
+    assert_eq!((started.ante, started.pot), (1200, 2200));
+    let antes: Vec<(Pubkey, u64)> = decode_events::<AntePosted>(result.logs())
+        .iter()
+        .map(|ante| (ante.player, ante.amount))
+        .collect();
+    assert_eq!(antes, vec![(players[0].0.pubkey(), 1000), (players[1].0.pubkey(), 1200)]);
 }
 
 #[test]
-fn test_player_fold() {
-    let mut svm = LiteSVM::new();
-    svm.add_program_from_file(
-        POKER_PROGRAM_ID,
-        "../../target/deploy/poker.so",
+fn test_tournament_settings_are_reported() {
+    let mut svm = setup_svm();
+    let sng = SitAndGo::create(&mut svm, 3, vec![10_000]);
+
+    let result = svm.send_instruction(
+        build_set_payout_structure_ix(sng.tournament, &sng.creator, vec![7_000, 3_000]),
+        &[&sng.creator],
     ).unwrap();
+    let payouts = &decode_events::<PayoutStructureSet>(result.logs())[0];
+    assert_eq!((payouts.tournament, payouts.payout_bps.clone()), (sng.tournament, vec![7_000, 3_000]));
 
-    // Setup table with 2 players and start hand
-    let creator = Keypair::new();
-    svm.airdrop(&creator.pubkey(), 10 * SOL).unwrap();
+    let result = svm.send_instruction(
+        build_set_tournament_bounty_ix(sng.tournament, &sng.creator, BountyType::Progressive, 5_000),
+        &[&sng.creator],
+    ).unwrap();
+    let bounty = &decode_events::<TournamentBountySet>(result.logs())[0];
+    assert_eq!((bounty.bounty_type, bounty.bounty_bps), (BountyType::Progressive, 5_000));
 
-    let table_id = 1u64;
-    let (table_pda, _) = derive_table_pda(table_id);
+    let result = svm.send_instruction(
+        build_set_tournament_rebuys_ix(sng.tournament, &sng.creator, SOL, 1500, 2, 1, SOL, 3000),
+        &[&sng.creator],
+    ).unwrap();
+    let rebuys = &decode_events::<TournamentRebuysSet>(result.logs())[0];
+    assert_eq!((rebuys.rebuy_fee, rebuys.rebuy_chips, rebuys.max_rebuys), (SOL, 1500, 2));
+    assert_eq!((rebuys.rebuy_period_levels, rebuys.addon_fee, rebuys.addon_chips), (1, SOL, 3000));
+}
 
-    let init_ix = build_initialize_table_ix(
-        table_pda,
-        &creator,
-        table_id,
-        SMALL_BLIND_DEFAULT,
-        BIG_BLIND_DEFAULT,
-        MIN_BUY_IN_DEFAULT,
-        MAX_BUY_IN_DEFAULT,
-    );
-    svm.send_instruction(init_ix, &[&creator]).unwrap();
+// The view is read-only and returns its answer as return data
+fn legal_actions_view(
+    svm: &mut LiteSVM,
+    table: Pubkey,
+    player_pda: Pubkey,
+) -> std::result::Result<LegalActionsView, String> {
+    let payer = funded_keypair(svm);
+    let result = svm
+        .send_instruction(build_legal_actions_ix(table, player_pda), &[&payer])
+        .map_err(|err| format!("{:?}", err))?;
+    Ok(LegalActionsView::deserialize(&mut &result.inner().return_data.data[..]).unwrap())
+}
 
-    let player1 = Keypair::new();
-    let player2 = Keypair::new();
-    svm.airdrop(&player1.pubkey(), 10 * SOL).unwrap();
-    svm.airdrop(&player2.pubkey(), 10 * SOL).unwrap();
+// A 5000 stack and a 1000 stack with the blinds in, and the player to act
+fn short_stack_hand(svm: &mut LiteSVM) -> (Pubkey, PokerTable, PlayerState, Pubkey) {
+    let (creator, table_pda, players) = setup_table(svm, &[5000, 1000]);
+    start_with_blinds(svm, &creator, table_pda, &players);
+    let table: PokerTable = read_account(svm, &table_pda);
+    let (_, to_act_pda) = &players[table.current_player_index as usize];
+    (table_pda, table, read_account(svm, to_act_pda), *to_act_pda)
+}
 
-    let (player1_pda, _) = derive_player_pda(&table_pda, &player1.pubkey());
-    let (player2_pda, _) = derive_player_pda(&table_pda, &player2.pubkey());
+#[test]
+fn test_legal_actions_view_needs_a_hand_in_progress() {
+    let mut svm = setup_svm();
+    let (_, table_pda, players) = setup_table(&mut svm, &[5000, 1000]);
+    let err = legal_actions_view(&mut svm, table_pda, players[0].1).unwrap_err();
+    assert!(err.contains("WrongGameState"));
+}
 
-    let join1_ix = build_join_table_ix(table_pda, player1_pda, &player1, 5000, 0);
-    let join2_ix = build_join_table_ix(table_pda, player2_pda, &player2, 5000, 1);
-    svm.send_instruction(join1_ix, &[&player1]).unwrap();
-    svm.send_instruction(join2_ix, &[&player2]).unwrap();
+#[test]
+fn test_legal_actions_view_only_answers_for_the_player_to_act() {
+    let mut svm = setup_svm();
+    let (table_pda, table, to_act, _) = short_stack_hand(&mut svm);
+    let waiting = table.players[1 - to_act.position as usize];
+    let (waiting_pda, _) = derive_player_pda(&table_pda, &waiting);
+    let err = legal_actions_view(&mut svm, table_pda, waiting_pda).unwrap_err();
+    assert!(err.contains("NotYourTurn"));
+}
 
-    let start_ix = build_start_hand_ix(table_pda, &creator);
-    svm.send_instruction(start_ix, &[&creator]).unwrap();
+#[test]
+fn test_legal_actions_view_reports_call_and_raise_range() {
+    let mut svm = setup_svm();
+    let (table_pda, table, player_state, to_act_pda) = short_stack_hand(&mut svm);
+    let view = legal_actions_view(&mut svm, table_pda, to_act_pda).unwrap();
+
+    assert_eq!(view.position, table.current_player_index);
+    assert!(view.can_fold && view.can_raise && !view.missed_blinds_due);
+    assert_eq!(view.can_check, player_state.current_bet == table.current_bet);
+    assert_eq!(view.call_amount, table.current_bet - player_state.current_bet);
+    assert_eq!(view.min_raise, BIG_BLIND_DEFAULT);
+    assert_eq!(view.max_raise, player_state.stack + player_state.current_bet - table.current_bet);
+}
 
-    // Determine who acts first
-    let table_account = svm.get_account(&table_pda).unwrap();
-    let table_data = &table_account.data[8..];
-    let table: PokerTable = AnchorDeserialize::deserialize(&mut &table_data[..]).unwrap();
+#[test]
+fn test_legal_actions_view_matches_the_off_chain_answer() {
+    let mut svm = setup_svm();
+    let (table_pda, table, player_state, to_act_pda) = short_stack_hand(&mut svm);
+    let view = legal_actions_view(&mut svm, table_pda, to_act_pda).unwrap();
+    assert_eq!(table.legal_actions(&player_state).unwrap(), view);
+}
 
-    let current_player_pubkey = table.players[table.current_player_index as usize];
-    let (acting_player, acting_player_pda) = if current_player_pubkey == player1.pubkey() {
-        (&player1, player1_pda)
-    } else {
-        (&player2, player2_pda)
-    };
+/// A heads-up hand with a 30 second action timeout, blinds posted.
+/// Returns the creator, the table, the players and the table as the hand stands.
+fn timed_hand(svm: &mut LiteSVM) -> (Keypair, Pubkey, Vec<(Keypair, Pubkey)>, PokerTable) {
+    let (creator, table_pda, players) = setup_table(svm, &[5000, 5000]);
+    let timeout = TableConfigUpdate { action_timeout: Some(30), ..Default::default() };
+    svm.send_instruction(build_update_table_config_ix(table_pda, &creator, timeout), &[&creator])
+        .unwrap()
+        .assert_success();
+    svm.send_instruction(
+        build_start_hand_with_accounts_ix(table_pda, vec![players[0].1, players[1].1]),
+        &[&creator],
+    ).unwrap().assert_success();
+    post_blinds(svm, table_pda, &players);
+    let table: PokerTable = read_account(svm, &table_pda);
+    (creator, table_pda, players, table)
+}
 
-    // Player folds
-    let fold_ix = build_player_action_ix(
-        table_pda,
-        acting_player_pda,
-        acting_player,
-        PlayerActionType::Fold,
-        0,
-    );
-    let result = svm.send_instruction(fold_ix, &[acting_player]).unwrap();
-    result.assert_success();
+/// Move the clock to `seconds` after the player to act was given the turn
+fn wait_for_turn(svm: &mut LiteSVM, table: &PokerTable, seconds: i64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = table.turn_started_at + seconds;
+    svm.set_sysvar(&clock);
+}
 
-    // Verify player status changed
-    let player_account = svm.get_account(&acting_player_pda).unwrap();
-    let player_data = &player_account.data[8..];
-    let player_state: PlayerState = AnchorDeserialize::deserialize(&mut &player_data[..]).unwrap();
+#[test]
+fn test_crank_timeout_is_rejected_before_the_timeout() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players, table) = timed_hand(&mut svm);
+    let to_act_pda = players[table.current_player_index as usize].1;
+    wait_for_turn(&mut svm, &table, 29);
+
+    let crank = build_crank_timeout_ix(table_pda, to_act_pda, vec![players[0].1, players[1].1]);
+    let err = svm.send_instruction(crank, &[&creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("ActionNotTimedOut"));
+}
+
+#[test]
+fn test_crank_timeout_needs_every_dealt_in_player_state() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players, table) = timed_hand(&mut svm);
+    let to_act_pda = players[table.current_player_index as usize].1;
+    wait_for_turn(&mut svm, &table, 30);
+
+    let crank = build_crank_timeout_ix(table_pda, to_act_pda, vec![to_act_pda]);
+    let err = svm.send_instruction(crank, &[&creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("MissingPlayerAccounts"));
+}
 
+#[test]
+fn test_crank_timeout_folds_player_facing_a_bet() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players, table) = timed_hand(&mut svm);
+    let to_act_pda = players[table.current_player_index as usize].1;
+    let other_pda = players[1 - table.current_player_index as usize].1;
+    let to_act: PlayerState = read_account(&svm, &to_act_pda);
+    assert!(to_act.current_bet < table.current_bet);
+    wait_for_turn(&mut svm, &table, 30);
+
+    let crank = build_crank_timeout_ix(table_pda, to_act_pda, vec![players[0].1, players[1].1]);
+    svm.send_instruction(crank, &[&creator]).unwrap().assert_success();
+
+    let player_state: PlayerState = read_account(&svm, &to_act_pda);
     assert_eq!(player_state.status, PlayerStatus::Folded);
+    let other: PlayerState = read_account(&svm, &other_pda);
+    assert_eq!(other.status, PlayerStatus::Active);
 }
 
-// ========== NEW GAME LOGIC TESTS ==========
+#[test]
+fn test_action_timeout_is_capped() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, _players) = setup_table(&mut svm, &[5000, 5000]);
+
+    let too_long = TableConfigUpdate { action_timeout: Some(MAX_ACTION_TIMEOUT + 1), ..Default::default() };
+    let err = svm.send_instruction(build_update_table_config_ix(table_pda, &creator, too_long), &[&creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("InvalidTableConfig"));
+
+    let longest = TableConfigUpdate { action_timeout: Some(MAX_ACTION_TIMEOUT), ..Default::default() };
+    svm.send_instruction(build_update_table_config_ix(table_pda, &creator, longest), &[&creator]).unwrap().assert_success();
+}
 
 #[test]
 fn test_blind_posting_with_bitmask() {
@@ -545,6 +3047,27 @@ fn test_blind_posting_with_bitmask() {
     // by the successful blind posts above and the bitmask tracking.
 }
 
+#[test]
+fn test_all_in_short_of_a_min_raise_is_too_small() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = setup_table(&mut svm, &[1000, 5000]);
+    start_with_blinds(&mut svm, &creator, table_pda, &players);
+
+    // The deep stack on the button raises 900, to 920
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!(table.current_player_index, 1);
+    act(&mut svm, table_pda, &players, PlayerActionType::Raise, 900).unwrap();
+
+    // 80 more is all the short stack has, less than a full raise, so it can only call
+    let short_raise = all_in_raise(&svm, table_pda, &players);
+    assert_eq!(short_raise, 80);
+    let err = act(&mut svm, table_pda, &players, PlayerActionType::Raise, short_raise).unwrap_err();
+    assert!(err.contains("RaiseTooSmall"));
+    act(&mut svm, table_pda, &players, PlayerActionType::Call, 0).unwrap();
+    let short: PlayerState = read_account(&svm, &players[0].1);
+    assert_eq!((short.stack, short.status), (0, PlayerStatus::AllIn));
+}
+
 #[test]
 fn test_raise_validation_with_min_raise() {
     let mut svm = LiteSVM::new();
@@ -577,17 +3100,12 @@ fn test_raise_validation_with_min_raise() {
 
     svm.send_instruction(build_start_hand_ix(table_pda, &creator), &[&creator]).unwrap();
 
-    // Get blind positions - button at 1, SB at 0, BB at 1 with 2 players
-    let table_account = svm.get_account(&table_pda).unwrap();
-    let table_data = &table_account.data[8..];
-    let table: PokerTable = AnchorDeserialize::deserialize(&mut &table_data[..]).unwrap();
-
-    // With 2 players: button at 1, so SB at 0 (player1), BB at 1 (player2)
+    // Heads-up the button (seat 1, player2) posts the small blind and player1 the big
     // Post blinds
     svm.send_instruction(build_post_blinds_ix(table_pda, player1_pda, &player1), &[&player1]).unwrap();
     svm.send_instruction(build_post_blinds_ix(table_pda, player2_pda, &player2), &[&player2]).unwrap();
 
-    // Get current player (should be player1 in heads-up)
+    // Get current player (the button acts first preflop heads-up)
     let table_account = svm.get_account(&table_pda).unwrap();
     let table_data = &table_account.data[8..];
     let table: PokerTable = AnchorDeserialize::deserialize(&mut &table_data[..]).unwrap();
@@ -676,6 +3194,24 @@ fn test_check_auto_win_detects_single_active_player() {
     assert!(result.is_ok(), "check_auto_win instruction should succeed");
 }
 
+#[test]
+fn test_check_auto_win_pays_a_hand_once() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = blinds_in(&mut svm);
+    act(&mut svm, table_pda, &players, PlayerActionType::Fold, 0).unwrap();
+    let player_states: Vec<Pubkey> = players.iter().map(|(_, pda)| *pda).collect();
+    svm.send_instruction(build_check_auto_win_ix(table_pda, player_states.clone()), &[&creator]).unwrap().assert_success();
+    let stacks: Vec<u64> = players.iter().map(|(_, pda)| read_account::<PlayerState>(&svm, pda).stack).collect();
+
+    // The hand is over, so calling again can't pay the winner a second time
+    svm.expire_blockhash();
+    let err = svm.send_instruction(build_check_auto_win_ix(table_pda, player_states), &[&creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("WrongGameState"));
+    for ((_, pda), stack) in players.iter().zip(stacks) {
+        assert_eq!(read_account::<PlayerState>(&svm, pda).stack, stack);
+    }
+}
+
 #[test]
 fn test_advance_street_auto_validates_betting_round() {
     let mut svm = LiteSVM::new();
@@ -766,6 +3302,963 @@ fn test_start_hand_resets_player_states() {
     assert_eq!(table.game_state, GameState::PreFlop);
 }
 
+#[test]
+fn test_start_hand_rewrites_player_states_in_place() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players) = setup_table(&mut svm, &[5000, 5000]);
+    let player_states: Vec<Pubkey> = players.iter().map(|(_, player_pda)| *player_pda).collect();
+
+    // Hand 1: whoever acts first folds
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, player_states.clone()), &[&creator]).unwrap().assert_success();
+    post_blinds(&mut svm, table_pda, &players);
+    let table: PokerTable = read_account(&svm, &table_pda);
+    let folder = table.current_player_index as usize;
+    act(&mut svm, table_pda, &players, PlayerActionType::Fold, 0).unwrap();
+    svm.send_instruction(build_check_auto_win_ix(table_pda, player_states.clone()), &[&creator]).unwrap().assert_success();
+
+    // Hand 2 resets the folded player; the state is written back over the whole
+    // account, discriminator included, so it still decodes
+    svm.expire_blockhash();
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, player_states), &[&creator]).unwrap().assert_success();
+    let account = svm.get_account(&players[folder].1).unwrap();
+    assert!(account.data.starts_with(PlayerState::DISCRIMINATOR));
+    let state: PlayerState = read_account(&svm, &players[folder].1);
+    assert_eq!(state.status, PlayerStatus::Active);
+    assert_eq!(state.current_bet, 0);
+    assert_eq!(state.player, players[folder].0.pubkey());
+    assert_eq!(state.table, table_pda);
+}
+
+// Two 5000 stacks at a table moving up a level every hand: 10/20, then 20/40 with a 5 ante
+fn blind_structure_table(svm: &mut LiteSVM) -> (Keypair, Pubkey, Vec<(Keypair, Pubkey)>, Vec<Pubkey>) {
+    let (creator, table_pda, players) = setup_table(svm, &[5000, 5000]);
+    let (blind_structure_pda, _) = derive_blind_structure_pda(&table_pda);
+    svm.send_instruction(build_set_blind_structure_ix(
+        table_pda, blind_structure_pda, &creator,
+        vec![
+            BlindLevel { small_blind: 10, big_blind: 20, ante: 0 },
+            BlindLevel { small_blind: 20, big_blind: 40, ante: 5 },
+        ],
+        LevelDurationUnit::Hands,
+        1,
+    ), &[&creator]).unwrap().assert_success();
+
+    let mut remaining = vec![blind_structure_pda];
+    remaining.extend(players.iter().map(|(_, pda)| *pda));
+    (creator, table_pda, players, remaining)
+}
+
+// Hand 1 at the first level: player2 (button, small blind) folds to player1
+fn play_first_level(
+    svm: &mut LiteSVM,
+    creator: &Keypair,
+    table: Pubkey,
+    players: &[(Keypair, Pubkey)],
+    remaining: &[Pubkey],
+) {
+    svm.send_instruction(build_start_hand_with_accounts_ix(table, remaining.to_vec()), &[creator]).unwrap().assert_success();
+    post_blinds(svm, table, players);
+    act(svm, table, players, PlayerActionType::Fold, 0).unwrap();
+    svm.send_instruction(build_end_hand_ix(table, players[0].1, 0), &[creator]).unwrap().assert_success();
+    svm.expire_blockhash();
+}
+
+#[test]
+fn test_first_hand_plays_at_the_first_level() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, _, remaining) = blind_structure_table(&mut svm);
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, remaining.clone()), &[&creator]).unwrap().assert_success();
+
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!((table.small_blind, table.big_blind, table.ante), (10, 20, 0));
+    assert_eq!(read_account::<BlindStructure>(&svm, &remaining[0]).current_level, 0);
+}
+
+#[test]
+fn test_blind_structure_moves_up_a_level_on_schedule() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players, remaining) = blind_structure_table(&mut svm);
+    play_first_level(&mut svm, &creator, table_pda, &players, &remaining);
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, remaining.clone()), &[&creator]).unwrap().assert_success();
+
+    let table: PokerTable = read_account(&svm, &table_pda);
+    assert_eq!((table.small_blind, table.big_blind, table.ante), (20, 40, 5));
+    assert_eq!(table.current_bet, 40);
+    let blind_structure: BlindStructure = read_account(&svm, &remaining[0]);
+    assert_eq!((blind_structure.current_level, blind_structure.level_started_hand), (1, 2));
+}
+
+#[test]
+fn test_antes_are_collected_when_the_hand_starts() {
+    let mut svm = setup_svm();
+    let (creator, table_pda, players, remaining) = blind_structure_table(&mut svm);
+    play_first_level(&mut svm, &creator, table_pda, &players, &remaining);
+    svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, remaining), &[&creator]).unwrap().assert_success();
+
+    assert_eq!(read_account::<PokerTable>(&svm, &table_pda).pot, 10);
+    // player1 won player2's 10 chip small blind in hand 1
+    assert_eq!(read_account::<PlayerState>(&svm, &players[0].1).stack, 5000 + 10 - 5);
+    assert_eq!(read_account::<PlayerState>(&svm, &players[1].1).stack, 5000 - 10 - 5);
+}
+
+#[test]
+fn test_short_stack_posts_big_blind_all_in_after_ante() {
+    let mut svm = setup_svm();
+    let (creator, table, players) = setup_table(&mut svm, &[1000, 5000]);
+    let (blind_structure, _) = derive_blind_structure_pda(&table);
+    svm.send_instruction(build_set_blind_structure_ix(
+        table, blind_structure, &creator,
+        vec![BlindLevel { small_blind: 500, big_blind: 1000, ante: 100 }],
+        LevelDurationUnit::Hands,
+        10,
+    ), &[&creator]).unwrap().assert_success();
+
+    let remaining = vec![blind_structure, players[0].1, players[1].1];
+    svm.send_instruction(build_start_hand_with_accounts_ix(table, remaining), &[&creator]).unwrap().assert_success();
+
+    // player1 has 900 left after the ante, so their big blind is all in for 900
+    let (player1, player1_pda) = &players[0];
+    let (player2, player2_pda) = &players[1];
+    svm.send_instruction(build_post_blinds_ix(table, *player2_pda, player2), &[player2]).unwrap().assert_success();
+    let result = svm.send_instruction(build_post_blinds_ix(table, *player1_pda, player1), &[player1]).unwrap();
+    result.assert_success();
+    let posted = decode_events::<BlindPosted>(result.logs());
+    assert_eq!(posted.len(), 1);
+    assert_eq!(posted[0].amount, 900);
+
+    let state: PlayerState = read_account(&svm, player1_pda);
+    assert_eq!((state.stack, state.current_bet, state.status), (0, 900, PlayerStatus::AllIn));
+    let table_state: PokerTable = read_account(&svm, &table);
+    assert_eq!(table_state.pot, 200 + 500 + 900);
+    assert_eq!(table_state.current_player_index, 1);
+
+    // The small blind still completes to the full big blind
+    act(&mut svm, table, &players, PlayerActionType::Call, 0).unwrap();
+    let state: PlayerState = read_account(&svm, player2_pda);
+    assert_eq!(state.stack, 5000 - 100 - 1000);
+}
+
+// Start the second-level hand with antes, dealing in `remaining` as the player states
+fn start_ante_hand(svm: &mut LiteSVM, remaining: impl Fn(&[Pubkey]) -> Vec<Pubkey>) -> (Vec<(Keypair, Pubkey)>, String) {
+    let (creator, table_pda, players, accounts) = blind_structure_table(svm);
+    play_first_level(svm, &creator, table_pda, &players, &accounts);
+    let err = svm.send_instruction(build_start_hand_with_accounts_ix(table_pda, remaining(&accounts)), &[&creator]).unwrap_err();
+    (players, format!("{:?}", err))
+}
+
+#[test]
+fn test_antes_are_not_taken_from_another_tables_player() {
+    let mut svm = setup_svm();
+    let other_creator = funded_keypair(&mut svm);
+    let (other_table, _) = derive_table_pda(2);
+    svm.send_instruction(build_initialize_table_ix(
+        other_table, &other_creator, 2,
+        SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT,
+        MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT,
+    ), &[&other_creator]).unwrap().assert_success();
+    let other_player = funded_keypair(&mut svm);
+    let (other_state, _) = derive_player_pda(&other_table, &other_player.pubkey());
+    svm.send_instruction(build_join_table_ix(other_table, other_state, &other_player, 5000, 1), &[&other_player])
+        .unwrap().assert_success();
+
+    // The other table's seat 1 stands in for player2
+    let (_, err) = start_ante_hand(&mut svm, |accounts| vec![accounts[0], accounts[1], other_state]);
+    assert!(err.contains("MissingPlayerAccounts"));
+    assert_eq!(read_account::<PlayerState>(&svm, &other_state).stack, 5000);
+}
+
+#[test]
+fn test_antes_are_not_taken_twice_from_a_duplicated_player() {
+    let mut svm = setup_svm();
+    let (players, err) = start_ante_hand(&mut svm, |accounts| vec![accounts[0], accounts[1], accounts[1], accounts[2]]);
+    assert!(err.contains("DuplicatePlayerAccount"));
+    assert_eq!(read_account::<PlayerState>(&svm, &players[0].1).stack, 5000 + 10);
+}
+
+#[test]
+fn test_antes_need_every_dealt_in_players_state() {
+    let mut svm = setup_svm();
+    let (players, err) = start_ante_hand(&mut svm, |accounts| vec![accounts[0], accounts[1]]);
+    assert!(err.contains("MissingPlayerAccounts"));
+    assert_eq!(read_account::<PlayerState>(&svm, &players[0].1).stack, 5000 + 10);
+}
+
+#[test]
+fn test_blind_structure_can_be_replaced_and_removed() {
+    let mut svm = setup_svm();
+    let (creator, table, players) = setup_table(&mut svm, &[5000, 5000]);
+    let (blind_structure, _) = derive_blind_structure_pda(&table);
+    svm.send_instruction(build_set_blind_structure_ix(
+        table, blind_structure, &creator,
+        vec![BlindLevel { small_blind: 10, big_blind: 20, ante: 0 }],
+        LevelDurationUnit::Hands,
+        10,
+    ), &[&creator]).unwrap().assert_success();
+
+    // Setting it again replaces the schedule in place
+    svm.send_instruction(build_set_blind_structure_ix(
+        table, blind_structure, &creator,
+        vec![
+            BlindLevel { small_blind: 25, big_blind: 50, ante: 5 },
+            BlindLevel { small_blind: 50, big_blind: 100, ante: 10 },
+        ],
+        LevelDurationUnit::Hands,
+        5,
+    ), &[&creator]).unwrap().assert_success();
+    let structure: BlindStructure = read_account(&svm, &blind_structure);
+    assert_eq!((structure.levels.len(), structure.level_duration), (2, 5));
+    let table_state: PokerTable = read_account(&svm, &table);
+    assert_eq!((table_state.small_blind, table_state.big_blind, table_state.ante), (25, 50, 5));
+
+    // Only the table authority can remove it
+    let (player1, _) = &players[0];
+    assert!(svm.send_instruction(
+        build_remove_blind_structure_ix(table, blind_structure, player1),
+        &[player1],
+    ).is_err());
+
+    // Removing it closes the account and leaves the current level as fixed blinds
+    svm.send_instruction(
+        build_remove_blind_structure_ix(table, blind_structure, &creator),
+        &[&creator],
+    ).unwrap().assert_success();
+    assert!(svm.get_account(&blind_structure).is_none_or(|account| account.data.is_empty()));
+    let table_state: PokerTable = read_account(&svm, &table);
+    assert_eq!(table_state.blind_structure, Pubkey::default());
+    assert_eq!((table_state.small_blind, table_state.big_blind), (25, 50));
+
+    let remaining = vec![players[0].1, players[1].1];
+    svm.send_instruction(build_start_hand_with_accounts_ix(table, remaining), &[&creator]).unwrap().assert_success();
+}
+
+// ========== TOURNAMENT TESTS ==========
+
+#[test]
+fn test_sit_and_go_starts_when_registration_fills() {
+    let mut svm = setup_svm();
+    let mut sng = SitAndGo::create(&mut svm, 2, vec![10_000]);
+    sng.register(&mut svm).unwrap();
+
+    // Cash-game joins are rejected on a tournament table
+    let outsider = funded_keypair(&mut svm);
+    let (outsider_pda, _) = derive_player_pda(&sng.table, &outsider.pubkey());
+    let join_ix = build_join_table_ix(sng.table, outsider_pda, &outsider, 1500, 5);
+    assert!(svm.send_instruction(join_ix, &[&outsider]).is_err());
+    assert_eq!(sng.state(&svm).status, TournamentStatus::Registering);
+
+    // Second registration fills the table and starts the tournament
+    sng.register(&mut svm).unwrap();
+    let tournament = sng.state(&svm);
+    assert_eq!(tournament.status, TournamentStatus::Running);
+    assert_eq!(tournament.prize_pool, 2 * SOL);
+    assert_eq!(tournament.players_remaining, 2);
+    let player1: PlayerState = read_account(&svm, &sng.players[0].1);
+    assert_eq!(player1.stack, 1500);
+
+    // The table is full
+    assert!(sng.register(&mut svm).is_err());
+}
+
+#[test]
+fn test_tournament_is_created_by_table_authority() {
+    let mut svm = setup_svm();
+    let (creator, table, _) = setup_table(&mut svm, &[]);
+    let (blind_structure, _) = derive_blind_structure_pda(&table);
+    svm.send_instruction(build_set_blind_structure_ix(
+        table, blind_structure, &creator,
+        vec![BlindLevel { small_blind: 10, big_blind: 20, ante: 0 }],
+        LevelDurationUnit::Hands,
+        10,
+    ), &[&creator]).unwrap().assert_success();
+
+    // Once the table is handed over, only its new authority can run a tournament on it
+    let manager = funded_keypair(&mut svm);
+    svm.send_instruction(build_set_table_authority_ix(table, &creator, manager.pubkey()), &[&creator])
+        .unwrap()
+        .assert_success();
+    let (tournament, _) = derive_tournament_pda(1);
+    for (signer, allowed) in [(&creator, false), (&manager, true)] {
+        let create = build_create_tournament_ix(tournament, table, signer, 1, SOL, 1500, 2, vec![10_000]);
+        assert_eq!(svm.send_instruction(create, &[signer]).is_ok(), allowed);
+    }
+    let state: Tournament = read_account(&svm, &tournament);
+    assert_eq!(state.creator, manager.pubkey());
+}
+
+#[test]
+fn test_unregister_refunds_entry_and_frees_seat() {
+    let mut svm = setup_svm();
+    let mut sng = SitAndGo::create(&mut svm, 3, vec![10_000]);
+    sng.register_all(&mut svm, 2);
+
+    let (player1, player1_pda) = (&sng.players[0].0.insecure_clone(), sng.players[0].1);
+    let balance_before = svm.get_balance(&player1.pubkey()).unwrap();
+    svm.send_instruction(
+        build_unregister_tournament_ix(sng.tournament, sng.table, player1),
+        &[player1],
+    ).unwrap().assert_success();
+    assert!(svm.get_balance(&player1.pubkey()).unwrap() > balance_before + SOL);
+    assert!(svm.get_account(&player1_pda).is_none_or(|account| account.data.is_empty()));
+
+    let tournament = sng.state(&svm);
+    assert_eq!(tournament.entries.len(), 1);
+    assert_eq!(tournament.prize_pool, SOL);
+    assert_eq!(tournament.table_counts, vec![1]);
+    let table: PokerTable = read_account(&svm, &sng.table);
+    assert_eq!(table.player_count, 1);
+    assert_eq!(table.players[0], Pubkey::default());
+
+    // Can't unregister twice, or once the tournament is under way
+    svm.expire_blockhash();
+    assert!(svm.send_instruction(
+        build_unregister_tournament_ix(sng.tournament, sng.table, player1),
+        &[player1],
+    ).is_err());
+    sng.register_all(&mut svm, 2);
+    assert_eq!(sng.state(&svm).status, TournamentStatus::Running);
+    let (player2, _) = &sng.players[1];
+    assert!(svm.send_instruction(
+        build_unregister_tournament_ix(sng.tournament, sng.table, player2),
+        &[player2],
+    ).is_err());
+}
+
+#[test]
+fn test_cancel_tournament_refunds_every_entry() {
+    let mut svm = setup_svm();
+    let mut sng = SitAndGo::create(&mut svm, 3, vec![10_000]);
+    sng.register_all(&mut svm, 2);
+    let wallets: Vec<Pubkey> = sng.players.iter().map(|(player, _)| player.pubkey()).collect();
+
+    // Only the creator (or program admin) can cancel, and every registrant is refunded
+    let (player1, player1_pda) = (&sng.players[0].0.insecure_clone(), sng.players[0].1);
+    assert!(svm.send_instruction(
+        build_cancel_tournament_ix(sng.tournament, player1, wallets.clone()),
+        &[player1],
+    ).is_err());
+    assert!(svm.send_instruction(
+        build_cancel_tournament_ix(sng.tournament, &sng.creator, wallets[..1].to_vec()),
+        &[&sng.creator],
+    ).is_err());
+
+    let balances: Vec<u64> = wallets.iter().map(|wallet| svm.get_balance(wallet).unwrap()).collect();
+    svm.send_instruction(
+        build_cancel_tournament_ix(sng.tournament, &sng.creator, wallets.clone()),
+        &[&sng.creator],
+    ).unwrap().assert_success();
+    for (wallet, before) in wallets.iter().zip(balances) {
+        assert_eq!(svm.get_balance(wallet).unwrap(), before + SOL);
+    }
+    let tournament = sng.state(&svm);
+    assert_eq!(tournament.status, TournamentStatus::Cancelled);
+    assert_eq!(tournament.prize_pool, 0);
+    assert!(sng.register(&mut svm).is_err());
+
+    // Registrants stand up once they show the tournament is over
+    let leave = build_leave_table_ix(sng.table, player1_pda, player1);
+    assert!(svm.send_instruction(leave.clone(), &[player1]).is_err());
+    for (player, player_pda) in &sng.players {
+        let mut leave = build_leave_table_ix(sng.table, *player_pda, player);
+        leave.accounts.push(AccountMeta::new_readonly(sng.tournament, false));
+        svm.send_instruction(leave, &[player]).unwrap().assert_success();
+    }
+
+    // With the table empty it can be closed
+    svm.send_instruction(
+        build_close_table_ix(sng.table, sng.creator.pubkey(), &sng.creator, vec![sng.tournament, sng.blind_structure]),
+        &[&sng.creator],
+    ).unwrap().assert_success();
+}
+
+#[test]
+fn test_busted_player_blocks_next_hand_until_eliminated() {
+    let mut svm = setup_svm();
+    let mut sng = SitAndGo::create(&mut svm, 2, vec![10_000]);
+    sng.register_all(&mut svm, 2);
+
+    sng.play_all_in_hand(&mut svm, 0);
+    let table: PokerTable = read_account(&svm, &sng.table);
+    assert_eq!(table.button_position, 1);
+    let player2: PlayerState = read_account(&svm, &sng.players[1].1);
+    assert_eq!(player2.stack, 0);
+
+    svm.expire_blockhash();
+    assert!(sng.start_hand(&mut svm).is_err());
+
+    sng.eliminate(&mut svm, 1).unwrap();
+    assert!(svm.get_account(&sng.players[1].1).is_none_or(|account| account.data.is_empty()));
+}
+
+#[test]
+fn test_tournament_hand_needs_every_player_state() {
+    let mut svm = setup_svm();
+    let mut sng = SitAndGo::create(&mut svm, 2, vec![10_000]);
+    sng.register_all(&mut svm, 2);
+    sng.play_all_in_hand(&mut svm, 0);
+
+    // Leaving out the busted player's state doesn't get around the elimination check
+    svm.expire_blockhash();
+    let start_hand = build_start_hand_with_accounts_ix(
+        sng.table,
+        vec![sng.tournament, sng.blind_structure, sng.players[0].1],
+    );
+    assert!(svm.send_instruction(start_hand, &[&sng.creator]).is_err());
+}
+
+#[test]
+fn test_tournament_hand_checks_the_states_it_is_given() {
+    let mut svm = setup_svm();
+    let mut sng = SitAndGo::create(&mut svm, 2, vec![10_000]);
+    sng.register_all(&mut svm, 2);
+    sng.play_all_in_hand(&mut svm, 0);
+    svm.expire_blockhash();
+
+    // Neither the winner's state twice nor the busted player's wallet stands in for their state
+    let (busted, _) = &sng.players[1];
+    let winner_pda = sng.players[0].1;
+    for (stand_in, error) in [(winner_pda, "DuplicatePlayerAccount"), (busted.pubkey(), "MissingPlayerAccounts")] {
+        let start_hand = build_start_hand_with_accounts_ix(
+            sng.table,
+            vec![sng.tournament, sng.blind_structure, winner_pda, stand_in],
+        );
+        let err = svm.send_instruction(start_hand, &[&sng.creator]).unwrap_err();
+        assert!(format!("{:?}", err).contains(error));
+    }
+}
+
+#[test]
+fn test_sit_and_go_winner_claims_prize_pool() {
+    let mut svm = setup_svm();
+    let mut sng = SitAndGo::create(&mut svm, 2, vec![10_000]);
+    sng.register_all(&mut svm, 2);
+    sng.play_all_in_hand(&mut svm, 0);
+    sng.eliminate(&mut svm, 1).unwrap();
+
+    let tournament = sng.state(&svm);
+    assert_eq!(tournament.status, TournamentStatus::Finished);
+    assert_eq!(tournament.entries[0].finish_place, 1);
+    assert_eq!(tournament.entries[1].finish_place, 2);
+
+    // Winner collects the whole prize pool; runner-up has nothing to claim
+    let (player1, _) = &sng.players[0];
+    let balance_before = svm.get_balance(&player1.pubkey()).unwrap();
+    svm.send_instruction(build_claim_tournament_payout_ix(sng.tournament, player1), &[player1]).unwrap().assert_success();
+    let balance_after = svm.get_balance(&player1.pubkey()).unwrap();
+    assert!(balance_after > balance_before + 2 * SOL - SOL / 100);
+
+    let (player2, _) = &sng.players[1];
+    assert!(svm.send_instruction(build_claim_tournament_payout_ix(sng.tournament, player2), &[player2]).is_err());
+}
+
+#[test]
+fn test_check_auto_win_records_bust_for_short_all_in_call() {
+    let mut svm = setup_svm();
+    let mut sng = SitAndGo::create(&mut svm, 2, vec![10_000]);
+    sng.register_all(&mut svm, 2);
+
+    // Hand 1: player2 folds the button, leaving player1 ten chips ahead
+    sng.start_hand(&mut svm).unwrap();
+    post_blinds(&mut svm, sng.table, &sng.players);
+    act(&mut svm, sng.table, &sng.players, PlayerActionType::Fold, 0).unwrap();
+    sng.check_auto_win(&mut svm).unwrap();
+
+    // Hand 2: player1 raises without going all in, player2 calls all in for less.
+    // player1 is the only Active player left, so check_auto_win ends the hand.
+    svm.expire_blockhash();
+    sng.start_hand(&mut svm).unwrap();
+    post_blinds(&mut svm, sng.table, &sng.players);
+    act(&mut svm, sng.table, &sng.players, PlayerActionType::Raise, 1480).unwrap();
+    act(&mut svm, sng.table, &sng.players, PlayerActionType::Call, 0).unwrap();
+    let player1: PlayerState = read_account(&svm, &sng.players[0].1);
+    let player2: PlayerState = read_account(&svm, &sng.players[1].1);
+    assert_eq!((player1.status, player2.status), (PlayerStatus::Active, PlayerStatus::AllIn));
+
+    // Without the tournament the bust couldn't be recorded, so the hand can't end
+    assert!(svm.send_instruction(
+        build_check_auto_win_ix(sng.table, sng.player_states()),
+        &[&sng.creator],
+    ).is_err());
+    sng.check_auto_win(&mut svm).unwrap();
+
+    let player2: PlayerState = read_account(&svm, &sng.players[1].1);
+    assert_eq!(player2.stack, 0);
+    assert_eq!(sng.state(&svm).entries[1].eliminated_by, Some(sng.players[0].0.pubkey()));
+
+    sng.eliminate(&mut svm, 1).unwrap();
+    assert_eq!(sng.state(&svm).status, TournamentStatus::Finished);
+}
+
+/// A tournament (id 7) on tables 1, 2, ... sharing one schedule of 10/20 blinds going
+/// to 20/40 after two hands, with a 1 SOL entry and 1500 chips
+struct MultiTable {
+    creator: Keypair,
+    tournament: Pubkey,
+    tables: Vec<Pubkey>,
+    /// Registered players with their state PDA and the index of their table
+    players: Vec<(Keypair, Pubkey, usize)>,
+}
+
+impl MultiTable {
+    /// A tournament on a single table; more are added with `add_table`
+    fn create(svm: &mut LiteSVM, max_entrants: u8) -> MultiTable {
+        let creator = funded_keypair(svm);
+        let table = MultiTable::new_table(svm, &creator, 1);
+        let (tournament, _) = derive_tournament_pda(7);
+        svm.send_instruction(build_create_tournament_ix(
+            tournament, table, &creator, 7,
+            SOL, 1500, max_entrants,
+            vec![7_000, 3_000],
+        ), &[&creator]).unwrap().assert_success();
+        MultiTable { creator, tournament, tables: vec![table], players: Vec::new() }
+    }
+
+    fn new_table(svm: &mut LiteSVM, creator: &Keypair, table_id: u64) -> Pubkey {
+        let (table, _) = derive_table_pda(table_id);
+        let (blind_structure, _) = derive_blind_structure_pda(&table);
+        svm.send_instruction(build_initialize_table_ix(
+            table, creator, table_id,
+            SMALL_BLIND_DEFAULT, BIG_BLIND_DEFAULT,
+            MIN_BUY_IN_DEFAULT, MAX_BUY_IN_DEFAULT,
+        ), &[creator]).unwrap().assert_success();
+        svm.send_instruction(build_set_blind_structure_ix(
+            table, blind_structure, creator,
+            vec![
+                BlindLevel { small_blind: 10, big_blind: 20, ante: 0 },
+                BlindLevel { small_blind: 20, big_blind: 40, ante: 0 },
+            ],
+            LevelDurationUnit::Hands,
+            2,
+        ), &[creator]).unwrap().assert_success();
+        table
+    }
+
+    fn add_table(&mut self, svm: &mut LiteSVM) {
+        let table = MultiTable::new_table(svm, &self.creator, self.tables.len() as u64 + 1);
+        svm.send_instruction(
+            build_add_tournament_table_ix(self.tournament, self.tables[0], table, &self.creator, &self.creator),
+            &[&self.creator],
+        ).unwrap().assert_success();
+        self.tables.push(table);
+    }
+
+    fn register(&mut self, svm: &mut LiteSVM, table_index: usize) -> std::result::Result<(), String> {
+        let player = funded_keypair(svm);
+        let table = self.tables[table_index];
+        let (player_pda, _) = derive_player_pda(&table, &player.pubkey());
+        svm.send_instruction(
+            build_register_tournament_ix(self.tournament, table, player_pda, &player),
+            &[&player],
+        ).map_err(|err| format!("{:?}", err))?;
+        self.players.push((player, player_pda, table_index));
+        Ok(())
+    }
+
+    /// Players at a table with their state PDAs, in seat order
+    fn seated(&self, table_index: usize) -> Vec<(Keypair, Pubkey)> {
+        self.players
+            .iter()
+            .filter(|(_, _, index)| *index == table_index)
+            .map(|(player, player_pda, _)| (player.insecure_clone(), *player_pda))
+            .collect()
+    }
+
+    /// Deal a hand at a table, which the small blind folds
+    fn play_folded_hand(&self, svm: &mut LiteSVM, table_index: usize) {
+        let table = self.tables[table_index];
+        let seated = self.seated(table_index);
+        let player_states: Vec<Pubkey> = seated.iter().map(|(_, player_pda)| *player_pda).collect();
+
+        svm.expire_blockhash();
+        let mut remaining = vec![self.tournament, derive_blind_structure_pda(&table).0];
+        remaining.extend(&player_states);
+        svm.send_instruction(build_start_hand_with_accounts_ix(table, remaining), &[&self.creator])
+            .unwrap()
+            .assert_success();
+        post_blinds(svm, table, &seated);
+        act(svm, table, &seated, PlayerActionType::Fold, 0).unwrap();
+        let mut remaining = vec![self.tournament];
+        remaining.extend(&player_states);
+        svm.send_instruction(build_check_auto_win_ix(table, remaining), &[&self.creator])
+            .unwrap()
+            .assert_success();
+    }
+
+    fn state(&self, svm: &LiteSVM) -> Tournament {
+        read_account(svm, &self.tournament)
+    }
+}
+
+#[test]
+fn test_registration_keeps_tables_even() {
+    let mut svm = setup_svm();
+    let mut mtt = MultiTable::create(&mut svm, 4);
+    mtt.add_table(&mut svm);
+
+    // The second player can't sit at table 1 while table 2 is empty
+    mtt.register(&mut svm, 0).unwrap();
+    assert!(mtt.register(&mut svm, 0).is_err());
+    for table_index in [1, 0, 1] {
+        mtt.register(&mut svm, table_index).unwrap();
+    }
+
+    let tournament = mtt.state(&svm);
+    assert_eq!(tournament.status, TournamentStatus::Running);
+    assert_eq!(tournament.table_counts, vec![2, 2]);
+}
+
+#[test]
+fn test_registration_needs_a_seat_for_every_entrant() {
+    let mut svm = setup_svm();
+    let seats = MAX_SEATS_DEFAULT;
+    let mut mtt = MultiTable::create(&mut svm, seats + 1);
+
+    // One table can't seat the whole field, so nobody can register yet
+    let err = mtt.register(&mut svm, 0).unwrap_err();
+    assert!(err.contains("NotEnoughSeats"), "{}", err);
+
+    mtt.add_table(&mut svm);
+    mtt.register(&mut svm, 0).unwrap();
+}
+
+#[test]
+fn test_breaking_a_table_moves_players_with_their_chips() {
+    let mut svm = setup_svm();
+    let mut mtt = MultiTable::create(&mut svm, 4);
+    mtt.add_table(&mut svm);
+    for table_index in [0, 1, 0, 1] {
+        mtt.register(&mut svm, table_index).unwrap();
+    }
+    let (table1, table2) = (mtt.tables[0], mtt.tables[1]);
+    let movers: Vec<Pubkey> = [0, 2].iter().map(|index| mtt.players[*index].0.pubkey()).collect();
+
+    // All four fit at one table, so table 1 can be broken into table 2, but only
+    // the tournament's creator decides who moves
+    let (player1, _, _) = &mtt.players[0];
+    assert!(svm.send_instruction(
+        build_move_tournament_player_ix(mtt.tournament, table1, table2, movers[0], player1),
+        &[player1],
+    ).is_err());
+    for player in &movers {
+        svm.send_instruction(
+            build_move_tournament_player_ix(mtt.tournament, table1, table2, *player, &mtt.creator),
+            &[&mtt.creator],
+        ).unwrap().assert_success();
+    }
+
+    let tournament = mtt.state(&svm);
+    assert_eq!(tournament.table_counts, vec![0, 4]);
+    assert!(tournament.entries.iter().all(|entry| entry.table_index == 1));
+    let table: PokerTable = read_account(&svm, &table2);
+    assert_eq!(table.player_count, 4);
+
+    // The moved player keeps their chips on the new table
+    let moved: PlayerState = read_account(&svm, &derive_player_pda(&table2, &movers[0]).0);
+    assert_eq!(moved.stack, 1500);
+    assert_eq!(moved.table, table2);
+    let (old_pda, _) = derive_player_pda(&table1, &movers[0]);
+    assert!(svm.get_account(&old_pda).is_none_or(|account| account.data.is_empty()));
+}
+
+#[test]
+fn test_tables_share_the_tournament_level_clock() {
+    let mut svm = setup_svm();
+    let mut mtt = MultiTable::create(&mut svm, 4);
+    mtt.add_table(&mut svm);
+    for table_index in [0, 1, 0, 1] {
+        mtt.register(&mut svm, table_index).unwrap();
+    }
+
+    // Levels last two hands counted across both tables: hands 1 and 2 are at 10/20...
+    mtt.play_folded_hand(&mut svm, 0);
+    mtt.play_folded_hand(&mut svm, 1);
+    let table2: PokerTable = read_account(&svm, &mtt.tables[1]);
+    assert_eq!(table2.big_blind, 20);
+
+    // ...and the third, dealt at table 1, moves the whole tournament up a level
+    mtt.play_folded_hand(&mut svm, 0);
+    let tournament = mtt.state(&svm);
+    assert_eq!((tournament.blind_level, tournament.hands_dealt), (1, 3));
+
+    // Table 2 is only on its second hand, but plays the same level as table 1
+    mtt.play_folded_hand(&mut svm, 1);
+    let table2: PokerTable = read_account(&svm, &mtt.tables[1]);
+    assert_eq!((table2.hand_number, table2.big_blind), (2, 40));
+    let table1: PokerTable = read_account(&svm, &mtt.tables[0]);
+    assert_eq!(table1.big_blind, 40);
+}
+
+/// Heads-up sit-and-go with `bps` of every entry put on the player's head
+fn bounty_sit_and_go(svm: &mut LiteSVM, bounty_type: BountyType, bps: u16) -> SitAndGo {
+    let mut sng = SitAndGo::create(svm, 2, vec![10_000]);
+    svm.send_instruction(
+        build_set_tournament_bounty_ix(sng.tournament, &sng.creator, bounty_type, bps),
+        &[&sng.creator],
+    ).unwrap().assert_success();
+    sng.register_all(svm, 2);
+    sng
+}
+
+#[test]
+fn test_progressive_knockout_splits_bounty_on_bust() {
+    let mut svm = setup_svm();
+    let sng = bounty_sit_and_go(&mut svm, BountyType::Progressive, 5_000);
+    assert_eq!(sng.state(&svm).entries[1].bounty, SOL / 2);
+
+    // player1 wins the all-in and busts player2
+    sng.play_all_in_hand(&mut svm, 0);
+
+    // Half of the bounty is paid, the other half goes on the eliminator's head
+    let tournament = sng.state(&svm);
+    assert_eq!(tournament.prize_pool, SOL);
+    assert_eq!(tournament.entries[1].bounty, 0);
+    assert_eq!(tournament.entries[1].eliminated_by, Some(sng.players[0].0.pubkey()));
+    assert_eq!(tournament.entries[0].bounties_won, SOL / 4);
+    assert_eq!(tournament.entries[0].bounty, SOL / 2 + SOL / 4);
+}
+
+#[test]
+fn test_standard_bounty_pays_in_full_on_bust() {
+    let mut svm = setup_svm();
+    let sng = bounty_sit_and_go(&mut svm, BountyType::Standard, 5_000);
+    sng.play_all_in_hand(&mut svm, 0);
+
+    let tournament = sng.state(&svm);
+    assert_eq!(tournament.entries[1].bounty, 0);
+    assert_eq!(tournament.entries[0].bounties_won, SOL / 2);
+    assert_eq!(tournament.entries[0].bounty, SOL / 2);
+}
+
+#[test]
+fn test_bust_is_not_skipped_by_passing_the_winner_twice() {
+    let mut svm = setup_svm();
+    let sng = bounty_sit_and_go(&mut svm, BountyType::Standard, 5_000);
+    sng.all_in_to_showdown(&mut svm);
+
+    // The winner's state in place of the busted player's can't hide the bust
+    let winner_pda = sng.players[0].1;
+    let end_hand = build_end_hand_with_accounts_ix(sng.table, winner_pda, 0, vec![sng.tournament, winner_pda, winner_pda]);
+    let err = svm.send_instruction(end_hand, &[&sng.creator]).unwrap_err();
+    assert!(format!("{:?}", err).contains("DuplicatePlayerAccount"));
+    assert_eq!(sng.state(&svm).entries[1].eliminated_by, None);
+}
+
+#[test]
+fn test_bounty_is_fixed_before_registration() {
+    let mut svm = setup_svm();
+    let mut sng = SitAndGo::create(&mut svm, 2, vec![10_000]);
+
+    // A bounty type needs a share of the entry, and none needs none
+    assert!(svm.send_instruction(
+        build_set_tournament_bounty_ix(sng.tournament, &sng.creator, BountyType::Standard, 0),
+        &[&sng.creator],
+    ).is_err());
+    assert!(svm.send_instruction(
+        build_set_tournament_bounty_ix(sng.tournament, &sng.creator, BountyType::None, 1_000),
+        &[&sng.creator],
+    ).is_err());
+
+    sng.register(&mut svm).unwrap();
+    assert!(svm.send_instruction(
+        build_set_tournament_bounty_ix(sng.tournament, &sng.creator, BountyType::Progressive, 5_000),
+        &[&sng.creator],
+    ).is_err());
+}
+
+#[test]
+fn test_knockout_winner_claims_prize_and_bounties() {
+    let mut svm = setup_svm();
+    let sng = bounty_sit_and_go(&mut svm, BountyType::Progressive, 5_000);
+    sng.play_all_in_hand(&mut svm, 0);
+    sng.eliminate(&mut svm, 1).unwrap();
+
+    // Winner takes the prize pool, the half-bounty won and their own grown bounty
+    let (player1, _) = &sng.players[0];
+    let balance_before = svm.get_balance(&player1.pubkey()).unwrap();
+    svm.send_instruction(build_claim_tournament_payout_ix(sng.tournament, player1), &[player1]).unwrap().assert_success();
+    let balance_after = svm.get_balance(&player1.pubkey()).unwrap();
+    assert!(balance_after > balance_before + 2 * SOL - SOL / 100);
+}
+
+/// Heads-up sit-and-go allowing one 1 SOL rebuy in the first level, then a 1 SOL add-on.
+/// player2 has busted to player1 in the first hand.
+fn rebuy_sit_and_go(svm: &mut LiteSVM) -> SitAndGo {
+    let mut sng = SitAndGo::create(svm, 2, vec![10_000]);
+    svm.send_instruction(
+        build_set_tournament_rebuys_ix(sng.tournament, &sng.creator, SOL, 1500, 1, 1, SOL, 3000),
+        &[&sng.creator],
+    ).unwrap().assert_success();
+    sng.register_all(svm, 2);
+    sng.play_all_in_hand(svm, 0);
+    sng
+}
+
+#[test]
+fn test_busted_player_rebuys_during_rebuy_period() {
+    let mut svm = setup_svm();
+    let sng = rebuy_sit_and_go(&mut svm);
+    let (player1, _) = &sng.players[0];
+    let (player2, player2_pda) = &sng.players[1];
+
+    // The add-on is only offered once rebuys close
+    assert!(svm.send_instruction(
+        build_tournament_purchase_ix("addon", sng.tournament, sng.table, player1),
+        &[player1],
+    ).is_err());
+
+    // Instead of being eliminated, player2 rebuys back in
+    svm.send_instruction(
+        build_tournament_purchase_ix("rebuy", sng.tournament, sng.table, player2),
+        &[player2],
+    ).unwrap().assert_success();
+    let state: PlayerState = read_account(&svm, player2_pda);
+    assert_eq!(state.stack, 1500);
+    let tournament = sng.state(&svm);
+    assert_eq!(tournament.prize_pool, 3 * SOL);
+    assert_eq!(tournament.entries[1].rebuys, 1);
+    assert_eq!(tournament.entries[1].eliminated_by, None);
+
+    // The per-player limit is enforced
+    svm.expire_blockhash();
+    assert!(svm.send_instruction(
+        build_tournament_purchase_ix("rebuy", sng.tournament, sng.table, player2),
+        &[player2],
+    ).is_err());
+}
+
+#[test]
+fn test_player_who_can_rebuy_is_eliminated_after_decision_window() {
+    let mut svm = setup_svm();
+    let sng = rebuy_sit_and_go(&mut svm);
+
+    // player2 still has a rebuy, so nobody else can knock them out yet
+    let err = sng.eliminate(&mut svm, 1).unwrap_err();
+    assert!(err.contains("RebuyStillAvailable"), "{}", err);
+
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = sng.state(&svm).entries[1].busted_at + REBUY_DECISION_SECONDS;
+    svm.set_sysvar(&clock);
+    svm.expire_blockhash();
+    sng.eliminate(&mut svm, 1).unwrap();
+    assert_eq!(sng.state(&svm).status, TournamentStatus::Finished);
+}
+
+#[test]
+fn test_busted_player_can_decline_rebuy() {
+    let mut svm = setup_svm();
+    let sng = rebuy_sit_and_go(&mut svm);
+    let (player2, player2_pda) = &sng.players[1];
+
+    // Signing their own elimination gives up the rebuy straight away
+    let mut eliminate = build_eliminate_player_ix(sng.tournament, sng.table, *player2_pda, player2.pubkey());
+    eliminate.accounts[3].is_signer = true;
+    svm.send_instruction(eliminate, &[player2]).unwrap().assert_success();
+    assert_eq!(sng.state(&svm).entries[1].finish_place, 2);
+}
+
+#[test]
+fn test_rebuys_need_a_fee() {
+    let mut svm = setup_svm();
+    let sng = SitAndGo::create(&mut svm, 2, vec![10_000]);
+    assert!(svm.send_instruction(
+        build_set_tournament_rebuys_ix(sng.tournament, &sng.creator, 0, 1500, 1, 1, 0, 0),
+        &[&sng.creator],
+    ).is_err());
+}
+
+#[test]
+fn test_icm_deal_pays_chip_leader_less_than_chip_chop() {
+    let mut svm = setup_svm();
+    let mut sng = SitAndGo::create(&mut svm, 2, vec![6_500, 3_500]);
+    sng.register_all(&mut svm, 2);
+
+    // player2 (button, small blind) folds, leaving stacks of 1510 and 1490
+    sng.start_hand(&mut svm).unwrap();
+    post_blinds(&mut svm, sng.table, &sng.players);
+    act(&mut svm, sng.table, &sng.players, PlayerActionType::Fold, 0).unwrap();
+    sng.check_auto_win(&mut svm).unwrap();
+
+    // Heads-up ICM: 0.7 SOL for second plus 1510/3000 of the 0.6 SOL difference
+    sng.propose_deal(&mut svm, 0, DealKind::Icm).unwrap();
+    let deal = sng.deal(&svm);
+    let stacks: Vec<u64> = deal.shares.iter().map(|share| share.stack).collect();
+    assert_eq!(stacks, vec![1510, 1490]);
+    let icm: Vec<u64> = deal.shares.iter().map(|share| share.amount).collect();
+    assert_eq!(icm.iter().sum::<u64>(), 2 * SOL);
+    assert!(icm[0].abs_diff(1_002_000_000) <= 2);
+
+    // Rejecting clears the deal so a chip chop can be proposed instead
+    let (player2, _) = &sng.players[1];
+    let result = svm.send_instruction(
+        build_reject_deal_ix(sng.tournament, sng.players[0].0.pubkey(), player2),
+        &[player2],
+    ).unwrap();
+    let rejected = decode_events::<DealRejected>(result.logs());
+    assert_eq!((rejected[0].tournament, rejected[0].player), (sng.tournament, player2.pubkey()));
+    sng.propose_deal(&mut svm, 0, DealKind::ChipChop).unwrap();
+    let chip_chop: Vec<u64> = sng.deal(&svm).shares.iter().map(|share| share.amount).collect();
+    assert_eq!(chip_chop.iter().sum::<u64>(), 2 * SOL);
+    assert!(icm[0] < chip_chop[0] && icm[1] > chip_chop[1]);
+}
+
+#[test]
+fn test_icm_deal_is_capped_at_max_players() {
+    for (entrants, allowed) in [(MAX_ICM_DEAL_PLAYERS, true), (MAX_ICM_DEAL_PLAYERS + 1, false)] {
+        let mut svm = setup_svm();
+        let mut sng = SitAndGo::create(&mut svm, entrants as u8, vec![5_000, 3_000, 2_000]);
+        sng.register_all(&mut svm, entrants);
+
+        assert_eq!(sng.propose_deal(&mut svm, 0, DealKind::Icm).is_ok(), allowed);
+        if allowed {
+            // Equal stacks are worth an equal share of the pool
+            let shares = sng.deal(&svm).shares;
+            assert_eq!(shares.iter().map(|share| share.amount).sum::<u64>(), entrants as u64 * SOL);
+            assert!(shares.iter().all(|share| share.amount.abs_diff(SOL) <= entrants as u64));
+        } else {
+            // A chip chop is cheap at any size
+            sng.propose_deal(&mut svm, 0, DealKind::ChipChop).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_chip_chop_deal_ends_tournament_and_table_closes() {
+    let mut svm = setup_svm();
+    let mut sng = SitAndGo::create(&mut svm, 2, vec![6_500, 3_500]);
+    sng.register_all(&mut svm, 2);
+
+    // Even stacks: a chip chop splits the whole 2 SOL pool down the middle
+    sng.propose_deal(&mut svm, 0, DealKind::ChipChop).unwrap();
+    let (player2, _) = (&sng.players[1].0.insecure_clone(), sng.players[1].1);
+    svm.send_instruction(
+        build_accept_deal_ix(sng.tournament, sng.players[0].0.pubkey(), player2, sng.deal_accounts()),
+        &[player2],
+    ).unwrap().assert_success();
+
+    let tournament = sng.state(&svm);
+    assert_eq!(tournament.status, TournamentStatus::Finished);
+    assert!(tournament.deal_made);
+    assert_eq!(tournament.entries[0].deal_payout, Some(SOL));
+    assert_eq!(tournament.entries[1].deal_payout, Some(SOL));
+    let deal_account = svm.get_account(&derive_deal_pda(&sng.tournament).0);
+    assert!(deal_account.is_none_or(|account| account.data.is_empty()));
+
+    let balance_before = svm.get_balance(&player2.pubkey()).unwrap();
+    svm.send_instruction(build_claim_tournament_payout_ix(sng.tournament, player2), &[player2]).unwrap().assert_success();
+    let balance_after = svm.get_balance(&player2.pubkey()).unwrap();
+    assert!(balance_after > balance_before + SOL - SOL / 100);
+
+    // Everyone still seated stands up and the final table closes
+    for (player, player_pda) in &sng.players {
+        let mut leave = build_leave_table_ix(sng.table, *player_pda, player);
+        leave.accounts.push(AccountMeta::new_readonly(sng.tournament, false));
+        svm.send_instruction(leave, &[player]).unwrap().assert_success();
+    }
+    svm.send_instruction(
+        build_close_table_ix(sng.table, sng.creator.pubkey(), &sng.creator, vec![sng.tournament, sng.blind_structure]),
+        &[&sng.creator],
+    ).unwrap().assert_success();
+}
+
 // Helper function to display test results
 #[allow(dead_code)]
 fn print_test_summary() {
@@ -774,14 +4267,116 @@ fn print_test_summary() {
     println!("  - Initialize table");
     println!("  - Join table");
     println!("  - Player actions");
+    println!("  - Buy-ins and top-ups are escrowed by the table");
+    println!("  - Top-ups can't exceed the max buy-in");
+    println!("  - Top-ups are rejected while a hand is running");
+    println!("  - Sitting-out players are dealt out and owe the missed blind");
+    println!("  - Blinds skip a player sitting out");
+    println!("  - Sitting in waits for the hand to end");
+    println!("  - Returning players post the missed blind dead");
+    println!("  - Leaving mid-hand folds and cashes out the stack");
+    println!("  - Leaving mid-hand holds the seat until the hand ends");
+    println!("  - Forfeited players close their account after the hand");
+    println!("  - Waitlist seat reservation");
+    println!("  - Waitlist is only for full tables");
+    println!("  - Waitlisted player takes a seat freed by a hand");
+    println!("  - Private table waitlist needs an invite");
+    println!("  - Private tables seat invited players");
+    println!("  - Private tables refuse uninvited players");
+    println!("  - Only the table authority invites players");
+    println!("  - Revoked invites no longer seat players");
+    println!("  - Only the table authority updates config");
+    println!("  - Config updates change only the fields given");
+    println!("  - Config updates keep blinds ordered");
+    println!("  - Config can't change while a hand is running");
+    println!("  - Close table");
+    println!("  - Close table returns hand history and invite rent");
+    println!("  - Only the table admin pauses");
+    println!("  - Emergency refund needs a paused table");
+    println!("  - Paused tables reject actions");
+    println!("  - Emergency refund returns committed chips");
+    println!("  - Resumed tables start a clean hand");
+    println!("  - Tables can't have more seats than MAX_PLAYERS");
+    println!("  - Heads-up tables have only two seats");
+    println!("  - Heads-up tables reject seats past the last");
+    println!("  - Heads-up button posts the small blind and acts first");
+    println!("  - Heads-up button alternates between seats");
+    println!("  - No rake from hands that end preflop");
+    println!("  - Rake is taken once a flop is seen");
+    println!("  - Only the rake authority withdraws rake");
+    println!("  - Table authority can't take over the rake");
+    println!("  - Rake withdrawals leave the vault's rent");
+    println!("  - Rakeback needs every contributor's state");
+    println!("  - Rakeback follows contributed rake");
+    println!("  - Claimed rakeback can't be claimed again");
+    println!("  - Player stats creation is reported");
+    println!("  - Rakeback needs every stats address");
+    println!("  - Rakeback skips players without stats");
+    println!("  - Hand history must be passed once opened");
+    println!("  - Hand history records blinds, actions, showdown and payout");
+    println!("  - Hand history records no showdown for an uncontested pot");
+    println!("  - Hand history deals in only players not sitting out");
+    println!("  - Each player's ante is reported");
+    println!("  - Tournament settings are reported");
+    println!("  - Table creation is reported");
+    println!("  - Joins are reported");
+    println!("  - Each blind reports the pot");
+    println!("  - Actions report what went in and the pot");
+    println!("  - Street advances are reported");
+    println!("  - Hand completion reports the payout");
+    println!("  - Legal actions view needs a hand in progress");
+    println!("  - Legal actions view only answers for the player to act");
+    println!("  - Legal actions view reports the call and raise range");
+    println!("  - Legal actions view matches the off-chain answer");
+    println!("  - Crank timeout is rejected before the timeout");
+    println!("  - Crank timeout needs every dealt-in player state");
+    println!("  - Crank timeout folds a player facing a bet");
+    println!("  - Action timeout is capped");
     println!(" - Game Flow Tests");
     println!("  - Start hand");
     println!("  - Player fold");
     println!("\n- New Game Logic Tests");
     println!("  - Blind posting with bitmask");
     println!("  - Raise validation with min-raise");
+    println!("  - All-in short of a min raise is too small");
     println!("  - Check auto-win detection");
+    println!("  - Check auto-win pays a hand once");
     println!("  - Advance street auto validation");
     println!("  - Start hand player state reset");
+    println!("  - Player states rewritten in place");
+    println!("  - First hand plays at the first blind level");
+    println!("  - Blind structure moves up a level on schedule");
+    println!("  - Antes are collected when the hand starts");
+    println!("  - Short stack posts blind all in after ante");
+    println!("  - Antes are not taken from another table's player");
+    println!("  - Antes are not taken twice from a duplicated player");
+    println!("  - Antes need every dealt-in player's state");
+    println!("  - Replace and remove blind structure");
+    println!("\n- Tournament Tests");
+    println!("  - Sit-and-go starts when registration fills");
+    println!("  - Tournaments are created by the table authority");
+    println!("  - Unregistering refunds the entry");
+    println!("  - Cancelling refunds every entry");
+    println!("  - Busted player blocks the next hand until eliminated");
+    println!("  - Tournament hands need every player state");
+    println!("  - Tournament hands check the states they are given");
+    println!("  - Sit-and-go winner claims the prize pool");
+    println!("  - Auto-win records busts from short all-in calls");
+    println!("  - Multi-table registration stays balanced");
+    println!("  - Registration needs a seat for every entrant");
+    println!("  - Breaking a table moves players and chips");
+    println!("  - Tables share the tournament level clock");
+    println!("  - Progressive knockout bounties");
+    println!("  - Standard bounties");
+    println!("  - Bust can't be skipped by passing the winner twice");
+    println!("  - Bounty configuration before registration");
+    println!("  - Knockout winner payout");
+    println!("  - Rebuys and add-ons");
+    println!("  - Elimination waits for the rebuy decision");
+    println!("  - Declining a rebuy");
+    println!("  - Rebuys need a fee");
+    println!("  - ICM deals against chip chops");
+    println!("  - ICM deal size cap");
+    println!("  - Chip-chop deal and closing the final table");
     println!("\nAll poker functionality verified!");
 }